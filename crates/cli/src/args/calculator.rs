@@ -0,0 +1,95 @@
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct CalcArgs {
+    /// Mathematical expression to evaluate (if not provided, reads from stdin).
+    /// Multiple lines are evaluated in order and can share variables; a line that
+    /// ends with an operator continues on the next, and a line starting with a
+    /// binary operator like * continues the one before. An error names its line,
+    /// after the results of the lines before it.
+    /// Without an expression on a terminal, starts an interactive session.
+    #[arg(allow_hyphen_values = true)]
+    pub expression: Option<String>,
+
+    /// Show the integer result in another base
+    #[arg(short = 'b', long, conflicts_with = "group")]
+    pub base: Option<OutputBase>,
+
+    /// Bit width for two's-complement display of negative numbers (1-128)
+    #[arg(short = 'w', long, requires = "base", value_parser = clap::value_parser!(u32).range(1..=128))]
+    pub bits: Option<u32>,
+
+    /// Use exact decimal arithmetic (0.1 + 0.2 = 0.3) instead of floating point
+    #[arg(short = 'e', long)]
+    pub exact: bool,
+
+    /// Significant digits for non-terminating results in exact mode; use
+    /// --sig-figs to round the printed result instead
+    #[arg(short = 'd', long, requires = "exact", conflicts_with = "sig_figs", default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub digits: u32,
+
+    #[command(flatten)]
+    pub format: NumberFormatArgs,
+
+    /// Units file (TOML or JSON) with extra units for unit arithmetic
+    /// [default: ~/.config/strapd/units.toml, if it exists]
+    #[arg(long, value_name = "PATH")]
+    pub units_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputBase {
+    /// Decimal
+    Dec,
+    /// Hexadecimal (0x prefix)
+    Hex,
+    /// Binary (0b prefix)
+    Bin,
+    /// Octal (0o prefix)
+    Oct,
+    /// All of the above, one per line
+    All,
+}
+
+/// Notation, rounding and digit grouping of results, shared by calc and convert
+#[derive(Args, Debug, Clone)]
+pub struct NumberFormatArgs {
+    /// Number of decimal places (0-10)
+    #[arg(short = 'p', long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(0..=10))]
+    pub precision: Option<usize>,
+
+    /// Round to this many significant figures
+    #[arg(short = 's', long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=30))]
+    pub sig_figs: Option<u32>,
+
+    /// Notation of numbers
+    #[arg(short = 'n', long, value_enum, default_value_t = Notation::Fixed)]
+    pub notation: Notation,
+
+    /// Separate groups of three integer digits with _ or ,
+    #[arg(short = 'g', long, value_name = "SEP")]
+    pub group: Option<Separator>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Notation {
+    /// Plain decimal (0.00042)
+    Fixed,
+    /// Scientific notation (4.2e-4)
+    #[value(alias = "sci")]
+    Scientific,
+    /// Engineering notation, exponent a multiple of 3 (420e-6)
+    #[value(alias = "eng")]
+    Engineering,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Separator {
+    /// Underscore (1_536_000)
+    #[value(name = "_", alias = "underscore")]
+    Underscore,
+    /// Comma (1,536,000)
+    #[value(name = ",", alias = "comma")]
+    Comma,
+}
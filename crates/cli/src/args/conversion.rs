@@ -1,7 +1,13 @@
-use clap::Args;
+use super::calculator::NumberFormatArgs;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct ConvertArgs {
+    #[command(subcommand)]
+    pub operation: Option<ConvertOperation>,
+
     /// Conversion expression like "10 km to mi" (or reads from stdin)
     /// Quote the expression: strapd convert "10 km to mi"
     /// Or use unquoted: strapd convert 10 km to mi
@@ -12,7 +18,63 @@ pub struct ConvertArgs {
     #[arg(short = 'a', long)]
     pub all: bool,
 
-    /// Number of decimal places (0-10)
-    #[arg(short = 'p', long)]
-    pub precision: Option<usize>,
+    /// Show the result in the most readable unit (same as "to auto")
+    #[arg(long, conflicts_with = "all")]
+    pub human: bool,
+
+    #[command(flatten)]
+    pub format: NumberFormatArgs,
+
+    /// Root font size in px for rem and em (or @base=18 in the expression)
+    #[arg(long, value_name = "PX", default_value_t = 16.0)]
+    pub root_font_size: f64,
+
+    /// Viewport width in px for vw (or @vw=1440 in the expression)
+    #[arg(long, value_name = "PX", default_value_t = 1920.0)]
+    pub viewport_width: f64,
+
+    /// Viewport height in px for vh (or @vh=900 in the expression)
+    #[arg(long, value_name = "PX", default_value_t = 1080.0)]
+    pub viewport_height: f64,
+
+    /// Pixels per inch for pt and pc (or @dpi=300 in the expression)
+    #[arg(long, default_value_t = 96.0)]
+    pub dpi: f64,
+
+    /// Units file (TOML or JSON) with extra units and categories
+    /// [default: ~/.config/strapd/units.toml, if it exists]
+    #[arg(long, value_name = "PATH")]
+    pub units_file: Option<PathBuf>,
+
+    /// Currency rates file (JSON or CSV) with a base currency and a date
+    /// [default: ~/.config/strapd/rates.json or rates.csv, if it exists]
+    #[arg(long, value_name = "PATH")]
+    pub rates_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConvertOperation {
+    /// Show numbers in the most readable unit, one per line
+    ///
+    /// Bare numbers are read in --unit; lines may also carry their own unit.
+    /// Lines that cannot be read are reported on stderr and passed through
+    /// unchanged, and the command exits with an error at the end.
+    /// Example: du -b * | cut -f1 | strapd convert humanize
+    Humanize {
+        /// Newline-separated numbers (if not provided, reads from stdin)
+        #[arg(allow_hyphen_values = true)]
+        input: Option<String>,
+
+        /// Unit of bare numbers
+        #[arg(short = 'u', long, default_value = "B")]
+        unit: String,
+
+        #[command(flatten)]
+        format: NumberFormatArgs,
+
+        /// Units file (TOML or JSON) with extra units and categories
+        /// [default: ~/.config/strapd/units.toml, if it exists]
+        #[arg(long, value_name = "PATH")]
+        units_file: Option<PathBuf>,
+    },
 }
@@ -0,0 +1,26 @@
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct CronArgs {
+    /// Cron expression, quoted or as separate words (if not provided, reads from stdin)
+    pub expression: Vec<String>,
+
+    /// Number of upcoming runs to show; 0 only explains the expression
+    #[arg(short = 'n', long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(0..=1000))]
+    pub count: u32,
+
+    /// Timezone the schedule runs in: IANA name, UTC or an offset like +05:30
+    /// [default: local timezone]
+    #[arg(short = 'z', long, value_name = "ZONE")]
+    pub tz: Option<String>,
+
+    /// Format of the run times: human, iso, rfc2822, http, sql, compact, date,
+    /// relative or a strftime pattern [default: human]
+    #[arg(short = 'f', long, value_name = "FORMAT")]
+    pub format: Option<String>,
+
+    /// Show runs after this instant: "now", a Unix timestamp or a date string
+    /// [default: now]
+    #[arg(long, value_name = "INSTANT")]
+    pub from: Option<String>,
+}
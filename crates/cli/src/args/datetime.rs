@@ -1,37 +1,285 @@
-use clap::{Subcommand, ValueEnum};
+use clap::{Args, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Subcommand, Debug)]
 pub enum TimeOperation {
     /// Get current Unix timestamp
     Now {
         /// Output in milliseconds
-        #[arg(short = 'm', long)]
+        #[arg(short = 'm', long, conflicts_with = "unit")]
         millis: bool,
 
-        /// Format the output to human readable string
-        #[arg(short = 'f', long)]
-        format: Option<TimestampFormat>,
+        /// Unit of the output
+        #[arg(short = 'u', long, default_value = "s")]
+        unit: TimestampUnit,
+
+        /// Format the output to human readable string: human, iso, rfc2822, http,
+        /// sql, compact, date, relative or a strftime pattern like "%Y-%m-%d %H:%M"
+        #[arg(short = 'f', long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Timezone of the formatted output: IANA name, UTC or an offset like +05:30
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
     },
 
     /// Convert timestamp to human-readable date
+    ///
+    /// Seconds, milliseconds, microseconds and nanoseconds are told apart by the
+    /// magnitude of the timestamp unless --unit is given.
     From {
         /// Unix timestamp (reads from stdin if not provided)
         timestamp: Option<i64>,
 
+        /// Input is in milliseconds (same as --unit ms)
+        #[arg(short = 'm', long, conflicts_with = "unit")]
+        millis: bool,
+
+        /// Unit of the input [default: guessed from the magnitude]
+        #[arg(short = 'u', long)]
+        unit: Option<TimestampUnit>,
+
+        /// Format of the datetime output: human, iso, rfc2822, http, sql, compact,
+        /// date, relative or a strftime pattern like "%Y-%m-%d %H:%M" [default: human]
+        #[arg(short = 'f', long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Timezone of the output: IANA name (America/New_York), UTC or an offset
+        /// like +05:30 [default: local timezone]
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
+    },
+
+    /// Convert a date string to a Unix timestamp
+    ///
+    /// Detects RFC 3339, RFC 2822, HTTP dates, common log formats and plain dates
+    /// like 2025-03-04 10:00. Example: strapd time to "Tue, 04 Mar 2025 10:00:00 GMT"
+    #[command(alias = "parse")]
+    To {
+        /// Date string (reads from stdin if not provided)
+        input: Option<String>,
+
+        /// Output in milliseconds
+        #[arg(short = 'm', long)]
+        millis: bool,
+
+        /// strptime pattern of the input, e.g. "%d.%m.%Y %H:%M" [default: detect]
+        #[arg(short = 'f', long, value_name = "PATTERN")]
+        format: Option<String>,
+
+        /// Timezone of inputs without an offset: IANA name, UTC or an offset
+        /// like +05:30 [default: local timezone]
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
+    },
+
+    /// Add a duration to an instant
+    ///
+    /// Example: strapd time add now 90d, strapd time add 1741075200 "1mo 2h"
+    #[command(alias = "plus")]
+    Add(ShiftArgs),
+
+    /// Subtract a duration from an instant
+    ///
+    /// Example: strapd time sub "2025-03-31" 1mo
+    #[command(alias = "minus")]
+    Sub(ShiftArgs),
+
+    /// Show the time between two instants
+    ///
+    /// Example: strapd time diff 1741075200 "2025-06-01T00:00:00Z"
+    #[command(aliases = ["between", "until"])]
+    Diff {
+        /// Start: "now", a Unix timestamp or a date string
+        from: String,
+
+        /// End: "now", a Unix timestamp or a date string
+        to: String,
+
+        /// Unix timestamps are in milliseconds
+        #[arg(short = 'm', long)]
+        millis: bool,
+
+        /// Show the total in one unit instead of a compound duration
+        #[arg(short = 'u', long, conflicts_with = "calendar")]
+        unit: Option<DurationUnit>,
+
+        /// Count whole calendar months and days instead of 24-hour days
+        #[arg(short = 'c', long)]
+        calendar: bool,
+
+        /// Timezone of date strings without an offset and of calendar days
+        /// [default: local timezone]
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
+    },
+
+    /// Show how long ago an instant was, or how far ahead it is
+    ///
+    /// Example: strapd time ago 1741075200, strapd time ago "2026-01-01" -p 2
+    #[command(alias = "relative")]
+    Ago {
+        /// "now", a Unix timestamp or a date string (reads from stdin if not provided)
+        instant: Option<String>,
+
+        /// Unix timestamps are in milliseconds (same as --unit ms)
+        #[arg(short = 'm', long, conflicts_with = "unit")]
+        millis: bool,
+
+        /// Unit of Unix timestamps [default: guessed from the magnitude]
+        #[arg(short = 'u', long)]
+        unit: Option<TimestampUnit>,
+
+        /// Instant to measure from: "now", a Unix timestamp or a date string
+        /// [default: now]
+        #[arg(short = 's', long, value_name = "INSTANT")]
+        since: Option<String>,
+
+        /// Most units to show, e.g. 2 for "1 day, 3 hours ago"
+        #[arg(short = 'p', long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=7))]
+        parts: u32,
+
+        /// Smallest unit to show
+        #[arg(short = 'g', long, default_value = "second")]
+        granularity: Granularity,
+
+        /// Timezone of date strings without an offset and of calendar days
+        /// [default: local timezone]
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
+    },
+
+    /// Show one instant in several timezones
+    ///
+    /// Zones come from --tz, else ~/.config/strapd/zones.txt (one per line),
+    /// else a default set. Example: strapd time world --tz UTC,Europe/Berlin,+05:30
+    #[command(aliases = ["clock", "zones"])]
+    World {
+        /// Unix timestamp [default: now]
+        timestamp: Option<i64>,
+
         /// Input is in milliseconds
         #[arg(short = 'm', long)]
         millis: bool,
 
-        /// Format of the datetime output
-        #[arg(short = 'f', long)]
-        format: Option<TimestampFormat>,
+        /// Format of the datetime output: human, iso, rfc2822, http, sql, compact,
+        /// date, relative or a strftime pattern like "%Y-%m-%d %H:%M" [default: human]
+        #[arg(short = 'f', long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Timezones to show, comma separated or repeated
+        #[arg(short = 'z', long, value_name = "ZONE", value_delimiter = ',')]
+        tz: Vec<String>,
+
+        /// File with timezones to show, one per line
+        #[arg(long, value_name = "PATH", conflicts_with = "tz")]
+        zones_file: Option<PathBuf>,
     },
+
+    /// Make timestamps in text readable, e.g. in log lines
+    ///
+    /// Finds Unix timestamps from 1990 to 2100 in seconds, milliseconds, microseconds
+    /// or nanoseconds and ISO 8601 date-times, and adds a rendering after each.
+    /// Reads stdin line by line, so it works behind tail -f.
+    /// Example: kubectl logs app | strapd time annotate -z UTC -k ts,time
+    #[command(alias = "humanize")]
+    Annotate {
+        /// Text to annotate (reads stdin line by line if not provided)
+        input: Option<String>,
+
+        /// Format of the rendering: human, iso, rfc2822, http, sql, compact, date,
+        /// relative or a strftime pattern like "%Y-%m-%d %H:%M" [default: human]
+        #[arg(short = 'f', long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Timezone of the rendering and of ISO date-times without an offset
+        /// [default: local timezone]
+        #[arg(short = 'z', long, value_name = "ZONE")]
+        tz: Option<String>,
+
+        /// Replace timestamps with the rendering instead of appending it
+        #[arg(short = 'r', long)]
+        replace: bool,
+
+        /// Only annotate values of these JSON keys, comma separated or repeated
+        #[arg(short = 'k', long = "key", value_name = "KEY", value_delimiter = ',')]
+        keys: Vec<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ShiftArgs {
+    /// Instant: "now", a Unix timestamp or a date string
+    pub instant: String,
+
+    /// Duration: 90d, 1h30m, "1y 2mo", P1M, or seconds like 3600; years, months,
+    /// weeks and days follow the calendar
+    #[arg(allow_hyphen_values = true)]
+    pub duration: String,
+
+    /// Unix timestamps, in and out, are in milliseconds
+    #[arg(short = 'm', long)]
+    pub millis: bool,
+
+    /// Format of the result: human, iso, rfc2822, http, sql, compact, date,
+    /// relative or a strftime pattern [default: human]
+    #[arg(short = 'f', long, value_name = "FORMAT", conflicts_with = "epoch")]
+    pub format: Option<String>,
+
+    /// Print the result as a Unix timestamp
+    #[arg(short = 'e', long)]
+    pub epoch: bool,
+
+    /// Timezone for date strings without an offset, calendar days and the output
+    /// [default: local timezone]
+    #[arg(short = 'z', long, value_name = "ZONE")]
+    pub tz: Option<String>,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
-pub enum TimestampFormat {
-    /// Human-readable format (YYYY-MM-DD HH:MM:SS UTC)
-    Human,
-    /// ISO 8601 / RFC 3339 format
-    Iso,
+pub enum DurationUnit {
+    #[value(alias = "milliseconds")]
+    Ms,
+    #[value(aliases = ["sec", "seconds"])]
+    S,
+    #[value(aliases = ["min", "minutes"])]
+    M,
+    #[value(aliases = ["hours"])]
+    H,
+    #[value(aliases = ["days"])]
+    D,
+    #[value(aliases = ["weeks"])]
+    W,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum TimestampUnit {
+    /// Seconds
+    #[value(alias = "sec")]
+    S,
+    /// Milliseconds
+    Ms,
+    /// Microseconds
+    #[value(alias = "µs")]
+    Us,
+    /// Nanoseconds
+    Ns,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Granularity {
+    #[value(aliases = ["y", "years"])]
+    Year,
+    #[value(aliases = ["mo", "months"])]
+    Month,
+    #[value(aliases = ["w", "weeks"])]
+    Week,
+    #[value(aliases = ["d", "days"])]
+    Day,
+    #[value(aliases = ["h", "hours"])]
+    Hour,
+    #[value(aliases = ["m", "min", "minutes"])]
+    Minute,
+    #[value(aliases = ["s", "sec", "seconds"])]
+    Second,
 }
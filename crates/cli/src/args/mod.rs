@@ -1,12 +1,15 @@
 use clap::{Parser, Subcommand};
 
+pub mod calculator;
 pub mod conversion;
+pub mod cron;
 pub mod data_formats;
 pub mod datetime;
 pub mod encoding;
 pub mod identifiers;
 pub mod random;
 pub mod security;
+pub mod statistics;
 pub mod string;
 
 #[derive(Parser, Debug)]
@@ -78,18 +81,56 @@ pub enum Commands {
         #[clap(subcommand)]
         operation: datetime::TimeOperation,
     },
+    /// Explain a cron expression and show its next runs
+    ///
+    /// Takes 5 fields (minute hour day-of-month month day-of-week), 6 with seconds
+    /// first, or 7 with a year as in Quartz, and macros like @daily. Supports names
+    /// (JAN, MON), L (last), W (nearest weekday) and # (nth weekday).
+    /// Example: strapd cron "0 9 * * MON-FRI" -n 3 --tz Europe/Berlin
+    #[command(aliases = ["crontab", "schedule"])]
+    Cron(cron::CronArgs),
     Copy {
         /// The string to copy (if not provided, reads from stdin)
         input: Option<String>,
     },
     Paste {},
     /// Evaluate a mathematical expression
+    ///
+    /// Supports + - * / % ^, parentheses, functions (sqrt, abs, floor, ceil, round,
+    /// ln, log10, log2, exp, sin, cos, tan, asin, acos, atan, atan2, min, max)
+    /// and constants (pi, e, tau). Example: strapd calc "round(sqrt(2) * pi, 3)"
+    ///
+    /// Integers can be written as 0x1f, 0b1010, 0o17 or 1_000_000, and combined
+    /// with bitwise & | xor ~ << >>. Example: strapd calc "0xff & ~0x0f" --base hex
+    ///
+    /// Assign variables with `name = expr` and reuse the previous result as `ans` or `_`.
+    ///
+    /// Numbers can carry units and be converted with a `to` suffix.
+    /// Example: strapd calc "2 GiB / 30 s to MB/s"
     #[command(aliases = ["calculate", "eval"])]
-    Calc {
-        /// Mathematical expression to evaluate (if not provided, reads from stdin)
-        expression: Option<String>,
-    },
+    Calc(calculator::CalcArgs),
     /// Convert between units (length, bytes, time, temperature, data rate)
+    ///
+    /// Supports arithmetic over units. Example: strapd convert "1 h + 45 min to s"
+    ///
+    /// Byte units: kB, MB, GB are powers of 1000 and KiB, MiB, GiB are powers of 1024;
+    /// b is a bit and B is a byte, so Mb and mb are megabits. IEC units are bytes in
+    /// any case, so gib and GIB are both gibibytes.
+    ///
+    /// Durations: strapd convert "1h30m to min", "PT1H30M to s", "5415 s to human" or "to iso"
+    /// Mixed units: strapd convert "5 ft 11 in to cm" or "180 cm to ft+in"
+    /// Typography: strapd convert "24px to rem @base=18" (also @vw, @vh and @dpi)
+    /// Readable units: strapd convert "1536000 B to auto" or --human; numbers from stdin
+    /// with: strapd convert humanize --unit s
+    ///
+    /// Extra units and categories are read from ~/.config/strapd/units.toml or --units-file.
+    /// Currencies convert offline with rates from ~/.config/strapd/rates.json or --rates-file.
     #[command(aliases = ["conv", "cv"])]
     Convert(conversion::ConvertArgs),
+    /// Descriptive statistics for numbers read from stdin
+    ///
+    /// Reports count, sum, min, max, mean, median, standard deviation and variance.
+    /// Example: cat latencies.txt | strapd stats -p 50,95,99 --histogram
+    #[command(aliases = ["stat", "statistics"])]
+    Stats(statistics::StatsArgs),
 }
@@ -0,0 +1,20 @@
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Whitespace- or newline-separated numbers (if not provided, reads from stdin)
+    #[arg(allow_hyphen_values = true)]
+    pub input: Option<String>,
+
+    /// Percentiles to report, comma separated (e.g. 50,95,99)
+    #[arg(short = 'p', long, value_delimiter = ',')]
+    pub percentiles: Vec<f64>,
+
+    /// Show an ASCII histogram of the values
+    #[arg(short = 'H', long)]
+    pub histogram: bool,
+
+    /// Number of histogram bins
+    #[arg(short = 'b', long, default_value_t = 10, requires = "histogram", value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub bins: u32,
+}
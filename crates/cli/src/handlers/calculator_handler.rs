@@ -1,8 +1,241 @@
-use crate::handlers::{CommandResult, get_input_string, text_result};
-use strapd_core::calculator;
+use crate::{
+    args::calculator::{CalcArgs, Notation, NumberFormatArgs, OutputBase, Separator},
+    handlers::{CommandResult, conversion_handler, get_input_string, text_result},
+};
+use rustyline::{DefaultEditor, error::ReadlineError};
+use std::io::{self, IsTerminal};
+use strapd_core::{
+    calculator::{self, CalcError, CalcErrorKind, Context, NumberFormat, Radix},
+    conversion,
+};
 
-pub fn handle(expression: &Option<String>) -> CommandResult {
-    let expr = get_input_string(expression);
-    let result = calculator::evaluate(&expr)?;
-    text_result(result)
+pub fn handle(args: &CalcArgs) -> CommandResult {
+    conversion_handler::load_units_file(args.units_file.as_deref())?;
+
+    // No expression on an interactive terminal starts a session
+    if args.expression.is_none() && io::stdin().is_terminal() {
+        return run_interactive(args);
+    }
+
+    let input = get_input_string(&args.expression);
+    let mut context = new_context(args);
+
+    // Each statement is evaluated in order, sharing variables and `ans`
+    let lines = statements(&input);
+    match lines.as_slice() {
+        [] => return text_result(evaluate_line(&mut context, &input, args)?),
+        [(_, line)] => return text_result(evaluate_line(&mut context, line, args)?),
+        _ => {}
+    }
+
+    let mut results = Vec::with_capacity(lines.len());
+    for (number, line) in &lines {
+        match evaluate_line(&mut context, line, args) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                // Keep the results computed so far; the error goes to stderr after them
+                if !results.is_empty() {
+                    println!("{}", results.join("\n"));
+                }
+                return Err(format!("Line {}: {}", number, e));
+            }
+        }
+    }
+    text_result(results.join("\n"))
+}
+
+/// Split input into one statement per non-empty line, joining continuation lines,
+/// each with the number of the line it starts on
+///
+/// A line continues the statement before it when the statement ends with an operator
+/// or leaves a parenthesis open, so `1 +\n2` is still `3`. A line may also continue
+/// the statement by starting with a binary-only operator; `-`, `+` and `~` may start
+/// a new statement, so `5\n-3` stays two statements.
+fn statements(input: &str) -> Vec<(usize, String)> {
+    const OPERATORS: &[char] = &['+', '-', '*', '/', '%', '^', '&', '|', '<', '>'];
+    const BINARY_OPERATORS: &[char] = &['*', '/', '%', '^', '&', '|', '<', '>'];
+
+    let mut statements: Vec<(usize, String)> = Vec::new();
+    let lines = input
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+    for (index, line) in lines {
+        match statements.last_mut() {
+            Some((_, statement))
+                if line.starts_with(BINARY_OPERATORS)
+                    || line.starts_with(')')
+                    || statement.ends_with(OPERATORS)
+                    || statement.ends_with(['(', ','])
+                    || statement.matches('(').count() > statement.matches(')').count() =>
+            {
+                statement.push(' ');
+                statement.push_str(line);
+            }
+            _ => statements.push((index + 1, line.to_string())),
+        }
+    }
+    statements
+}
+
+fn new_context(args: &CalcArgs) -> Context {
+    let context = if args.exact {
+        Context::exact(args.digits as usize)
+    } else {
+        Context::new()
+    };
+    context.with_format(number_format(&args.format))
+}
+
+/// Notation, rounding and grouping from the shared `calc` and `convert` flags
+pub fn number_format(args: &NumberFormatArgs) -> NumberFormat {
+    NumberFormat {
+        notation: match args.notation {
+            Notation::Fixed => calculator::Notation::Fixed,
+            Notation::Scientific => calculator::Notation::Scientific,
+            Notation::Engineering => calculator::Notation::Engineering,
+        },
+        decimals: args.precision,
+        significant_figures: args.sig_figs.map(|n| n as usize),
+        separator: args.group.map(|separator| match separator {
+            Separator::Underscore => '_',
+            Separator::Comma => ',',
+        }),
+    }
+}
+
+fn evaluate_line(context: &mut Context, line: &str, args: &CalcArgs) -> Result<String, String> {
+    match args.base {
+        Some(base) => {
+            let value = context
+                .evaluate_integer(line)
+                .map_err(|error| describe_error(line, &error))?;
+            format_in_base(value, base, args.bits)
+        }
+        None => context.evaluate(line).or_else(|error| {
+            // An expression that failed while being evaluated was understood, so its
+            // error stands even when it mentions a unit name like `min`
+            if matches!(error.kind(), CalcErrorKind::Evaluation(_)) {
+                return Err(describe_error(line, &error));
+            }
+            evaluate_with_units(context, line, &error, &number_format(&args.format))
+                .unwrap_or_else(|| Err(describe_error(line, &error)))
+        }),
+    }
+}
+
+/// Render an error followed by the expression and a `^` marker under the offending token
+fn describe_error(line: &str, error: &CalcError) -> String {
+    let Some(span) = error.span() else {
+        return error.to_string();
+    };
+
+    // Keep tabs so the marker lines up with the echoed expression
+    let padding: String = line[..span.start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let width = line[span].chars().count().max(1);
+    format!("{}\n  {}\n  {}{}", error, line, padding, "^".repeat(width))
+}
+
+/// Evaluate expressions with units such as "2 GiB / 30 s to MB/s", sharing the
+/// context's variables and `ans`
+///
+/// Returns `None` when the line neither mentions a unit nor uses a unit result,
+/// so the calculator error is reported instead, unless it looks like a misspelled
+/// unit (`5 kms to m`).
+fn evaluate_with_units(
+    context: &mut Context,
+    line: &str,
+    error: &CalcError,
+    format: &NumberFormat,
+) -> Option<Result<String, String>> {
+    if !matches!(error.kind(), CalcErrorKind::UnitValue(_))
+        && !conversion::expression::contains_units(context, line)
+    {
+        return conversion::expression::misspelled_unit(line, error)
+            .map(|error| Err(describe_error(line, &error)));
+    }
+    // The conversion formatter renders `to human`, `to iso` and compound targets
+    Some(
+        conversion::expression::evaluate_in(context, line)
+            .map_err(|error| describe_error(line, &error))
+            .and_then(|result| conversion::formatter::format_output_with(&[result], format)),
+    )
+}
+
+/// Read-evaluate-print loop with line editing and history.
+///
+/// Besides expressions and assignments, accepts `vars` to list variables,
+/// `clear` to forget them, and `exit` / `quit` (or Ctrl-D) to leave.
+fn run_interactive(args: &CalcArgs) -> CommandResult {
+    let mut editor =
+        DefaultEditor::new().map_err(|e| format!("Failed to start interactive session: {}", e))?;
+    let mut context = new_context(args);
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(e) => return Err(format!("Failed to read input: {}", e)),
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        // History is best-effort; a failure here should not end the session
+        let _ = editor.add_history_entry(line);
+
+        match line {
+            "exit" | "quit" => break,
+            "vars" => {
+                for (name, value) in context.variables() {
+                    println!("{} = {}", name, value);
+                }
+            }
+            "clear" => context.clear(),
+            _ => match evaluate_line(&mut context, line, args) {
+                Ok(result) => println!("{}", result),
+                Err(e) => eprintln!("Error: {}", e),
+            },
+        }
+    }
+
+    // Results were printed as they came, so there is nothing left to show
+    text_result(String::new())
+}
+
+fn format_in_base(value: i128, base: OutputBase, bits: Option<u32>) -> Result<String, String> {
+    match convert_base(base) {
+        Some(radix) => calculator::format_radix(value, radix, bits),
+        None => {
+            let labelled = [
+                ("dec", Radix::Decimal),
+                ("hex", Radix::Hexadecimal),
+                ("oct", Radix::Octal),
+                ("bin", Radix::Binary),
+            ];
+            let lines = labelled
+                .iter()
+                .map(|(label, radix)| {
+                    calculator::format_radix(value, *radix, bits)
+                        .map(|formatted| format!("{} {}", label, formatted))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn convert_base(base: OutputBase) -> Option<Radix> {
+    match base {
+        OutputBase::Dec => Some(Radix::Decimal),
+        OutputBase::Hex => Some(Radix::Hexadecimal),
+        OutputBase::Bin => Some(Radix::Binary),
+        OutputBase::Oct => Some(Radix::Octal),
+        OutputBase::All => None,
+    }
 }
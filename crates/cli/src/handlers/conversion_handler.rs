@@ -1,21 +1,151 @@
 use crate::{
-    args::conversion::ConvertArgs,
-    handlers::{CommandResult, read_stdin_if_piped, text_result},
+    args::conversion::{ConvertArgs, ConvertOperation},
+    handlers::{
+        CommandResult, calculator_handler::number_format, config_file, get_input_string,
+        read_stdin_if_piped, text_result,
+    },
+};
+use std::{fs, path::Path};
+use strapd_core::{
+    calculator::{self, NumberFormat},
+    conversion,
 };
-use strapd_core::conversion;
 
 pub fn handle(args: &ConvertArgs) -> CommandResult {
-    let expression = build_expression(&args.expression)?;
-    let request = conversion::parser::parse_input(&expression)?;
+    if let Some(ConvertOperation::Humanize {
+        input,
+        unit,
+        format,
+        units_file,
+    }) = &args.operation
+    {
+        load_units_file(units_file.as_deref())?;
+        return humanize(&get_input_string(input), unit, &number_format(format));
+    }
+
+    load_units_file(args.units_file.as_deref())?;
+    load_rates_file(args.rates_file.as_deref())?;
+    let settings = conversion::typography::TypographySettings {
+        root_font_size: args.root_font_size,
+        viewport_width: args.viewport_width,
+        viewport_height: args.viewport_height,
+        dpi: args.dpi,
+    };
+    let (expression, settings) =
+        conversion::typography::extract_settings(&build_expression(&args.expression)?, settings)?;
+
+    // Arithmetic over units, e.g. "1 h + 45 min to s"
+    if conversion::expression::is_expression(&expression) {
+        let result = conversion::expression::evaluate(&expression)?;
+        let results = if args.all {
+            conversion::engine::convert_to_all_with(
+                result.output_value,
+                &result.output_unit,
+                &settings,
+            )?
+        } else if args.human {
+            vec![conversion::humanize::humanize(result)?]
+        } else {
+            vec![result]
+        };
+        let output =
+            conversion::formatter::format_output_with(&results, &number_format(&args.format))?;
+        return text_result(with_rates_date(output, &results));
+    }
+
+    let mut request = conversion::parser::parse_input(&expression)?;
 
     let results = if args.all {
-        conversion::engine::convert_to_all(request.value, &request.from_unit)?
+        conversion::engine::convert_to_all_with(request.value, &request.from_unit, &settings)?
+    } else if args.human && request.to_unit.is_some() {
+        // Scale within the target's units, e.g. "to MB" gives kB, MB or GB
+        let result = conversion::engine::convert_with(&request, &settings)?;
+        vec![conversion::humanize::humanize(result)?]
     } else {
-        vec![conversion::engine::convert(&request)?]
+        if args.human {
+            request.to_unit = Some("auto".to_string());
+        }
+        vec![conversion::engine::convert_with(&request, &settings)?]
+    };
+
+    let output = conversion::formatter::format_output_with(&results, &number_format(&args.format))?;
+    text_result(with_rates_date(output, &results))
+}
+
+/// Shows each line in the most readable unit; bare numbers are in `unit`
+///
+/// A line that cannot be read is reported on stderr with its number and passed
+/// through unchanged, so one bad line does not drop the rest of a stream. The
+/// command still fails once every line is written.
+fn humanize(input: &str, unit: &str, format: &NumberFormat) -> CommandResult {
+    let mut lines = Vec::new();
+    let mut failed = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match humanize_line(line, unit, format) {
+            Ok(output) => lines.push(output),
+            Err(e) => {
+                eprintln!("Line {}: {}", i + 1, e);
+                failed += 1;
+                lines.push(line.to_string());
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        return Err("No input provided. Provide numbers as argument or via stdin.".to_string());
+    }
+    let output = lines.join("\n");
+    if failed == 0 {
+        return text_result(output);
+    }
+    // The error only sets the exit status; the lines are written first
+    println!("{}", output);
+    Err(match failed {
+        1 => "1 line could not be converted".to_string(),
+        failed => format!("{} lines could not be converted", failed),
+    })
+}
+
+fn humanize_line(line: &str, unit: &str, format: &NumberFormat) -> Result<String, String> {
+    let request = match calculator::parse_literal(line) {
+        Ok(value) => conversion::types::ConversionRequest {
+            value,
+            from_unit: unit.to_string(),
+            to_unit: Some("auto".to_string()),
+        },
+        Err(_) => conversion::types::ConversionRequest {
+            to_unit: Some("auto".to_string()),
+            ..conversion::parser::parse_input(line)?
+        },
     };
+    let result = conversion::engine::convert(&request)?;
+    // One line per input, without the SI and IEC headings of --all
+    conversion::formatter::format_output_with(&[result], format)
+}
 
-    let output = conversion::formatter::format_output(&results, args.precision)?;
-    text_result(output)
+/// Appends the date of the rate table to currency results
+fn with_rates_date(output: String, results: &[conversion::types::ConversionResult]) -> String {
+    let is_currency = results.iter().any(|result| {
+        [&result.input_unit, &result.output_unit]
+            .into_iter()
+            .any(|unit| {
+                conversion::types::find_unit(unit)
+                    .is_some_and(|u| u.category == conversion::types::UnitCategory::Currency)
+            })
+    });
+    match conversion::currency::rates() {
+        Some(table) if is_currency => {
+            format!(
+                "{}\nRates as of {} (base {})",
+                output, table.date, table.base
+            )
+        }
+        _ => output,
+    }
 }
 
 /// Builds the conversion expression from stdin and/or command-line args.
@@ -48,3 +178,64 @@ fn build_expression(args: &[String]) -> Result<String, String> {
     stdin_content.push_str(&args_str);
     Ok(stdin_content)
 }
+
+/// Registers the units of a units file, or of the default one if it exists.
+///
+/// The file is TOML unless its extension is `.json`. A missing default file is
+/// not an error; a missing file given with `--units-file` is.
+pub fn load_units_file(path: Option<&Path>) -> Result<(), String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => match config_file("units.toml") {
+            Some(path) if path.is_file() => path,
+            _ => return Ok(()),
+        },
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read units file '{}': {}", path.display(), e))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let units = if is_json {
+        conversion::custom::parse_json(&contents)
+    } else {
+        conversion::custom::parse_toml(&contents)
+    };
+    units
+        .and_then(|units| conversion::custom::register(&units))
+        .map(|_| ())
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Sets the currency rate table from a rates file, or from the default one if it exists.
+///
+/// The file is CSV if its extension is `.csv` and JSON otherwise. Without a path,
+/// `rates.json` and then `rates.csv` are looked up in the config directory.
+pub fn load_rates_file(path: Option<&Path>) -> Result<(), String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => match ["rates.json", "rates.csv"]
+            .into_iter()
+            .filter_map(config_file)
+            .find(|path| path.is_file())
+        {
+            Some(path) => path,
+            None => return Ok(()),
+        },
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read rates file '{}': {}", path.display(), e))?;
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let table = if is_csv {
+        conversion::currency::parse_csv(&contents)
+    } else {
+        conversion::currency::parse_json(&contents)
+    };
+    table
+        .and_then(conversion::currency::set_rates)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
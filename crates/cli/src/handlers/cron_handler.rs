@@ -0,0 +1,47 @@
+use crate::{
+    args::cron::CronArgs,
+    handlers::{CommandResult, get_input_string, text_result},
+};
+use strapd_core::datetime::{
+    arithmetic,
+    cron::CronSchedule,
+    timestamp::{self, TimestampFormat},
+    timezone::Zone,
+};
+
+pub fn handle(args: &CronArgs) -> CommandResult {
+    let zone = args.tz.as_deref().map_or(Ok(Zone::Local), Zone::parse)?;
+    let format = args
+        .format
+        .as_deref()
+        .map_or(Ok(TimestampFormat::Human), TimestampFormat::parse)?;
+    let from = arithmetic::parse_instant(args.from.as_deref().unwrap_or("now"), false, zone)?;
+
+    let expression = match args.expression.is_empty() {
+        true => None,
+        false => Some(args.expression.join(" ")),
+    };
+    let schedule = CronSchedule::parse(&get_input_string(&expression))?;
+
+    let mut lines = vec![schedule.describe()];
+    if args.count > 0 {
+        let runs = schedule.next_runs(&from, args.count as usize, zone);
+        lines.push(String::new());
+        if runs.is_empty() {
+            lines.push("No runs in the next 30 years".to_string());
+        } else {
+            lines.push(match runs.len() {
+                1 => format!("Next run ({}):", zone),
+                count => format!("Next {} runs ({}):", count, zone),
+            });
+            for run in runs {
+                lines.push(timestamp::from_timestamp_millis_in(
+                    run.timestamp_millis(),
+                    format.clone(),
+                    zone,
+                )?);
+            }
+        }
+    }
+    text_result(lines.join("\n"))
+}
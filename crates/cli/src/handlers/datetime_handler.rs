@@ -1,48 +1,307 @@
 use crate::{
-    args::datetime::{TimeOperation, TimestampFormat},
-    handlers::{CommandResult, error_result, get_input_int, text_result},
+    args::datetime::{DurationUnit, Granularity, ShiftArgs, TimeOperation, TimestampUnit},
+    handlers::{
+        CommandResult, config_file, error_result, get_input_int, get_input_string, text_result,
+    },
+};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+use strapd_core::datetime::{
+    annotate::{self, AnnotateMode, AnnotateOptions},
+    arithmetic::{self, CalendarDuration},
+    relative::{self, RelativeOptions, RelativeUnit},
+    timestamp::{self, TimestampFormat, TimestampUnit as core_ts_unit},
+    timezone::{self, Zone},
 };
-use strapd_core::datetime::timestamp::{self, TimestampFormat as core_ts_format};
 
 pub fn handle(operation: &TimeOperation) -> CommandResult {
     match operation {
-        TimeOperation::Now { millis, format } => {
-            let result = match millis {
-                true => timestamp::now_millis(),
-                false => timestamp::now(),
+        TimeOperation::Now {
+            millis,
+            unit,
+            format,
+            tz,
+        } => {
+            let unit = match millis {
+                true => core_ts_unit::Millis,
+                false => convert_unit(unit),
             };
-            match format {
-                Some(format) => format_timestamp(result, *millis, format),
-                None => text_result(result.to_string()),
+            let result = timestamp::now_in(unit);
+            // A timezone implies formatted output
+            match (format, tz) {
+                (None, None) => text_result(result.to_string()),
+                (format, tz) => format_timestamp(
+                    result,
+                    unit,
+                    parse_format(format.as_deref())?,
+                    parse_zone(tz.as_deref())?,
+                ),
             }
         }
         TimeOperation::From {
             timestamp,
             millis,
+            unit,
             format,
+            tz,
         } => {
+            let zone = parse_zone(tz.as_deref())?;
+            let format = parse_format(format.as_deref())?;
             let input = get_input_int(timestamp);
-            let format = format.as_ref().unwrap_or(&TimestampFormat::Human);
-            format_timestamp(input, *millis, format)
+            let unit = timestamp_unit(input, *millis, unit.as_ref());
+            format_timestamp(input, unit, format, zone)
+        }
+        TimeOperation::To {
+            input,
+            millis,
+            format,
+            tz,
+        } => {
+            let zone = parse_zone(tz.as_deref())?;
+            let input = get_input_string(input);
+            let result = match millis {
+                true => timestamp::to_timestamp_millis(&input, format.as_deref(), zone)?,
+                false => timestamp::to_timestamp(&input, format.as_deref(), zone)?,
+            };
+            text_result(result.to_string())
+        }
+        TimeOperation::Add(args) => shift(args, false),
+        TimeOperation::Sub(args) => shift(args, true),
+        TimeOperation::Diff {
+            from,
+            to,
+            millis,
+            unit,
+            calendar,
+            tz,
+        } => {
+            let zone = parse_zone(tz.as_deref())?;
+            let from = arithmetic::parse_instant(from, *millis, zone)?;
+            let to = arithmetic::parse_instant(to, *millis, zone)?;
+
+            let result = match (unit, calendar) {
+                (Some(unit), _) => {
+                    let unit_millis = match unit {
+                        DurationUnit::Ms => 1.0,
+                        DurationUnit::S => 1_000.0,
+                        DurationUnit::M => 60_000.0,
+                        DurationUnit::H => 3_600_000.0,
+                        DurationUnit::D => 86_400_000.0,
+                        DurationUnit::W => 604_800_000.0,
+                    };
+                    let total = arithmetic::diff(&from, &to).num_milliseconds() as f64;
+                    (total / unit_millis).to_string()
+                }
+                (None, true) => arithmetic::calendar_diff(&from, &to, zone).to_string(),
+                (None, false) => CalendarDuration::from(arithmetic::diff(&from, &to)).to_string(),
+            };
+            text_result(result)
+        }
+        TimeOperation::Ago {
+            instant,
+            millis,
+            unit,
+            since,
+            parts,
+            granularity,
+            tz,
+        } => {
+            let zone = parse_zone(tz.as_deref())?;
+            let instant = get_input_string(instant);
+            let since = since.as_deref().unwrap_or("now");
+            let instant = arithmetic::parse_instant_in_unit(
+                &instant,
+                instant_unit(&instant, *millis, unit.as_ref()),
+                zone,
+            )?;
+            let since = arithmetic::parse_instant_in_unit(
+                since,
+                instant_unit(since, *millis, unit.as_ref()),
+                zone,
+            )?;
+            let options = RelativeOptions {
+                parts: *parts as usize,
+                granularity: convert_granularity(granularity),
+            };
+            text_result(relative::relative(&instant, &since, zone, &options))
+        }
+        TimeOperation::World {
+            timestamp,
+            millis,
+            format,
+            tz,
+            zones_file,
+        } => {
+            let zones = world_clock_zones(tz, zones_file.as_deref())?;
+            let timestamp_millis = match (timestamp, millis) {
+                (Some(ts), true) => *ts,
+                (Some(ts), false) => ts
+                    .checked_mul(1000)
+                    .ok_or_else(|| "Invalid timestamp".to_string())?,
+                (None, _) => timestamp::now_millis(),
+            };
+            let format = parse_format(format.as_deref())?;
+            let rows = timestamp::world_clock(timestamp_millis, &zones, format)?;
+
+            let width = rows.iter().map(|(zone, _)| zone.len()).max().unwrap_or(0);
+            let lines: Vec<String> = rows
+                .iter()
+                .map(|(zone, time)| format!("{:<width$}  {}", zone, time, width = width))
+                .collect();
+            text_result(lines.join("\n"))
         }
+        TimeOperation::Annotate {
+            input,
+            format,
+            tz,
+            replace,
+            keys,
+        } => {
+            let options = AnnotateOptions {
+                format: parse_format(format.as_deref())?,
+                zone: parse_zone(tz.as_deref())?,
+                mode: match replace {
+                    true => AnnotateMode::Replace,
+                    false => AnnotateMode::Append,
+                },
+                keys: keys.clone(),
+            };
+            match input {
+                Some(text) => text_result(annotate::annotate(text, &options)),
+                None => annotate_stdin(&options),
+            }
+        }
+    }
+}
+
+/// Annotate stdin as lines arrive, so it keeps up with `tail -f`
+///
+/// Lines are written as they are read, so nothing is left for the caller to print.
+fn annotate_stdin(options: &AnnotateOptions) -> CommandResult {
+    let mut stdout = io::stdout().lock();
+    let write_error = |e: io::Error| format!("Failed to write output: {}", e);
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|e| format!("Failed to read from stdin: {}", e))?;
+        writeln!(stdout, "{}", annotate::annotate_line(&line, options)).map_err(write_error)?;
+        stdout.flush().map_err(write_error)?;
     }
+    text_result(String::new())
 }
 
-fn format_timestamp(ts: i64, is_millis: bool, format: &TimestampFormat) -> CommandResult {
-    let result = match is_millis {
-        true => timestamp::from_timestamp_millis(ts, convert_format(format)),
-        false => timestamp::from_timestamp(ts, convert_format(format)),
+fn shift(args: &ShiftArgs, subtract: bool) -> CommandResult {
+    let zone = parse_zone(args.tz.as_deref())?;
+    let format = parse_format(args.format.as_deref())?;
+    let instant = arithmetic::parse_instant(&args.instant, args.millis, zone)?;
+    let duration = CalendarDuration::parse(&args.duration)?;
+
+    let result = match subtract {
+        true => arithmetic::sub(&instant, &duration, zone)?,
+        false => arithmetic::add(&instant, &duration, zone)?,
     };
+    match (args.epoch, args.millis) {
+        (true, true) => text_result(result.timestamp_millis().to_string()),
+        (true, false) => text_result(result.timestamp().to_string()),
+        (false, _) => format_timestamp(
+            result.timestamp_millis(),
+            core_ts_unit::Millis,
+            format,
+            zone,
+        ),
+    }
+}
+
+/// The unit of an input timestamp: --millis, then --unit, else guessed from the magnitude
+fn timestamp_unit(input: i64, millis: bool, unit: Option<&TimestampUnit>) -> core_ts_unit {
+    match (millis, unit) {
+        (true, _) => core_ts_unit::Millis,
+        (false, Some(unit)) => convert_unit(unit),
+        (false, None) => {
+            let unit = core_ts_unit::detect(input);
+            // Seconds were always the default, so only other units need a note;
+            // it goes to stderr to keep the output pipeable
+            if unit != core_ts_unit::Seconds {
+                eprintln!(
+                    "Assuming {} from the magnitude; use --unit to override",
+                    unit.name()
+                );
+            }
+            unit
+        }
+    }
+}
+
+/// The unit of an instant that may be a Unix timestamp; other instants ignore it
+fn instant_unit(input: &str, millis: bool, unit: Option<&TimestampUnit>) -> core_ts_unit {
+    match input.trim().parse::<i64>() {
+        Ok(value) => timestamp_unit(value, millis, unit),
+        Err(_) => core_ts_unit::Seconds,
+    }
+}
 
-    match result {
+fn format_timestamp(
+    ts: i64,
+    unit: core_ts_unit,
+    format: TimestampFormat,
+    zone: Zone,
+) -> CommandResult {
+    match timestamp::from_timestamp_unit_in(ts, unit, format, zone) {
         Ok(dt) => text_result(dt),
         Err(msg) => error_result(msg),
     }
 }
 
-fn convert_format(input_format: &TimestampFormat) -> core_ts_format {
-    match input_format {
-        TimestampFormat::Human => core_ts_format::Human,
-        TimestampFormat::Iso => core_ts_format::Iso,
+fn convert_unit(unit: &TimestampUnit) -> core_ts_unit {
+    match unit {
+        TimestampUnit::S => core_ts_unit::Seconds,
+        TimestampUnit::Ms => core_ts_unit::Millis,
+        TimestampUnit::Us => core_ts_unit::Micros,
+        TimestampUnit::Ns => core_ts_unit::Nanos,
+    }
+}
+
+fn convert_granularity(granularity: &Granularity) -> RelativeUnit {
+    match granularity {
+        Granularity::Year => RelativeUnit::Year,
+        Granularity::Month => RelativeUnit::Month,
+        Granularity::Week => RelativeUnit::Week,
+        Granularity::Day => RelativeUnit::Day,
+        Granularity::Hour => RelativeUnit::Hour,
+        Granularity::Minute => RelativeUnit::Minute,
+        Granularity::Second => RelativeUnit::Second,
+    }
+}
+
+fn parse_format(format: Option<&str>) -> Result<TimestampFormat, String> {
+    format.map_or(Ok(TimestampFormat::Human), TimestampFormat::parse)
+}
+
+fn parse_zone(tz: Option<&str>) -> Result<Zone, String> {
+    tz.map_or(Ok(Zone::Local), Zone::parse)
+}
+
+/// Zones from --tz, else from a zones file (the given one or the default), else a default set
+fn world_clock_zones(tz: &[String], zones_file: Option<&Path>) -> Result<Vec<Zone>, String> {
+    if !tz.is_empty() {
+        return tz.iter().map(|name| Zone::parse(name)).collect();
+    }
+
+    let path = match zones_file {
+        Some(path) => Some(path.to_path_buf()),
+        None => config_file("zones.txt").filter(|path| path.is_file()),
+    };
+    let Some(path) = path else {
+        return timezone::parse_zone_list(&timezone::DEFAULT_WORLD_CLOCK.join(","));
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read zones file '{}': {}", path.display(), e))?;
+    let zones =
+        timezone::parse_zone_list(&contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if zones.is_empty() {
+        return Err(format!("{}: no timezones found", path.display()));
     }
+    Ok(zones)
 }
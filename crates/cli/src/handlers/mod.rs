@@ -1,18 +1,22 @@
 // Handlers for various command line operations
 use std::{
+    env,
     io::{self, IsTerminal, Read},
+    path::PathBuf,
     str::FromStr,
 };
 
 pub mod calculator_handler;
 pub mod clipboard_handler;
 pub mod conversion_handler;
+pub mod cron_handler;
 pub mod data_formats_handler;
 pub mod datetime_handler;
 pub mod encoding_handler;
 pub mod identifiers_handler;
 pub mod random_handler;
 pub mod security_handler;
+pub mod statistics_handler;
 pub mod string_handler;
 
 pub type CommandResult = Result<Vec<u8>, String>;
@@ -86,3 +90,12 @@ where
         }
     }
 }
+
+/// A file in `$XDG_CONFIG_HOME/strapd`, falling back to `~/.config/strapd`
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("strapd").join(name))
+}
@@ -0,0 +1,86 @@
+use crate::{
+    args::statistics::StatsArgs,
+    handlers::{CommandResult, get_input_string, text_result},
+};
+use strapd_core::statistics::{self, HistogramBin};
+
+/// Width of the longest histogram bar in characters
+const HISTOGRAM_WIDTH: usize = 40;
+
+pub fn handle(args: &StatsArgs) -> CommandResult {
+    let input = get_input_string(&args.input);
+    let values = statistics::parse_numbers(&input)?;
+    let summary = statistics::summarize(&values)?;
+
+    let mut rows = vec![
+        ("count".to_string(), summary.count.to_string()),
+        ("sum".to_string(), format_value(summary.sum)),
+        ("min".to_string(), format_value(summary.min)),
+        ("max".to_string(), format_value(summary.max)),
+        ("mean".to_string(), format_value(summary.mean)),
+        ("median".to_string(), format_value(summary.median)),
+        ("std dev".to_string(), format_value(summary.std_dev)),
+        ("variance".to_string(), format_value(summary.variance)),
+    ];
+
+    let percentiles = statistics::percentiles(&values, &args.percentiles)?;
+    for (p, value) in args.percentiles.iter().zip(percentiles) {
+        rows.push((format!("p{}", p), format_value(value)));
+    }
+
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|(label, value)| format!("{:<width$}  {}", label, value, width = label_width))
+        .collect();
+
+    if args.histogram {
+        let bins = statistics::histogram(&values, args.bins as usize)?;
+        lines.push(String::new());
+        lines.extend(render_histogram(&bins));
+    }
+
+    text_result(lines.join("\n"))
+}
+
+/// Show up to 6 decimal places, trimming trailing zeros
+fn format_value(value: f64) -> String {
+    let formatted = format!("{:.6}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Render bins as `[start, end) | ##### count` rows with bars scaled to the largest bin
+fn render_histogram(bins: &[HistogramBin]) -> Vec<String> {
+    let ranges: Vec<(String, String)> = bins
+        .iter()
+        .map(|bin| (format_value(bin.start), format_value(bin.end)))
+        .collect();
+    let start_width = ranges.iter().map(|(s, _)| s.len()).max().unwrap_or(0);
+    let end_width = ranges.iter().map(|(_, e)| e.len()).max().unwrap_or(0);
+    let max_count = bins.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+
+    bins.iter()
+        .zip(&ranges)
+        .enumerate()
+        .map(|(i, (bin, (start, end)))| {
+            // The last bin includes its upper bound
+            let close = if i == bins.len() - 1 { ']' } else { ')' };
+            let bar = "#".repeat((bin.count * HISTOGRAM_WIDTH).div_ceil(max_count));
+            format!(
+                "[{:>sw$}, {:>ew$}{} | {} {}",
+                start,
+                end,
+                close,
+                bar,
+                bin.count,
+                sw = start_width,
+                ew = end_width
+            )
+        })
+        .collect()
+}
@@ -3,9 +3,9 @@ use std::io::{self, Write};
 
 use strapd::args::{Cli, Commands};
 use strapd::handlers::{
-    calculator_handler, clipboard_handler, conversion_handler, data_formats_handler,
+    calculator_handler, clipboard_handler, conversion_handler, cron_handler, data_formats_handler,
     datetime_handler, encoding_handler, identifiers_handler, random_handler, security_handler,
-    string_handler,
+    statistics_handler, string_handler,
 };
 
 fn main() {
@@ -26,13 +26,17 @@ fn main() {
         Commands::Hmac { operation } => security_handler::handle_hmac(operation),
         Commands::Random { operation } => random_handler::handle(operation),
         Commands::Time { operation } => datetime_handler::handle(operation),
+        Commands::Cron(args) => cron_handler::handle(args),
         Commands::Copy { input } => clipboard_handler::handle_copy(input),
         Commands::Paste {} => clipboard_handler::handle_paste(),
-        Commands::Calc { expression } => calculator_handler::handle(expression),
+        Commands::Calc(args) => calculator_handler::handle(args),
         Commands::Convert(args) => conversion_handler::handle(args),
+        Commands::Stats(args) => statistics_handler::handle(args),
     };
 
     match result {
+        // Nothing to print, e.g. after an interactive session or a streamed filter
+        Ok(bytes) if bytes.is_empty() => {}
         Ok(bytes) => {
             io::stdout()
                 .write_all(&bytes)
@@ -11,8 +11,8 @@ fn result_to_string(result: Result<Vec<u8>, String>) -> Result<String, String> {
 fn test_cli_calc_simple_addition() {
     let cli = Cli::parse_from(["strapd", "calc", "1 + 2"]);
     match &cli.command {
-        Commands::Calc { expression } => {
-            let result = calculator_handler::handle(expression);
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
             assert!(result.is_ok());
             let output = result_to_string(result).unwrap();
             assert_eq!(output.trim(), "3");
@@ -25,8 +25,8 @@ fn test_cli_calc_simple_addition() {
 fn test_cli_calc_precedence() {
     let cli = Cli::parse_from(["strapd", "calc", "2 * 3 + 4"]);
     match &cli.command {
-        Commands::Calc { expression } => {
-            let result = calculator_handler::handle(expression);
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
             assert!(result.is_ok());
             let output = result_to_string(result).unwrap();
             assert_eq!(output.trim(), "10");
@@ -39,8 +39,8 @@ fn test_cli_calc_precedence() {
 fn test_cli_calc_invalid() {
     let cli = Cli::parse_from(["strapd", "calc", "1 + "]);
     match &cli.command {
-        Commands::Calc { expression } => {
-            let result = calculator_handler::handle(expression);
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
             assert!(result.is_err());
         }
         _ => panic!("Expected Calc command"),
@@ -51,10 +51,483 @@ fn test_cli_calc_invalid() {
 fn test_cli_calc_division_by_zero() {
     let cli = Cli::parse_from(["strapd", "calc", "1 / 0"]);
     match &cli.command {
-        Commands::Calc { expression } => {
-            let result = calculator_handler::handle(expression);
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
             assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), "Division by zero");
+            assert_eq!(result.unwrap_err(), "Division by zero\n  1 / 0\n    ^");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_functions_and_constants() {
+    let cli = Cli::parse_from(["strapd", "calc", "max(3, 7) + round(pi, 2)"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "10.14");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_base_hex() {
+    let cli = Cli::parse_from(["strapd", "calc", "0xff & ~0x0f", "--base", "hex"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "0xf0");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_base_all_with_bits() {
+    let cli = Cli::parse_from(["strapd", "calc", "-1", "--base", "all", "--bits", "8"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "dec -1\nhex 0xff\noct 0o377\nbin 0b11111111");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_base_requires_integer() {
+    let cli = Cli::parse_from(["strapd", "calc", "1 / 3", "--base", "bin"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert!(result.unwrap_err().contains("not an integer"));
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_leading_negative() {
+    let cli = Cli::parse_from(["strapd", "calc", "-sqrt(4) * 2"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "-4");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_assignment() {
+    let cli = Cli::parse_from(["strapd", "calc", "rate = 1500 / 60"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "25");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_multiple_lines_share_context() {
+    let cli = Cli::parse_from(["strapd", "calc", "nodes = 12\nnodes * 64\nans / 1024"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "12\n768\n0.75");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_negative_number_on_own_line() {
+    let cli = Cli::parse_from(["strapd", "calc", "5\n-3"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "5\n-3");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_error_names_the_statement_line() {
+    let cli = Cli::parse_from(["strapd", "calc", "1 + 1\n\nfoo\n3"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Line 3: Unknown variable or constant: foo\n  foo\n  ^^^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_error_keeps_earlier_results() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_strapd"))
+        .args(["calc", "1 + 1\nx = 3\nfoo\n4"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2\n3\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("Error: Line 3: Unknown variable"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_cli_calc_continuation_lines() {
+    for (input, expected) in [
+        ("1 +\n2", "3"),
+        ("2\n* 3", "6"),
+        ("2 *\n3\n10", "6\n10"),
+        ("max(1,\n  5)\n(1\n+ 2) * 3", "5\n9"),
+    ] {
+        let cli = Cli::parse_from(["strapd", "calc", input]);
+        match &cli.command {
+            Commands::Calc(args) => {
+                let result = calculator_handler::handle(args);
+                let output = result_to_string(result).unwrap();
+                assert_eq!(output, expected, "{:?}", input);
+            }
+            _ => panic!("Expected Calc command"),
+        }
+    }
+}
+
+#[test]
+fn test_cli_calc_exact() {
+    let cli = Cli::parse_from(["strapd", "calc", "--exact", "0.1 + 0.2"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "0.3");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_exact_digits() {
+    let cli = Cli::parse_from(["strapd", "calc", "-e", "--digits", "6", "10 / 3"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "3.33333");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_digits_conflicts_with_sig_figs() {
+    let result = Cli::try_parse_from(["strapd", "calc", "-e", "-d", "6", "-s", "3", "10 / 3"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_calc_digits_requires_exact() {
+    let result = Cli::try_parse_from(["strapd", "calc", "--digits", "6", "10 / 3"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_calc_precision_out_of_range() {
+    let result = Cli::try_parse_from(["strapd", "calc", "-p", "20", "1 / 3"]);
+    assert!(result.is_err());
+    let result = Cli::try_parse_from(["strapd", "convert", "-p", "11", "1 km to mi"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_calc_base_conflicts_with_group() {
+    let result = Cli::try_parse_from(["strapd", "calc", "-b", "hex", "-g", "_", "255"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_calc_with_units() {
+    let cli = Cli::parse_from(["strapd", "calc", "2 GiB / 32 s to MiB/s"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output.trim(), "64 MiB/s");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_result_is_ans() {
+    let cli = Cli::parse_from(["strapd", "calc", "n = 4\nn * 512 MiB\nans / 2 s to MiB/s"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "4\n2048 MiB\n1024 MiB/s");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_error_marker() {
+    let cli = Cli::parse_from(["strapd", "calc", "2 GiB + 30 s"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Cannot add GiB (Bytes) and s (Time): units must have the same dimension\n  \
+                 2 GiB + 30 s\n        ^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_without_units_keeps_calculator_error() {
+    let cli = Cli::parse_from(["strapd", "calc", "foo + 1"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Unknown variable or constant: foo\n  foo + 1\n  ^^^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_misspelled_source_unit_suggests() {
+    let cli = Cli::parse_from(["strapd", "calc", "5 kms to m"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Unknown unit: 'kms'. Did you mean 'km', 'ms' or 'ems'?\n  5 kms to m\n    ^^^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_error_marker_mismatched_parentheses() {
+    let cli = Cli::parse_from(["strapd", "calc", "sqrt(16 + (2 * 3)"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Mismatched parentheses\n  sqrt(16 + (2 * 3)\n      ^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_error_marker_function_arity_and_comma() {
+    for (input, expected) in [
+        (
+            "min()",
+            "min() expects at least 1 argument, got 0\n  min()\n  ^^^",
+        ),
+        (
+            "(1,2)",
+            "Unexpected ',' outside of a function call\n  (1,2)\n    ^",
+        ),
+    ] {
+        let cli = Cli::parse_from(["strapd", "calc", input]);
+        match &cli.command {
+            Commands::Calc(args) => {
+                let result = calculator_handler::handle(args);
+                assert_eq!(result.unwrap_err(), expected);
+            }
+            _ => panic!("Expected Calc command"),
+        }
+    }
+}
+
+#[test]
+fn test_cli_calc_error_marker_after_unicode() {
+    // Markers line up by character, even though spans are byte offsets
+    let cli = Cli::parse_from(["strapd", "calc", "(1 + 2) × 3"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            assert_eq!(
+                result.unwrap_err(),
+                "Invalid character: ×\n  (1 + 2) × 3\n          ^"
+            );
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_significant_figures_and_grouping() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "calc",
+        "10^9 / 7",
+        "--sig-figs",
+        "5",
+        "--group",
+        ",",
+    ]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "142,860,000");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_scientific_notation() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "calc",
+        "-n",
+        "sci",
+        "-p",
+        "3",
+        "6.02214076 * 10^23 * 2",
+    ]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.204e24");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_result_uses_number_format() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "calc",
+        "--notation",
+        "engineering",
+        "-s",
+        "3",
+        "1 ns to day",
+    ]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "11.6e-15 day");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_result_to_human() {
+    let cli = Cli::parse_from(["strapd", "calc", "1 h + 30 min to human"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1h 30m");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_result_to_iso() {
+    let cli = Cli::parse_from(["strapd", "calc", "1 h + 30 min to iso"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "PT1H30M");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_unit_result_to_compound_target() {
+    let cli = Cli::parse_from(["strapd", "calc", "5 ft + 11 in to ft+in"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "5 ft 11 in");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_compound_duration_literal() {
+    let cli = Cli::parse_from(["strapd", "calc", "1h30m"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "5400 s");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_compound_duration_literal_to_unit() {
+    let cli = Cli::parse_from(["strapd", "calc", "1h30m to min"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "90 min");
+        }
+        _ => panic!("Expected Calc command"),
+    }
+}
+
+#[test]
+fn test_cli_calc_same_unit_sum_is_exact() {
+    let cli = Cli::parse_from(["strapd", "calc", "10 in + 2 in"]);
+    match &cli.command {
+        Commands::Calc(args) => {
+            let result = calculator_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "12 in");
         }
         _ => panic!("Expected Calc command"),
     }
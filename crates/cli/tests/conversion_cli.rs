@@ -94,3 +94,511 @@ fn test_cli_convert_invalid_unit() {
         _ => panic!("Expected Convert command"),
     }
 }
+
+#[test]
+fn test_cli_convert_unit_arithmetic() {
+    let cli = Cli::parse_from(["strapd", "convert", "90 min + 45min to s"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "8100 s");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_compound_duration_arithmetic() {
+    let cli = Cli::parse_from(["strapd", "convert", "1h30m + 45min to s"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "8100 s");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_unit_arithmetic_dimension_error() {
+    let cli = Cli::parse_from(["strapd", "convert", "1 GiB + 1 s"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            assert!(result.unwrap_err().contains("same dimension"));
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_speed() {
+    let cli = Cli::parse_from(["strapd", "convert", "100 km/h to mph"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "62.14 mph");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_all_data_rate() {
+    let cli = Cli::parse_from(["strapd", "convert", "1 Gbps", "--all"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert!(output.contains("1000 Mbps"));
+            assert!(output.contains("125 MB/s"));
+            assert!(output.contains("IEC (powers of 1024):\n"));
+            assert!(output.contains("119.21 MiB/s"));
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_si_to_iec_bytes() {
+    let cli = Cli::parse_from(["strapd", "convert", "1 GB to GiB", "-p", "3"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "0.931 GiB");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_compound_duration() {
+    let cli = Cli::parse_from(["strapd", "convert", "1h30m to min"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "90 min");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_to_human() {
+    let cli = Cli::parse_from(["strapd", "convert", "5415", "s", "to", "human"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1h 30m 15s");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_mixed_units() {
+    let cli = Cli::parse_from(["strapd", "convert", "5", "ft", "11", "in", "to", "cm"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "180.34 cm");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_compound_target() {
+    let cli = Cli::parse_from(["strapd", "convert", "180 cm to ft+in"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "5 ft 10.87 in");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_unknown_unit_suggestion() {
+    let cli = Cli::parse_from(["strapd", "convert", "10 kms to mi"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            assert!(result.unwrap_err().contains("Did you mean 'km'"));
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_unknown_command_suggestion() {
+    let error = Cli::try_parse_from(["strapd", "convrt", "10 km to mi"]).unwrap_err();
+    assert!(error.to_string().contains("convert"));
+}
+
+fn write_temp_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("strapd-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_cli_convert_units_file_toml() {
+    let path = write_temp_file(
+        "units.toml",
+        "[[units]]\nname = \"mug\"\naliases = [\"mugs\"]\ncategory = \"coffee\"\nmultiplier = 1\n\n\
+         [[units]]\nname = \"pot\"\ncategory = \"coffee\"\nmultiplier = 8\n",
+    );
+    let cli = Cli::parse_from(["strapd", "convert", "3 pot to mugs", "--units-file", &path]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "24 mugs");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_units_file_json() {
+    let path = write_temp_file(
+        "units.json",
+        r#"{"units": [{"name": "kgf/cm2", "category": "pressure", "multiplier": 98066.5}]}"#,
+    );
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "--units-file",
+        &path,
+        "1 kgf/cm2 to bar",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "0.98 bar");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_units_file_collision() {
+    let path = write_temp_file(
+        "collision.toml",
+        "[[units]]\nname = \"mile\"\ncategory = \"length\"\nmultiplier = 1852\n",
+    );
+    let cli = Cli::parse_from(["strapd", "convert", "1 mi to km", "--units-file", &path]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let error = conversion_handler::handle(args).unwrap_err();
+            assert!(
+                error.ends_with("Unit name 'mile' of 'mile' collides with built-in unit 'mi'"),
+                "{}",
+                error
+            );
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_units_file_missing() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "1 km to m",
+        "--units-file",
+        "/nonexistent/strapd/units.toml",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let error = conversion_handler::handle(args).unwrap_err();
+            assert!(
+                error.starts_with("Failed to read units file '/nonexistent/strapd/units.toml'")
+            );
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+// The rate table is global, so the no-table error is checked before loading one
+#[test]
+fn test_cli_convert_currency_rates_file() {
+    let cli = Cli::parse_from(["strapd", "convert", "120 usd to eur"]);
+    match &cli.command {
+        Commands::Convert(args) => {
+            let error = conversion_handler::handle(args).unwrap_err();
+            assert!(error.starts_with("No currency rates configured for 'usd'"));
+        }
+        _ => panic!("Expected Convert command"),
+    }
+
+    let path = write_temp_file(
+        "rates.csv",
+        "date,base,currency,rate\n2026-10-01,USD,EUR,0.92\n",
+    );
+    let cli = Cli::parse_from(["strapd", "convert", "120 usd to eur", "--rates-file", &path]);
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "110.4 eur\nRates as of 2026-10-01 (base USD)");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_typography_flags() {
+    let cli = Cli::parse_from(["strapd", "convert", "24px to rem", "--root-font-size", "18"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.33 rem");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_typography_setting_in_expression() {
+    // A setting in the expression overrides the flag
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "--viewport-width",
+        "1000",
+        "50 vw to px @vw=1440",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "720 px");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_human_flag() {
+    let cli = Cli::parse_from(["strapd", "convert", "93784 s", "--human"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.09 day");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_human_flag_with_target() {
+    // The result is scaled within the target's units
+    let cli = Cli::parse_from(["strapd", "convert", "--human", "3 GiB to kB"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "3.22 GB");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_human_conflicts_with_all() {
+    let result = Cli::try_parse_from(["strapd", "convert", "1 km", "--human", "--all"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_convert_humanize() {
+    let cli = Cli::parse_from(["strapd", "convert", "humanize", "1536000\n512\n\n2 TB"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.46 MiB\n512 B\n2 TB");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_humanize_with_unit() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "humanize",
+        "--unit",
+        "ms",
+        "-p",
+        "1",
+        "93784000\n250",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.1 day\n250.0 ms");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_humanize_calculator_literals() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "humanize",
+        "0\n1\n1_000\n1e3\n0x400\n1500000",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "0 B\n1 B\n1000 B\n1000 B\n1 KiB\n1.43 MiB");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_humanize_invalid_line() {
+    let cli = Cli::parse_from(["strapd", "convert", "humanize", "1024\nlots"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let error = conversion_handler::handle(args).unwrap_err();
+            assert_eq!(error, "1 line could not be converted");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_humanize_keeps_going_after_bad_line() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_strapd"))
+        .args(["convert", "humanize", "1024\nlots\n2 TB"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "1 KiB\nlots\n2 TB\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Line 2: "), "{}", stderr);
+    assert!(
+        stderr.ends_with("Error: 1 line could not be converted\n"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_cli_convert_scientific_notation() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "convert",
+        "1 ns to day",
+        "--notation",
+        "scientific",
+        "--sig-figs",
+        "4",
+    ]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.157e-14 day");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_tiny_value_is_not_zero() {
+    let cli = Cli::parse_from(["strapd", "convert", "1 ns to day"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1.157e-14 day");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_explicit_precision_applies_to_tiny_values() {
+    let cli = Cli::parse_from(["strapd", "convert", "1 ns to day", "-p", "3"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "0.000 day");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_grouping() {
+    let cli = Cli::parse_from(["strapd", "convert", "-g", "_", "1.5 GiB to byte"]);
+
+    match &cli.command {
+        Commands::Convert(args) => {
+            let result = conversion_handler::handle(args);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1_610_612_736 byte");
+        }
+        _ => panic!("Expected Convert command"),
+    }
+}
+
+#[test]
+fn test_cli_convert_invalid_separator() {
+    let result = Cli::try_parse_from(["strapd", "convert", "--group", ".", "1 km to m"]);
+    assert!(result.is_err());
+}
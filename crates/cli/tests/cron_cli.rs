@@ -0,0 +1,145 @@
+use clap::Parser;
+use strapd::args::{Cli, Commands};
+use strapd::handlers::cron_handler;
+
+// Helper to convert Result<Vec<u8>, String> to String
+fn result_to_string(result: Result<Vec<u8>, String>) -> Result<String, String> {
+    result.map(|bytes| String::from_utf8(bytes).expect("Invalid UTF-8"))
+}
+
+#[test]
+fn test_cli_cron_next_runs() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "cron",
+        "0 9 * * MON-FRI",
+        "-n",
+        "3",
+        "--tz",
+        "UTC",
+        "--from",
+        "2025-03-06T12:00:00Z",
+    ]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let output = result_to_string(cron_handler::handle(args)).unwrap();
+            assert_eq!(
+                output,
+                "At 09:00, on Monday through Friday\n\
+                 \n\
+                 Next 3 runs (UTC):\n\
+                 2025-03-07 09:00:00 UTC\n\
+                 2025-03-10 09:00:00 UTC\n\
+                 2025-03-11 09:00:00 UTC"
+            );
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
+
+#[test]
+fn test_cli_cron_separate_words_and_format() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "cron",
+        "0",
+        "0",
+        "12",
+        "?",
+        "*",
+        "FRI#3",
+        "-n",
+        "2",
+        "-f",
+        "iso",
+        "-z",
+        "Europe/Berlin",
+        "--from",
+        "1741075200",
+    ]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let output = result_to_string(cron_handler::handle(args)).unwrap();
+            assert_eq!(
+                output,
+                "At 12:00, on the third Friday of the month\n\
+                 \n\
+                 Next 2 runs (Europe/Berlin):\n\
+                 2025-03-21T12:00:00+01:00\n\
+                 2025-04-18T12:00:00+02:00"
+            );
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
+
+#[test]
+fn test_cli_cron_explain_only() {
+    let cli = Cli::parse_from(["strapd", "cron", "@hourly", "-n", "0"]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let output = result_to_string(cron_handler::handle(args)).unwrap();
+            assert_eq!(output, "Every hour");
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
+
+#[test]
+fn test_cli_cron_single_run() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "cron",
+        "0 9 * * 0-7",
+        "-n",
+        "1",
+        "--tz",
+        "UTC",
+        "--from",
+        "2025-03-06T12:00:00Z",
+    ]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let output = result_to_string(cron_handler::handle(args)).unwrap();
+            assert_eq!(
+                output,
+                "At 09:00, every day\n\
+                 \n\
+                 Next run (UTC):\n\
+                 2025-03-07 09:00:00 UTC"
+            );
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
+
+#[test]
+fn test_cli_cron_never_runs() {
+    let cli = Cli::parse_from(["strapd", "cron", "0 0 30 2 *", "-z", "UTC"]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let output = result_to_string(cron_handler::handle(args)).unwrap();
+            assert!(
+                output.ends_with("No runs in the next 30 years"),
+                "{}",
+                output
+            );
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
+
+#[test]
+fn test_cli_cron_invalid_field() {
+    let cli = Cli::parse_from(["strapd", "cron", "0 25 * * *"]);
+    match &cli.command {
+        Commands::Cron(args) => {
+            let error = cron_handler::handle(args).unwrap_err();
+            assert_eq!(
+                error,
+                "Invalid hour field '25' (field 2 of 5): 25 is out of range 0-23"
+            );
+        }
+        _ => panic!("Expected Cron command"),
+    }
+}
@@ -92,3 +92,649 @@ fn test_cli_time_from_timestamp_millis() {
         _ => panic!("Expected Time command"),
     }
 }
+
+#[test]
+fn test_cli_time_from_timestamp_tz() {
+    // The output no longer depends on the machine's timezone
+    let cli = Cli::parse_from(["strapd", "time", "from", "0", "--tz", "America/New_York"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1969-12-31 19:00:00 EST");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_timestamp_offset() {
+    let cli = Cli::parse_from(["strapd", "time", "from", "0", "-f", "iso", "-z", "+05:30"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1970-01-01T05:30:00+05:30");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_unknown_tz() {
+    let cli = Cli::parse_from(["strapd", "time", "from", "0", "--tz", "Nowhere/City"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let error = datetime_handler::handle(operation).unwrap_err();
+            assert!(
+                error.starts_with("Unknown timezone 'Nowhere/City'"),
+                "{}",
+                error
+            );
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_non_ascii_offset() {
+    let cli = Cli::parse_from(["strapd", "time", "from", "1700000000", "--tz", "+1é1"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let error = datetime_handler::handle(operation).unwrap_err();
+            assert!(error.starts_with("Invalid UTC offset '+1é1'"), "{}", error);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_now_tz() {
+    // A timezone without --format shows the human format
+    let cli = Cli::parse_from(["strapd", "time", "now", "--tz", "UTC"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert!(output.ends_with(" UTC"), "{}", output);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_world() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "world",
+        "1710072000",
+        "--tz",
+        "UTC,America/New_York",
+        "--tz",
+        "+05:30",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(
+                output,
+                "UTC               2024-03-10 12:00:00 UTC\n\
+                 America/New_York  2024-03-10 08:00:00 EDT\n\
+                 +05:30            2024-03-10 17:30:00 +05:30"
+            );
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_world_zones_file() {
+    let path = std::env::temp_dir().join(format!("strapd-zones-{}.txt", std::process::id()));
+    std::fs::write(&path, "# team\nEurope/Berlin\nAsia/Tokyo\n").unwrap();
+
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "world",
+        "1710072000000",
+        "--millis",
+        "--format",
+        "iso",
+        "--zones-file",
+        path.to_str().unwrap(),
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(
+                output,
+                "Europe/Berlin  2024-03-10T13:00:00+01:00\nAsia/Tokyo     2024-03-10T21:00:00+09:00"
+            );
+        }
+        _ => panic!("Expected Time command"),
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_cli_time_to() {
+    let cli = Cli::parse_from(["strapd", "time", "to", "Tue, 04 Mar 2025 10:00:00 GMT"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1741082400");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_to_millis_tz() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "to",
+        "2025-03-04 10:00",
+        "--tz",
+        "+02:00",
+        "-m",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1741075200000");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_to_format() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "to",
+        "04.03.2025",
+        "-f",
+        "%d.%m.%Y",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1741046400");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_to_invalid() {
+    let cli = Cli::parse_from(["strapd", "time", "to", "not a date", "-z", "UTC"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let error = datetime_handler::handle(operation).unwrap_err();
+            assert!(error.starts_with("Unrecognized date"), "{}", error);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_preset_formats() {
+    for (format, expected) in [
+        ("sql", "2025-03-04 08:00:00"),
+        ("http", "Tue, 04 Mar 2025 08:00:00 GMT"),
+        ("compact", "20250304080000"),
+        ("date", "2025-03-04"),
+        ("%d.%m.%Y %H:%M", "04.03.2025 08:00"),
+    ] {
+        let cli = Cli::parse_from([
+            "strapd",
+            "time",
+            "from",
+            "1741075200",
+            "-f",
+            format,
+            "-z",
+            "UTC",
+        ]);
+        match &cli.command {
+            Commands::Time { operation } => {
+                let result = datetime_handler::handle(operation);
+                let output = result_to_string(result).unwrap();
+                assert_eq!(output, expected, "{}", format);
+            }
+            _ => panic!("Expected Time command"),
+        }
+    }
+}
+
+#[test]
+fn test_cli_time_from_invalid_format() {
+    let cli = Cli::parse_from(["strapd", "time", "from", "0", "-f", "%Y-%Q"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let error = datetime_handler::handle(operation).unwrap_err();
+            assert_eq!(error, "Invalid format pattern '%Y-%Q'");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_add() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "add",
+        "1741075200",
+        "3600",
+        "-f",
+        "iso",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "2025-03-04T09:00:00+00:00");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_sub_epoch() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "sub",
+        "2025-03-31T00:00:00Z",
+        "1mo",
+        "--epoch",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            // 2025-02-28T00:00:00Z
+            assert_eq!(output, "1740700800");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_add_negative_duration() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "add",
+        "1741075200",
+        "-1h",
+        "-e",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1741071600");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_add_invalid_duration() {
+    let cli = Cli::parse_from(["strapd", "time", "add", "now", "soon"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let error = datetime_handler::handle(operation).unwrap_err();
+            assert!(error.starts_with("Invalid duration 'soon'"), "{}", error);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_diff() {
+    let extra_args: [(&[&str], &str); 3] = [
+        (&[], "1d 1h 30m"),
+        (&["-u", "h"], "25.5"),
+        (&["--unit", "min"], "1530"),
+    ];
+    for (extra, expected) in extra_args {
+        let mut args = vec![
+            "strapd",
+            "time",
+            "diff",
+            "1741075200",
+            "2025-03-05T09:30:00Z",
+            "-z",
+            "UTC",
+        ];
+        args.extend(extra);
+        let cli = Cli::parse_from(args);
+        match &cli.command {
+            Commands::Time { operation } => {
+                let result = datetime_handler::handle(operation);
+                let output = result_to_string(result).unwrap();
+                assert_eq!(output, expected);
+            }
+            _ => panic!("Expected Time command"),
+        }
+    }
+}
+
+#[test]
+fn test_cli_time_diff_calendar() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "diff",
+        "2024-01-31",
+        "2025-03-01",
+        "--calendar",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1y 1mo 1d");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_detects_unit() {
+    for (timestamp, expected) in [
+        ("1741075200", "2025-03-04T08:00:00+00:00"),
+        ("1741075200123", "2025-03-04T08:00:00.123+00:00"),
+        ("1741075200123456", "2025-03-04T08:00:00.123456+00:00"),
+        ("1741075200123456789", "2025-03-04T08:00:00.123456789+00:00"),
+    ] {
+        let cli = Cli::parse_from([
+            "strapd", "time", "from", timestamp, "-f", "iso", "-z", "UTC",
+        ]);
+        match &cli.command {
+            Commands::Time { operation } => {
+                let result = datetime_handler::handle(operation);
+                let output = result_to_string(result).unwrap();
+                assert_eq!(output, expected, "{}", timestamp);
+            }
+            _ => panic!("Expected Time command"),
+        }
+    }
+}
+
+#[test]
+fn test_cli_time_from_explicit_unit() {
+    // Without --unit, 1741075200 would be read as seconds
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "from",
+        "1741075200",
+        "--unit",
+        "ms",
+        "-f",
+        "iso",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1970-01-21T03:37:55.200+00:00");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_from_unit_conflicts_with_millis() {
+    let result = Cli::try_parse_from(["strapd", "time", "from", "1", "-m", "-u", "ns"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_time_now_unit() {
+    let cli = Cli::parse_from(["strapd", "time", "now", "--unit", "ns"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            let nanos: i64 = output.parse().unwrap();
+            assert!(nanos > 1_577_836_800_000_000_000);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_annotate() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "annotate",
+        "start 1741075200\nend 2025-03-04T09:00:00Z",
+        "-f",
+        "iso",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(
+                output,
+                "start 1741075200 (2025-03-04T08:00:00+00:00)\nend 2025-03-04T09:00:00Z (2025-03-04T09:00:00+00:00)"
+            );
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_annotate_keys_replace() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "annotate",
+        r#"{"ts": 1741075200000, "id": 1741075200}"#,
+        "--key",
+        "ts,time",
+        "--replace",
+        "-f",
+        "sql",
+        "-z",
+        "Asia/Kolkata",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, r#"{"ts": "2025-03-04 13:30:00", "id": 1741075200}"#);
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_annotate_stdin_ends_each_line() {
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_strapd"))
+        .args(["time", "annotate", "-z", "UTC"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), b"at 1741075200\nnone\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("at 1741075200 ("), "{}", stdout);
+    assert!(stdout.ends_with(")\nnone\n"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_time_annotate_invalid_format() {
+    let cli = Cli::parse_from(["strapd", "time", "annotate", "1741075200", "-f", "nope"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            assert!(result.is_err());
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_since() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "ago",
+        "2025-03-02T05:30:00Z",
+        "--since",
+        "2025-03-04T08:00:00Z",
+        "-p",
+        "2",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "2 days, 2 hours ago");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_future_granularity() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "relative",
+        "1741075200000",
+        "-m",
+        "--since",
+        "1740000000000",
+        "-p",
+        "3",
+        "-g",
+        "hours",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "in 1 week, 5 days, 10 hours");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_now() {
+    let hour_ago = (strapd_core::datetime::timestamp::now() - 3600).to_string();
+    let cli = Cli::parse_from(["strapd", "time", "ago", &hour_ago, "-z", "UTC"]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1 hour ago");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_invalid_parts() {
+    let result = Cli::try_parse_from(["strapd", "time", "ago", "now", "-p", "0"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cli_time_from_relative_format() {
+    let two_days_ago = (strapd_core::datetime::timestamp::now() - 2 * 86_400 - 60).to_string();
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "from",
+        &two_days_ago,
+        "-f",
+        "relative",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "2 days ago");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_detects_unit() {
+    // Milliseconds, told apart by the magnitude as in time from
+    let cli = Cli::parse_from([
+        "strapd",
+        "time",
+        "ago",
+        "1741075200000",
+        "--since",
+        "1741161600",
+        "-z",
+        "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1 day ago");
+        }
+        _ => panic!("Expected Time command"),
+    }
+}
+
+#[test]
+fn test_cli_time_ago_explicit_unit() {
+    let cli = Cli::parse_from([
+        "strapd", "time", "ago", "0", "--since", "86400000", "-u", "ms", "-z", "UTC",
+    ]);
+    match &cli.command {
+        Commands::Time { operation } => {
+            let result = datetime_handler::handle(operation);
+            let output = result_to_string(result).unwrap();
+            assert_eq!(output, "1 day ago");
+        }
+        _ => panic!("Expected Time command"),
+    }
+
+    let result = Cli::try_parse_from(["strapd", "time", "ago", "1", "-m", "-u", "s"]);
+    assert!(result.is_err());
+}
@@ -91,6 +91,7 @@ fn test_cli_random_string_multiple() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_cli_random_number_defaults() {
     let cli = Cli::parse_from(["strapd", "random", "number"]);
     match &cli.command {
@@ -106,6 +107,7 @@ fn test_cli_random_number_defaults() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_cli_random_number_range() {
     let cli = Cli::parse_from(["strapd", "random", "number", "--min", "10", "--max", "20"]);
     match &cli.command {
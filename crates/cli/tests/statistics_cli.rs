@@ -0,0 +1,88 @@
+use clap::Parser;
+use strapd::args::{Cli, Commands};
+use strapd::handlers::statistics_handler;
+
+// Helper to convert Result<Vec<u8>, String> to String
+fn result_to_string(result: Result<Vec<u8>, String>) -> Result<String, String> {
+    result.map(|bytes| String::from_utf8(bytes).expect("Invalid UTF-8"))
+}
+
+#[test]
+fn test_cli_stats_summary() {
+    let cli = Cli::parse_from(["strapd", "stats", "1 2 3 4\n10"]);
+    match &cli.command {
+        Commands::Stats(args) => {
+            let output = result_to_string(statistics_handler::handle(args)).unwrap();
+            assert_eq!(
+                output,
+                "count     5\n\
+                 sum       20\n\
+                 min       1\n\
+                 max       10\n\
+                 mean      4\n\
+                 median    3\n\
+                 std dev   3.535534\n\
+                 variance  12.5"
+            );
+        }
+        _ => panic!("Expected Stats command"),
+    }
+}
+
+#[test]
+fn test_cli_stats_percentiles() {
+    let cli = Cli::parse_from(["strapd", "stats", "-p", "50,95,99", "1 2 3 4 5 6 7 8 9 10"]);
+    match &cli.command {
+        Commands::Stats(args) => {
+            let output = result_to_string(statistics_handler::handle(args)).unwrap();
+            assert!(output.contains("p50       5.5"));
+            assert!(output.contains("p95       9.55"));
+            assert!(output.contains("p99       9.91"));
+        }
+        _ => panic!("Expected Stats command"),
+    }
+}
+
+#[test]
+fn test_cli_stats_histogram() {
+    let cli = Cli::parse_from([
+        "strapd",
+        "stats",
+        "--histogram",
+        "--bins",
+        "2",
+        "0 1 2 3 4 10",
+    ]);
+    match &cli.command {
+        Commands::Stats(args) => {
+            let output = result_to_string(statistics_handler::handle(args)).unwrap();
+            let histogram: Vec<&str> = output.lines().skip_while(|l| !l.is_empty()).collect();
+            assert_eq!(
+                histogram,
+                vec![
+                    "",
+                    "[0,  5) | ######################################## 5",
+                    "[5, 10] | ######## 1",
+                ]
+            );
+        }
+        _ => panic!("Expected Stats command"),
+    }
+}
+
+#[test]
+fn test_cli_stats_invalid_number() {
+    let cli = Cli::parse_from(["strapd", "stats", "1 2 x"]);
+    match &cli.command {
+        Commands::Stats(args) => {
+            let result = statistics_handler::handle(args);
+            assert_eq!(result.unwrap_err(), "Invalid number: x");
+        }
+        _ => panic!("Expected Stats command"),
+    }
+}
+
+#[test]
+fn test_cli_stats_bins_require_histogram() {
+    assert!(Cli::try_parse_from(["strapd", "stats", "--bins", "5", "1 2"]).is_err());
+}
@@ -0,0 +1,118 @@
+// Structured calculator errors
+//
+// Every error carries the byte range of the offending token in the input
+// expression when one is known, so callers can point at the mistake.
+
+use std::{error, fmt, ops::Range};
+
+/// What went wrong while evaluating an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalcErrorKind {
+    /// Nothing to evaluate
+    EmptyExpression,
+    InvalidCharacter(char),
+    InvalidNumber(String),
+    MultipleDecimalPoints,
+    StandaloneDecimalPoint,
+    /// A `-` that is neither subtraction nor followed by an operand
+    ExpectedNumberAfterMinus,
+    /// Two operands next to each other, e.g. `2 3`
+    MissingOperator,
+    /// An operator without an operand on one side, e.g. `2 +`
+    MissingOperand,
+    MismatchedParentheses,
+    UnexpectedComma,
+    UnknownFunction(String),
+    UnknownVariable(String),
+    NoPreviousResult,
+    /// A variable or `ans` holding a unit result, used where only plain numbers work
+    UnitValue(String),
+    InvalidAssignmentTarget(String),
+    ReservedName(String),
+    /// Arithmetic or function error such as division by zero or a domain error
+    Evaluation(String),
+}
+
+impl fmt::Display for CalcErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcErrorKind::EmptyExpression => write!(f, "Invalid expression"),
+            CalcErrorKind::InvalidCharacter(c) => write!(f, "Invalid character: {}", c),
+            CalcErrorKind::InvalidNumber(literal) => write!(f, "Invalid number: {}", literal),
+            CalcErrorKind::MultipleDecimalPoints => {
+                write!(f, "Invalid number format: multiple decimal points")
+            }
+            CalcErrorKind::StandaloneDecimalPoint => {
+                write!(f, "Invalid number: standalone decimal point")
+            }
+            CalcErrorKind::ExpectedNumberAfterMinus => {
+                write!(f, "Invalid expression: expected number after '-'")
+            }
+            CalcErrorKind::MissingOperator => write!(f, "Invalid expression: missing operator"),
+            CalcErrorKind::MissingOperand => write!(f, "Invalid expression: missing operand"),
+            CalcErrorKind::MismatchedParentheses => write!(f, "Mismatched parentheses"),
+            CalcErrorKind::UnexpectedComma => {
+                write!(f, "Unexpected ',' outside of a function call")
+            }
+            CalcErrorKind::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+            CalcErrorKind::UnknownVariable(name) => {
+                write!(f, "Unknown variable or constant: {}", name)
+            }
+            CalcErrorKind::NoPreviousResult => write!(f, "No previous result to use as 'ans'"),
+            CalcErrorKind::UnitValue(name) => {
+                write!(f, "'{}' has units and can only be used with units", name)
+            }
+            CalcErrorKind::InvalidAssignmentTarget(name) => {
+                write!(f, "Invalid assignment target: '{}'", name)
+            }
+            CalcErrorKind::ReservedName(name) => {
+                write!(f, "Cannot assign to reserved name: {}", name)
+            }
+            CalcErrorKind::Evaluation(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Calculator error with the byte span of the offending input, if known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalcError {
+    kind: CalcErrorKind,
+    span: Option<Range<usize>>,
+}
+
+impl CalcError {
+    pub fn new(kind: CalcErrorKind, span: Range<usize>) -> Self {
+        Self {
+            kind,
+            span: Some(span),
+        }
+    }
+
+    /// Error that is not tied to a specific part of the input
+    pub fn without_span(kind: CalcErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    pub fn kind(&self) -> &CalcErrorKind {
+        &self.kind
+    }
+
+    /// Byte range of the offending token in the evaluated expression
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl error::Error for CalcError {}
+
+impl From<CalcError> for String {
+    fn from(error: CalcError) -> Self {
+        error.to_string()
+    }
+}
@@ -0,0 +1,303 @@
+// Number formatting shared by calculator and conversion output
+//
+// Numbers are rounded on their decimal digits rather than as f64, so big
+// integers and exact fractions keep every digit they have. Floats start from
+// their shortest round-trip representation. Rounding is half away from zero.
+
+/// Significant figures kept for nonzero values that fixed decimals would show as 0
+const SMALL_VALUE_FIGURES: usize = 4;
+
+/// How a number is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// Plain decimal, e.g. `0.000015`
+    #[default]
+    Fixed,
+    /// One digit before the point and a power of ten, e.g. `1.157e-14`
+    Scientific,
+    /// Like scientific, with the exponent a multiple of three, e.g. `11.57e-15`
+    Engineering,
+}
+
+/// Notation, rounding and digit grouping for printed numbers
+///
+/// The default leaves numbers as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NumberFormat {
+    pub notation: Notation,
+    /// Digits after the point, padded with zeros; in scientific and engineering
+    /// notation, digits after the point of the mantissa
+    pub decimals: Option<usize>,
+    /// Significant figures, trailing zeros after the point trimmed; takes
+    /// precedence over `decimals`
+    pub significant_figures: Option<usize>,
+    /// Separator between groups of three integer digits, e.g. `_` or `,`
+    pub separator: Option<char>,
+}
+
+impl NumberFormat {
+    /// Format a float; infinities and NaN are printed as they are
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() || *self == Self::default() {
+            return value.to_string();
+        }
+        self.format_str(&format!("{:e}", value))
+    }
+
+    /// Like `format`, but a nonzero value that `decimals` would round to 0 keeps
+    /// significant figures instead, so `1.2e-5` to 2 decimals is not shown as `0.00`
+    ///
+    /// Meant for a default precision; an explicit one goes through `format` and is
+    /// applied as given. The fallback keeps [`SMALL_VALUE_FIGURES`] significant
+    /// figures, in scientific notation below 0.0001.
+    pub fn format_nonzero(&self, value: f64) -> String {
+        let (Some(decimals), None, Notation::Fixed) =
+            (self.decimals, self.significant_figures, self.notation)
+        else {
+            return self.format(value);
+        };
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let Some(decimal) = Decimal::parse(&format!("{:e}", value)) else {
+            return self.format(value);
+        };
+        let mut rounded = decimal.clone();
+        rounded.round_to(rounded.exponent + decimals as i64);
+        if !rounded.digits.is_empty() || decimal.digits.is_empty() {
+            return self.render(decimal);
+        }
+
+        let notation = match decimal.exponent < -3 {
+            true => Notation::Scientific,
+            false => Notation::Fixed,
+        };
+        NumberFormat {
+            notation,
+            decimals: None,
+            significant_figures: Some(SMALL_VALUE_FIGURES),
+            ..*self
+        }
+        .render(decimal)
+    }
+
+    /// Format a number written in decimal (`-1234.5`, `1.5e-9`)
+    ///
+    /// Text that is not a decimal number is returned unchanged.
+    pub fn format_str(&self, text: &str) -> String {
+        if *self == Self::default() {
+            return text.to_string();
+        }
+        match Decimal::parse(text) {
+            Some(decimal) => self.render(decimal),
+            None => text.to_string(),
+        }
+    }
+
+    fn render(&self, mut decimal: Decimal) -> String {
+        let sign = |decimal: &Decimal| if decimal.is_negative() { "-" } else { "" };
+
+        match self.notation {
+            Notation::Fixed => {
+                let min_decimals = match (self.significant_figures, self.decimals) {
+                    (Some(figures), _) => {
+                        decimal.round_to(figures.max(1) as i64);
+                        0
+                    }
+                    (None, Some(decimals)) => {
+                        decimal.round_to(decimal.exponent + decimals as i64);
+                        decimals
+                    }
+                    (None, None) => 0,
+                };
+                let (int_part, frac_part) = decimal.split(decimal.exponent, min_decimals);
+                format!(
+                    "{}{}{}",
+                    sign(&decimal),
+                    self.group(&int_part),
+                    point(&frac_part)
+                )
+            }
+            Notation::Scientific | Notation::Engineering => {
+                // Rounding can carry into a new leading digit (9.99 -> 10.0), which
+                // moves the exponent, so round again against the new exponent
+                let mut int_digits = 1;
+                for _ in 0..2 {
+                    int_digits = self.mantissa_digits(&decimal);
+                    match (self.significant_figures, self.decimals) {
+                        (Some(figures), _) => decimal.round_to(figures.max(1) as i64),
+                        (None, Some(decimals)) => decimal.round_to(int_digits + decimals as i64),
+                        (None, None) => {}
+                    }
+                }
+                if decimal.digits.is_empty() {
+                    let (_, frac_part) = decimal.split(1, self.min_mantissa_decimals());
+                    return format!("0{}e0", point(&frac_part));
+                }
+
+                let (int_part, frac_part) = decimal.split(int_digits, self.min_mantissa_decimals());
+                format!(
+                    "{}{}{}e{}",
+                    sign(&decimal),
+                    int_part,
+                    point(&frac_part),
+                    decimal.exponent - int_digits
+                )
+            }
+        }
+    }
+
+    /// Digits of the mantissa before the point
+    fn mantissa_digits(&self, decimal: &Decimal) -> i64 {
+        match self.notation {
+            Notation::Engineering => (decimal.exponent - 1).rem_euclid(3) + 1,
+            _ => 1,
+        }
+    }
+
+    fn min_mantissa_decimals(&self) -> usize {
+        match self.significant_figures {
+            Some(_) => 0,
+            None => self.decimals.unwrap_or(0),
+        }
+    }
+
+    fn group(&self, int_part: &str) -> String {
+        let Some(separator) = self.separator else {
+            return int_part.to_string();
+        };
+        let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+}
+
+fn point(frac_part: &str) -> String {
+    if frac_part.is_empty() {
+        String::new()
+    } else {
+        format!(".{}", frac_part)
+    }
+}
+
+/// A decimal number `0.d1 d2 d3 ... * 10^exponent`
+#[derive(Debug, Clone)]
+struct Decimal {
+    negative: bool,
+    /// Digits without leading or trailing zeros; empty for zero
+    digits: Vec<u8>,
+    exponent: i64,
+}
+
+impl Decimal {
+    fn parse(text: &str) -> Option<Decimal> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return None;
+        }
+        if !int_part
+            .chars()
+            .chain(frac_part.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let mut digits: Vec<u8> = int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .map(|b| b - b'0')
+            .collect();
+        let mut exponent = int_part.len() as i64 + exponent;
+        let leading_zeros = digits.iter().take_while(|&&d| d == 0).count();
+        digits.drain(..leading_zeros);
+        exponent -= leading_zeros as i64;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Some(Decimal {
+            negative,
+            digits,
+            exponent,
+        })
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative && !self.digits.is_empty()
+    }
+
+    /// Keep the first `keep` digits, rounding half away from zero
+    fn round_to(&mut self, keep: i64) {
+        if keep >= self.digits.len() as i64 {
+            return;
+        }
+        if keep < 0 {
+            self.digits.clear();
+            return;
+        }
+
+        let keep = keep as usize;
+        let round_up = self.digits[keep] >= 5;
+        self.digits.truncate(keep);
+        if round_up {
+            let mut i = keep;
+            loop {
+                if i == 0 {
+                    self.digits.insert(0, 1);
+                    self.exponent += 1;
+                    break;
+                }
+                i -= 1;
+                if self.digits[i] == 9 {
+                    self.digits[i] = 0;
+                } else {
+                    self.digits[i] += 1;
+                    break;
+                }
+            }
+        }
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    /// Digits before and after a point placed after the first `int_digits` digits,
+    /// with the fraction padded to at least `min_decimals` digits
+    fn split(&self, int_digits: i64, min_decimals: usize) -> (String, String) {
+        let digits: String = self.digits.iter().map(|d| (b'0' + d) as char).collect();
+        let (int_part, mut frac_part) = if digits.is_empty() {
+            ("0".to_string(), String::new())
+        } else if int_digits <= 0 {
+            // Numbers below one have a zero before the point
+            let zeros = "0".repeat(int_digits.unsigned_abs() as usize);
+            ("0".to_string(), zeros + &digits)
+        } else if int_digits as usize >= digits.len() {
+            let zeros = "0".repeat(int_digits as usize - digits.len());
+            (digits + &zeros, String::new())
+        } else {
+            let (int_part, frac_part) = digits.split_at(int_digits as usize);
+            (int_part.to_string(), frac_part.to_string())
+        };
+
+        while frac_part.len() < min_decimals {
+            frac_part.push('0');
+        }
+        (int_part, frac_part)
+    }
+}
@@ -0,0 +1,257 @@
+// Built-in functions and named constants for the calculator
+//
+// Rounding, `abs`, `min`/`max` and square roots of perfect squares stay exact;
+// transcendental functions always produce an f64.
+
+use super::number::{self, Number};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Function {
+    Sqrt,
+    Abs,
+    Floor,
+    Ceil,
+    Round,
+    Ln,
+    Log10,
+    Log2,
+    Exp,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Min,
+    Max,
+}
+
+impl Function {
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        let function = match name.to_ascii_lowercase().as_str() {
+            "sqrt" => Function::Sqrt,
+            "abs" => Function::Abs,
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            "round" => Function::Round,
+            "ln" => Function::Ln,
+            "log10" | "log" => Function::Log10,
+            "log2" => Function::Log2,
+            "exp" => Function::Exp,
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "tan" => Function::Tan,
+            "asin" => Function::Asin,
+            "acos" => Function::Acos,
+            "atan" => Function::Atan,
+            "atan2" => Function::Atan2,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            _ => return None,
+        };
+        Some(function)
+    }
+
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Function::Sqrt => "sqrt",
+            Function::Abs => "abs",
+            Function::Floor => "floor",
+            Function::Ceil => "ceil",
+            Function::Round => "round",
+            Function::Ln => "ln",
+            Function::Log10 => "log10",
+            Function::Log2 => "log2",
+            Function::Exp => "exp",
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Asin => "asin",
+            Function::Acos => "acos",
+            Function::Atan => "atan",
+            Function::Atan2 => "atan2",
+            Function::Min => "min",
+            Function::Max => "max",
+        }
+    }
+
+    /// Minimum and maximum number of arguments accepted
+    const fn arity(self) -> (usize, usize) {
+        match self {
+            Function::Round => (1, 2),
+            Function::Atan2 => (2, 2),
+            Function::Min | Function::Max => (1, usize::MAX),
+            _ => (1, 1),
+        }
+    }
+}
+
+/// Look up a named constant
+pub(super) fn constant(name: &str) -> Option<Number> {
+    match name.to_ascii_lowercase().as_str() {
+        "pi" => Some(Number::Float(std::f64::consts::PI)),
+        "tau" => Some(Number::Float(std::f64::consts::TAU)),
+        "e" => Some(Number::Float(std::f64::consts::E)),
+        _ => None,
+    }
+}
+
+/// Apply a function to its evaluated arguments
+pub(super) fn apply_function(function: Function, args: &[Number]) -> Result<Number, String> {
+    let (min_args, max_args) = function.arity();
+    if args.len() < min_args || args.len() > max_args {
+        let expected = match (min_args, max_args) {
+            (min, usize::MAX) => format!("at least {}", min),
+            (min, max) if min == max => min.to_string(),
+            (min, max) => format!("{} to {}", min, max),
+        };
+        let plural = match (min_args, max_args) {
+            (_, 1) | (1, usize::MAX) => "",
+            _ => "s",
+        };
+        return Err(format!(
+            "{}() expects {} argument{}, got {}",
+            function.name(),
+            expected,
+            plural,
+            args.len()
+        ));
+    }
+
+    let x = &args[0];
+    let result = match function {
+        Function::Abs => match x {
+            Number::Float(f) => Number::Float(f.abs()),
+            exact if exact.compare(&Number::Int(0)) == Ordering::Less => exact.clone().negate(),
+            exact => exact.clone(),
+        },
+        Function::Floor => round_to_int(x, Rounding::Floor),
+        Function::Ceil => round_to_int(x, Rounding::Ceil),
+        Function::Round => match args.get(1) {
+            None => round_to_int(x, Rounding::Nearest),
+            Some(Number::Int(digits)) => round_to_digits(x, *digits)?,
+            Some(_) => {
+                return Err("round() digits must be an integer".to_string());
+            }
+        },
+        Function::Min | Function::Max => {
+            let mut best = x;
+            for candidate in &args[1..] {
+                let replace = match function {
+                    Function::Min => candidate.compare(best) == Ordering::Less,
+                    _ => candidate.compare(best) == Ordering::Greater,
+                };
+                if replace {
+                    best = candidate;
+                }
+            }
+            best.clone()
+        }
+        Function::Sqrt => {
+            if x.to_float() < 0.0 {
+                return Err(
+                    "Complex numbers not supported (square root of negative number)".to_string(),
+                );
+            }
+            // Keep perfect squares exact
+            number::exact_sqrt(x).unwrap_or_else(|| Number::Float(x.to_float().sqrt()))
+        }
+        Function::Ln | Function::Log10 | Function::Log2 => {
+            let value = x.to_float();
+            if value <= 0.0 {
+                return Err(format!(
+                    "{}() is undefined for zero or negative numbers",
+                    function.name()
+                ));
+            }
+            Number::Float(match function {
+                Function::Ln => value.ln(),
+                Function::Log10 => value.log10(),
+                _ => value.log2(),
+            })
+        }
+        Function::Asin | Function::Acos => {
+            let value = x.to_float();
+            if !(-1.0..=1.0).contains(&value) {
+                return Err(format!(
+                    "{}() argument must be between -1 and 1",
+                    function.name()
+                ));
+            }
+            Number::Float(match function {
+                Function::Asin => value.asin(),
+                _ => value.acos(),
+            })
+        }
+        Function::Exp => Number::Float(x.to_float().exp()),
+        Function::Sin => Number::Float(x.to_float().sin()),
+        Function::Cos => Number::Float(x.to_float().cos()),
+        Function::Tan => Number::Float(x.to_float().tan()),
+        Function::Atan => Number::Float(x.to_float().atan()),
+        Function::Atan2 => Number::Float(x.to_float().atan2(args[1].to_float())),
+    };
+
+    if let Number::Float(f) = result
+        && !f.is_finite()
+    {
+        return Err("Calculation overflow".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Rounding direction for `floor`, `ceil` and `round`
+#[derive(Debug, Clone, Copy)]
+enum Rounding {
+    Floor,
+    Ceil,
+    /// Halves round away from zero
+    Nearest,
+}
+
+/// Round a number to an integer, keeping the exact path when possible
+#[inline]
+fn round_to_int(x: &Number, rounding: Rounding) -> Number {
+    match x {
+        Number::Int(_) | Number::Big(_) => x.clone(),
+        Number::Ratio(r) => Number::from_ratio(match rounding {
+            Rounding::Floor => r.floor(),
+            Rounding::Ceil => r.ceil(),
+            Rounding::Nearest => r.round(),
+        }),
+        Number::Float(f) => {
+            let rounded = match rounding {
+                Rounding::Floor => f.floor(),
+                Rounding::Ceil => f.ceil(),
+                Rounding::Nearest => f.round(),
+            };
+            number::float_to_integer(rounded).unwrap_or(Number::Float(rounded))
+        }
+    }
+}
+
+/// Round a number to the given number of decimal digits (negative digits round to tens, hundreds, ...)
+fn round_to_digits(x: &Number, digits: i128) -> Result<Number, String> {
+    if digits == 0 {
+        return Ok(round_to_int(x, Rounding::Nearest));
+    }
+    if !(-308..=308).contains(&digits) {
+        return Err("round() digits must be between -308 and 308".to_string());
+    }
+    if digits > 0 && matches!(x, Number::Int(_) | Number::Big(_)) {
+        return Ok(x.clone());
+    }
+    if let Some(exact) = x.to_ratio() {
+        return Ok(number::exact_round(&exact, digits));
+    }
+
+    let factor = 10f64.powi(digits as i32);
+    let rounded = (x.to_float() * factor).round() / factor;
+    if digits < 0 {
+        Ok(round_to_int(&Number::Float(rounded), Rounding::Nearest))
+    } else {
+        Ok(Number::Float(rounded))
+    }
+}
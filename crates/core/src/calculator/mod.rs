@@ -0,0 +1,990 @@
+// Calculator module for evaluating mathematical expressions
+//
+// Design decisions:
+// - Shunting-yard algorithm for O(n) parsing
+// - Hybrid i128/f64 to maximize precision while supporting decimals; i128
+//   overflow promotes to big integers, and exact mode keeps decimals as fractions
+//   (see `number`)
+// - Zero-copy string iteration where possible
+// - Pre-allocated vectors based on input size
+// - Errors are `CalcError`s carrying the byte span of the offending token, so
+//   callers can point at the mistake
+// - Functions and constants resolved at tokenize time, arity resolved during
+//   the shunting-yard pass so evaluation stays a single stack walk
+// - Bitwise operators work on the i128 integer path only; multi-character
+//   operators (`<<`, `>>`, `xor`) are mapped to single internal chars
+// - Variables and the previous result live in a `Context`; the stateless
+//   `evaluate` uses a fresh one so both paths share the same pipeline
+// - Results are printed through `NumberFormat`, which conversion output shares,
+//   so notation, rounding and grouping look the same in both
+// - The pipeline is generic over `Operand`, so unit expressions in `conversion`
+//   reuse this tokenizer and evaluator instead of a grammar of their own
+
+mod error;
+mod format;
+mod functions;
+mod number;
+mod operand;
+
+use functions::constant;
+use num_bigint::BigInt;
+use std::{collections::HashMap, iter, ops::Range, str};
+
+pub(crate) use functions::Function;
+pub(crate) use number::Number;
+pub(crate) use operand::{Operand, Value};
+
+pub use error::{CalcError, CalcErrorKind};
+pub use format::{Notation, NumberFormat};
+pub use number::DEFAULT_SIGNIFICANT_DIGITS;
+
+/// Internal operator chars for operators that are not a single input character
+const SHIFT_LEFT: char = '\u{226A}'; // `<<`
+const SHIFT_RIGHT: char = '\u{226B}'; // `>>`
+const XOR: char = '\u{22BB}'; // `xor`
+
+/// Output radix for integer results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Decimal,
+    Hexadecimal,
+    Binary,
+    Octal,
+}
+
+#[derive(Debug, Clone)]
+enum Token<T = Number> {
+    Number(T),
+    /// Unit name read by `Operand::read_unit`; folded into the number before it
+    /// by `fold_units`
+    Unit(Result<T, String>),
+    Operator(char),
+    /// Prefix operator (unary minus) applied to the following operand
+    UnaryOperator(char),
+    /// Function call; the argument count is filled in by `infix_to_postfix`
+    Function(Function, usize),
+    Comma,
+    LeftParen,
+    RightParen,
+}
+
+/// Byte range of a token in the input expression
+type Span = Range<usize>;
+
+/// Names that cannot be used as variables
+const RESERVED_NAMES: [&str; 3] = ["ans", "_", "xor"];
+
+/// Evaluation context that keeps variables and the previous result between calls
+///
+/// Supports assignments (`rate = 1500 / 60`) and `ans` / `_` for the previous result.
+#[derive(Debug, Clone)]
+pub struct Context {
+    variables: HashMap<String, Value>,
+    last_result: Option<Value>,
+    /// Keep decimals and inexact division results as exact fractions
+    exact: bool,
+    /// Significant digits used when printing exact fractions
+    significant_digits: usize,
+    /// Notation, rounding and grouping of printed results
+    format: NumberFormat,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            variables: HashMap::new(),
+            last_result: None,
+            exact: false,
+            significant_digits: DEFAULT_SIGNIFICANT_DIGITS,
+            format: NumberFormat::default(),
+        }
+    }
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Context that evaluates decimals exactly (`0.1 + 0.2` is `0.3`) and prints
+    /// non-terminating results with the given number of significant digits
+    pub fn exact(significant_digits: usize) -> Self {
+        Self {
+            exact: true,
+            significant_digits: significant_digits.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Print results with the given notation, rounding and grouping
+    pub fn with_format(self, format: NumberFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Evaluate an expression or assignment and return the result as a string
+    pub fn evaluate(&mut self, expr: &str) -> Result<String, CalcError> {
+        self.evaluate_number(expr).map(|result| {
+            self.format
+                .format_str(&result.format(self.significant_digits))
+        })
+    }
+
+    /// Evaluate an expression or assignment that must produce an integer result
+    pub fn evaluate_integer(&mut self, expr: &str) -> Result<i128, CalcError> {
+        let message = match self.evaluate_number(expr)? {
+            Number::Int(i) => return Ok(i),
+            Number::Big(b) => format!("Result {} does not fit in 128 bits", b),
+            other => format!(
+                "Result {} is not an integer and cannot be shown in another base",
+                other.format(self.significant_digits)
+            ),
+        };
+        Err(CalcError::without_span(CalcErrorKind::Evaluation(message)))
+    }
+
+    /// Variables defined so far as `(name, value)` pairs, sorted by name
+    pub fn variables(&self) -> Vec<(String, String)> {
+        let mut variables: Vec<(String, String)> = self
+            .variables
+            .iter()
+            .map(|(name, value)| (name.clone(), value.format(self.significant_digits)))
+            .collect();
+        variables.sort();
+        variables
+    }
+
+    /// Forget all variables and the previous result
+    pub fn clear(&mut self) {
+        self.variables.clear();
+        self.last_result = None;
+    }
+
+    fn evaluate_number(&mut self, expr: &str) -> Result<Number, CalcError> {
+        self.evaluate_operand(expr)
+    }
+
+    /// Evaluate an expression or assignment, storing the result as `ans`
+    pub(crate) fn evaluate_operand<T: Operand>(&mut self, expr: &str) -> Result<T, CalcError> {
+        let (name, offset) = split_assignment(expr)?;
+        let result: T = self.evaluate_from(expr, offset)?;
+
+        if let Some(name) = name {
+            self.variables
+                .insert(name.to_string(), result.clone().into_value());
+        }
+        self.last_result = Some(result.clone().into_value());
+        Ok(result)
+    }
+
+    /// Evaluate `expr` from byte `offset` on, without assigning or storing the result
+    ///
+    /// Error spans are relative to the whole of `expr`.
+    pub(crate) fn evaluate_from<T: Operand>(
+        &self,
+        expr: &str,
+        offset: usize,
+    ) -> Result<T, CalcError> {
+        if expr[offset..].trim().is_empty() {
+            return Err(CalcError::without_span(CalcErrorKind::EmptyExpression));
+        }
+
+        let tokens = fold_units(tokenize(expr, offset, self)?, self.exact)?;
+        let postfix = infix_to_postfix(tokens)?;
+        evaluate_postfix(postfix, self.exact)
+    }
+
+    /// What an expression or assignment contains, or `None` when it cannot be read
+    pub(crate) fn shape<T: Operand>(&self, expr: &str) -> Option<Shape> {
+        let (_, offset) = split_assignment(expr).ok()?;
+        let tokens = tokenize::<T>(expr, offset, self).ok()?;
+        Some(Shape {
+            units: tokens.iter().any(|(token, _)| match token {
+                Token::Unit(_) => true,
+                Token::Number(operand) => operand.has_units(),
+                _ => false,
+            }),
+            arithmetic: tokens.iter().any(|(token, _)| {
+                matches!(
+                    token,
+                    Token::Operator(_) | Token::Function(..) | Token::LeftParen | Token::RightParen
+                )
+            }),
+        })
+    }
+}
+
+/// What an expression contains, for callers that choose how to evaluate input
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Shape {
+    /// At least one known unit, or a literal that carries one
+    pub(crate) units: bool,
+    /// Binary operators, parentheses or function calls
+    pub(crate) arithmetic: bool,
+}
+
+/// Evaluate a mathematical expression and return the result as a string
+pub fn evaluate(expr: &str) -> Result<String, CalcError> {
+    Context::new().evaluate(expr)
+}
+
+/// Evaluate a mathematical expression in exact mode, printing non-terminating
+/// results with the given number of significant digits
+pub fn evaluate_exact(expr: &str, significant_digits: usize) -> Result<String, CalcError> {
+    Context::exact(significant_digits).evaluate(expr)
+}
+
+/// Evaluate an expression that must produce an integer result
+pub fn evaluate_integer(expr: &str) -> Result<i128, CalcError> {
+    Context::new().evaluate_integer(expr)
+}
+
+/// Parse a single numeric literal (`42`, `-1.5`, `0xff`, `1_000`, `1e3`) with the calculator's syntax
+pub fn parse_literal(literal: &str) -> Result<f64, CalcError> {
+    let trimmed = literal.trim();
+    let invalid = || {
+        CalcError::new(
+            CalcErrorKind::InvalidNumber(trimmed.to_string()),
+            0..literal.len(),
+        )
+    };
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    if !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return Err(invalid());
+    }
+
+    // The first character is consumed up front, as in `tokenize`
+    let mut chars = digits.char_indices().peekable();
+    chars.next();
+    let number = parse_number(digits, 0, &mut chars, false).map_err(|_| invalid())?;
+    if chars.next().is_some() {
+        return Err(invalid());
+    }
+
+    let value = number.to_float();
+    Ok(if negative { -value } else { value })
+}
+
+/// Split `name = expr` into the variable name and the byte offset of the expression
+fn split_assignment(expr: &str) -> Result<(Option<&str>, usize), CalcError> {
+    let Some((target, _)) = expr.split_once('=') else {
+        return Ok((None, 0));
+    };
+
+    let name = target.trim();
+    let start = target.len() - target.trim_start().len();
+    let span = start..start + name.len();
+    let mut name_chars = name.chars();
+    let is_identifier = name_chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name_chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(CalcError::new(
+            CalcErrorKind::InvalidAssignmentTarget(name.to_string()),
+            span,
+        ));
+    }
+
+    let lowercase = name.to_ascii_lowercase();
+    if RESERVED_NAMES.contains(&lowercase.as_str())
+        || constant(name).is_some()
+        || Function::from_name(name).is_some()
+    {
+        return Err(CalcError::new(
+            CalcErrorKind::ReservedName(name.to_string()),
+            span,
+        ));
+    }
+
+    Ok((Some(name), target.len() + 1))
+}
+
+/// Format an integer in the given radix with a `0x`/`0b`/`0o` prefix
+///
+/// Negative numbers are shown with a leading `-` unless a bit width is given,
+/// in which case they are shown in two's complement and zero-padded to the width.
+pub fn format_radix(value: i128, radix: Radix, bits: Option<u32>) -> Result<String, String> {
+    let (prefix, digit_bits) = match radix {
+        Radix::Decimal => return Ok(value.to_string()),
+        Radix::Hexadecimal => ("0x", 4),
+        Radix::Binary => ("0b", 1),
+        Radix::Octal => ("0o", 3),
+    };
+
+    let Some(bits) = bits else {
+        let sign = if value < 0 { "-" } else { "" };
+        let magnitude = value.unsigned_abs();
+        let digits = match radix {
+            Radix::Hexadecimal => format!("{:x}", magnitude),
+            Radix::Binary => format!("{:b}", magnitude),
+            _ => format!("{:o}", magnitude),
+        };
+        return Ok(format!("{}{}{}", sign, prefix, digits));
+    };
+
+    if !(1..=128).contains(&bits) {
+        return Err("Bit width must be between 1 and 128".to_string());
+    }
+
+    // Accept both the signed and unsigned range of the width
+    let min = if bits == 128 {
+        i128::MIN
+    } else {
+        -(1i128 << (bits - 1))
+    };
+    let fits = value >= min && (bits >= 127 || value < (1i128 << bits));
+    if !fits {
+        return Err(format!("Value {} does not fit in {} bits", value, bits));
+    }
+
+    let mask = if bits == 128 {
+        u128::MAX
+    } else {
+        (1u128 << bits) - 1
+    };
+    let raw = (value as u128) & mask;
+    let width = bits.div_ceil(digit_bits) as usize;
+    let digits = match radix {
+        Radix::Hexadecimal => format!("{:0width$x}", raw, width = width),
+        Radix::Binary => format!("{:0width$b}", raw, width = width),
+        _ => format!("{:0width$o}", raw, width = width),
+    };
+    Ok(format!("{}{}", prefix, digits))
+}
+
+/// Tokenize the input expression into tokens with their byte spans, starting at `offset`
+fn tokenize<T: Operand>(
+    expr: &str,
+    offset: usize,
+    context: &Context,
+) -> Result<Vec<(Token<T>, Span)>, CalcError> {
+    // Pre-allocate based on input length (rough heuristic: ~1 token per 2 chars)
+    let mut tokens: Vec<(Token<T>, Span)> = Vec::with_capacity(expr.len() / 2 + 1);
+    let mut chars = expr.char_indices().peekable();
+    // Skip the assignment target, if any
+    skip_to(&mut chars, offset);
+
+    while let Some((start_pos, c)) = chars.next() {
+        // Skip whitespace
+        if c.is_whitespace() {
+            continue;
+        }
+
+        // Literals such as `1h30m` that the operand reads itself
+        if c.is_ascii_digit()
+            && let Some((operand, len)) = T::read_literal(&expr[start_pos..])
+        {
+            skip_to(&mut chars, start_pos + len);
+            tokens.push((Token::Number(operand), start_pos..start_pos + len));
+            continue;
+        }
+
+        // Handle numbers (including decimals)
+        if c.is_ascii_digit() || c == '.' {
+            let number = parse_number(expr, start_pos, &mut chars, context.exact)?;
+            tokens.push((
+                Token::Number(number.into()),
+                start_pos..next_pos(expr, &mut chars),
+            ));
+            continue;
+        }
+
+        // Handle function names, constants and variables, then units
+        if c.is_ascii_alphabetic() || c == '_' {
+            let name = parse_identifier(expr, start_pos, &mut chars);
+            let span = start_pos..start_pos + name.len();
+            // Resolving skips whitespace after the name, which a unit must not consume
+            let mut lookahead = chars.clone();
+            let resolved = resolve_identifier(name, &mut lookahead, context);
+            if let Err(CalcErrorKind::UnknownVariable(_)) = resolved
+                && let Some((unit, len)) = T::read_unit(&expr[start_pos..])
+            {
+                skip_to(&mut chars, start_pos + len);
+                tokens.push((Token::Unit(unit), start_pos..start_pos + len));
+                continue;
+            }
+            let token = resolved.map_err(|kind| CalcError::new(kind, span.clone()))?;
+            chars = lookahead;
+            tokens.push((token, span));
+            continue;
+        }
+
+        let char_span = start_pos..start_pos + c.len_utf8();
+
+        // Unit names that start with a symbol or a non-ASCII letter, like `°F` or `µs`
+        if !c.is_ascii()
+            && let Some((unit, len)) = T::read_unit(&expr[start_pos..])
+        {
+            skip_to(&mut chars, start_pos + len);
+            tokens.push((Token::Unit(unit), start_pos..start_pos + len));
+            continue;
+        }
+
+        // Handle operators and parentheses
+        match c {
+            '+' | '*' | '/' | '%' | '^' | '&' | '|' => {
+                tokens.push((Token::Operator(c), char_span));
+            }
+            '<' | '>' => {
+                // Only the shift operators `<<` and `>>` are supported
+                if chars.next_if(|(_, next)| *next == c).is_none() {
+                    return Err(CalcError::new(
+                        CalcErrorKind::InvalidCharacter(c),
+                        char_span,
+                    ));
+                }
+                let op = if c == '<' { SHIFT_LEFT } else { SHIFT_RIGHT };
+                tokens.push((Token::Operator(op), start_pos..start_pos + 2));
+            }
+            '~' => tokens.push((Token::UnaryOperator(c), char_span)),
+            '-' => {
+                // Check if this is a unary minus (negative number)
+                let is_unary = matches!(
+                    tokens.last(),
+                    None | Some((
+                        Token::Operator(_)
+                            | Token::UnaryOperator(_)
+                            | Token::LeftParen
+                            | Token::Comma,
+                        _
+                    ))
+                );
+
+                if !is_unary {
+                    tokens.push((Token::Operator(c), char_span));
+                    continue;
+                }
+
+                // Parse negative number
+                if let Some(&(digit_pos, next_char)) = chars.peek()
+                    && (next_char.is_ascii_digit() || next_char == '.')
+                {
+                    chars.next();
+                    let number = parse_number(expr, digit_pos, &mut chars, context.exact)?;
+                    let span = start_pos..next_pos(expr, &mut chars);
+                    tokens.push((Token::Number(number.negate().into()), span));
+                    continue;
+                }
+                // Negation of a constant, function call, parenthesized group or
+                // bitwise NOT
+                if let Some((_, next_char)) = chars.peek()
+                    && (next_char.is_ascii_alphabetic() || matches!(next_char, '(' | '~'))
+                {
+                    tokens.push((Token::UnaryOperator(c), char_span));
+                    continue;
+                }
+                return Err(CalcError::new(
+                    CalcErrorKind::ExpectedNumberAfterMinus,
+                    char_span,
+                ));
+            }
+            ',' => tokens.push((Token::Comma, char_span)),
+            '(' => tokens.push((Token::LeftParen, char_span)),
+            ')' => tokens.push((Token::RightParen, char_span)),
+            _ => {
+                return Err(CalcError::new(
+                    CalcErrorKind::InvalidCharacter(c),
+                    char_span,
+                ));
+            }
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(CalcError::without_span(CalcErrorKind::EmptyExpression));
+    }
+
+    Ok(tokens)
+}
+
+/// Consume characters up to byte position `end`
+#[inline]
+fn skip_to(chars: &mut iter::Peekable<str::CharIndices>, end: usize) {
+    while chars.next_if(|(pos, _)| *pos < end).is_some() {}
+}
+
+/// Fold each unit into the number written before it, so `2 GiB / 30 s` divides
+/// `2 GiB` by `30 s`
+///
+/// A power directly after a unit applies to the unit alone (`3 m^2` is three square
+/// meters), and a unit without a number stands for one of it.
+fn fold_units<T: Operand>(
+    tokens: Vec<(Token<T>, Span)>,
+    exact: bool,
+) -> Result<Vec<(Token<T>, Span)>, CalcError> {
+    let mut output: Vec<(Token<T>, Span)> = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some((token, span)) = tokens.next() {
+        let Token::Unit(unit) = token else {
+            output.push((token, span));
+            continue;
+        };
+        let mut unit = unit
+            .map_err(|message| CalcError::new(CalcErrorKind::Evaluation(message), span.clone()))?;
+        let mut span = span;
+
+        if matches!(tokens.peek(), Some((Token::Operator('^'), _))) {
+            let mut lookahead = tokens.clone();
+            lookahead.next();
+            if let Some((Token::Number(exponent), exponent_span)) = lookahead.next() {
+                unit = T::apply_operator('^', unit, exponent, exact)
+                    .map_err(evaluation_error(&span))?;
+                span.end = exponent_span.end;
+                tokens = lookahead;
+            }
+        }
+
+        if matches!(output.last(), Some((Token::Number(_), _)))
+            && let Some((Token::Number(value), value_span)) = output.pop()
+        {
+            unit = T::apply_operator('*', value, unit, exact).map_err(evaluation_error(&span))?;
+            span.start = value_span.start;
+        }
+        output.push((Token::Number(unit), span));
+    }
+    Ok(output)
+}
+
+/// Byte position of the next unconsumed character
+#[inline]
+fn next_pos(expr: &str, chars: &mut iter::Peekable<str::CharIndices>) -> usize {
+    chars.peek().map_or(expr.len(), |(pos, _)| *pos)
+}
+
+/// Parse a number whose first character at `start_pos` has already been consumed
+#[inline]
+fn parse_number(
+    expr: &str,
+    start_pos: usize,
+    chars: &mut iter::Peekable<str::CharIndices>,
+    exact: bool,
+) -> Result<Number, CalcError> {
+    let radix = match expr.get(start_pos..start_pos + 2) {
+        Some("0x" | "0X") => 16,
+        Some("0b" | "0B") => 2,
+        Some("0o" | "0O") => 8,
+        _ => 10,
+    };
+    if radix != 10 {
+        return parse_radix_literal(expr, start_pos, radix, chars);
+    }
+
+    let mut end_pos = start_pos;
+    let mut has_decimal = expr.as_bytes()[start_pos] == b'.';
+    let mut has_separator = false;
+
+    // Consume digits, `_` separators and at most one decimal point
+    while let Some(&(idx, c)) = chars.peek() {
+        if c.is_ascii_digit() {
+            end_pos = idx;
+            chars.next();
+        } else if c == '_' {
+            has_separator = true;
+            end_pos = idx;
+            chars.next();
+        } else if c == '.' {
+            if has_decimal {
+                return Err(CalcError::new(
+                    CalcErrorKind::MultipleDecimalPoints,
+                    start_pos..idx + 1,
+                ));
+            }
+            has_decimal = true;
+            end_pos = idx;
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    // Exponent, as in `1e3` or `2.5E-4`; only with digits after it, so `5em` stays a unit
+    let mantissa_end = end_pos + 1;
+    let after_e = expr[mantissa_end..].strip_prefix(['e', 'E']);
+    if let Some(after_e) = after_e {
+        let unsigned = after_e.strip_prefix(['+', '-']).unwrap_or(after_e);
+        let digit_count = unsigned.bytes().take_while(u8::is_ascii_digit).count();
+        if digit_count > 0 {
+            let len = 1 + (after_e.len() - unsigned.len()) + digit_count;
+            end_pos = mantissa_end + len - 1;
+            for _ in 0..len {
+                chars.next();
+            }
+        }
+    }
+
+    let num_str = &expr[start_pos..=end_pos];
+    let mantissa = &expr[start_pos..mantissa_end];
+    let exponent = num_str.get(mantissa.len() + 1..);
+    let span = start_pos..end_pos + 1;
+
+    // Validate and parse
+    if num_str == "." {
+        return Err(CalcError::new(CalcErrorKind::StandaloneDecimalPoint, span));
+    }
+
+    let invalid = || {
+        CalcError::new(
+            CalcErrorKind::InvalidNumber(num_str.to_string()),
+            span.clone(),
+        )
+    };
+    let digits = if has_separator {
+        strip_separators(mantissa).ok_or_else(invalid)?
+    } else {
+        mantissa.to_string()
+    };
+
+    let number = if let Some(exponent) = exponent {
+        let exponent = exponent.parse::<i128>().map_err(|_| invalid())?;
+        scale_literal(&digits, exponent, exact)
+    } else if has_decimal && exact {
+        Number::parse_exact_decimal(&digits)
+    } else if has_decimal {
+        digits.parse::<f64>().ok().map(Number::Float)
+    } else {
+        // Integers too large for i128 continue as big integers
+        digits
+            .parse::<i128>()
+            .map(Number::Int)
+            .ok()
+            .or_else(|| digits.parse::<BigInt>().ok().map(Number::from_big))
+    };
+    number.ok_or_else(invalid)
+}
+
+/// Value of a literal with an exponent, `digits * 10^exponent`
+///
+/// Integer values stay exact; fractions are kept in exact mode and become floats otherwise.
+fn scale_literal(digits: &str, exponent: i128, exact: bool) -> Option<Number> {
+    let mantissa = Number::parse_exact_decimal(digits)?;
+    let scale = number::power(Number::Int(10), Number::Int(exponent), true).ok()?;
+    let value = number::multiply(mantissa, scale).ok()?;
+    match value {
+        Number::Ratio(_) if !exact => Some(Number::Float(value.to_float())),
+        value => Some(value),
+    }
+}
+
+/// Parse a `0x`, `0b` or `0o` prefixed integer literal
+fn parse_radix_literal(
+    expr: &str,
+    start_pos: usize,
+    radix: u32,
+    chars: &mut iter::Peekable<str::CharIndices>,
+) -> Result<Number, CalcError> {
+    let mut end_pos = start_pos;
+
+    // The prefix may or may not have been consumed already by the caller
+    while let Some(&(idx, c)) = chars.peek() {
+        if idx < start_pos + 2 || c.is_ascii_alphanumeric() || c == '_' {
+            end_pos = idx;
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    let literal = &expr[start_pos..=end_pos];
+    let invalid = || {
+        CalcError::new(
+            CalcErrorKind::InvalidNumber(literal.to_string()),
+            start_pos..end_pos + 1,
+        )
+    };
+    let digits = strip_separators(&literal[2..])
+        .filter(|digits| !digits.is_empty())
+        .ok_or_else(invalid)?;
+
+    i128::from_str_radix(&digits, radix)
+        .map(Number::Int)
+        .ok()
+        .or_else(|| BigInt::parse_bytes(digits.as_bytes(), radix).map(Number::from_big))
+        .ok_or_else(invalid)
+}
+
+/// Remove `_` digit separators, which are only allowed between digits
+fn strip_separators(num_str: &str) -> Option<String> {
+    if num_str.starts_with('_') || num_str.ends_with('_') || num_str.contains("__") {
+        return None;
+    }
+    Some(num_str.replace('_', ""))
+}
+
+/// Consume an identifier (letters, digits and underscores) whose first character
+/// at `start_pos` has already been consumed
+#[inline]
+fn parse_identifier<'a>(
+    expr: &'a str,
+    start_pos: usize,
+    chars: &mut iter::Peekable<str::CharIndices>,
+) -> &'a str {
+    let mut end_pos = start_pos;
+    while let Some(&(idx, c)) = chars.peek() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            end_pos = idx;
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    &expr[start_pos..=end_pos]
+}
+
+/// Turn an identifier into a function token (when followed by '('), a constant,
+/// a variable or the previous result
+fn resolve_identifier<T: Operand>(
+    name: &str,
+    chars: &mut iter::Peekable<str::CharIndices>,
+    context: &Context,
+) -> Result<Token<T>, CalcErrorKind> {
+    if name.eq_ignore_ascii_case("xor") {
+        return Ok(Token::Operator(XOR));
+    }
+
+    // Skip whitespace between a function name and its opening parenthesis
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+    if matches!(chars.peek(), Some((_, '('))) {
+        return Function::from_name(name)
+            .map(|function| Token::Function(function, 0))
+            .ok_or_else(|| CalcErrorKind::UnknownFunction(name.to_string()));
+    }
+
+    let stored = |value: &Value| {
+        T::from_value(value)
+            .map(Token::Number)
+            .ok_or_else(|| CalcErrorKind::UnitValue(name.to_string()))
+    };
+
+    if name.eq_ignore_ascii_case("ans") || name == "_" {
+        return context
+            .last_result
+            .as_ref()
+            .ok_or(CalcErrorKind::NoPreviousResult)
+            .and_then(stored);
+    }
+
+    if let Some(value) = constant(name) {
+        return Ok(Token::Number(value.into()));
+    }
+    context
+        .variables
+        .get(name)
+        .ok_or_else(|| CalcErrorKind::UnknownVariable(name.to_string()))
+        .and_then(stored)
+}
+
+/// Get the precedence of an operator (higher number = higher precedence)
+///
+/// Bitwise operators follow C: `|` < `xor` < `&` < shifts < arithmetic
+#[inline]
+const fn precedence(op: char) -> u8 {
+    match op {
+        '|' => 1,
+        XOR => 2,
+        '&' => 3,
+        SHIFT_LEFT | SHIFT_RIGHT => 4,
+        '+' | '-' => 5,
+        '*' | '/' | '%' => 6,
+        '^' => 7,
+        _ => 0,
+    }
+}
+
+/// Unary operators bind tighter than any binary operator, matching the
+/// behavior of negative literals (`-2^2` is `(-2)^2`)
+const UNARY_PRECEDENCE: u8 = 8;
+
+/// Check if an operator is right-associative
+#[inline]
+const fn is_right_associative(op: char) -> bool {
+    op == '^'
+}
+
+/// Precedence of an operator sitting on the operator stack, if it is one
+#[inline]
+const fn stacked_precedence<T>(token: &(Token<T>, Span)) -> Option<u8> {
+    match token.0 {
+        Token::Operator(op) => Some(precedence(op)),
+        Token::UnaryOperator(_) => Some(UNARY_PRECEDENCE),
+        _ => None,
+    }
+}
+
+/// Convert infix notation to postfix notation using the Shunting-yard algorithm
+///
+/// Also checks that operands and operators alternate, so mistakes are reported
+/// at the offending token rather than as a stack underflow during evaluation.
+fn infix_to_postfix<T>(tokens: Vec<(Token<T>, Span)>) -> Result<Vec<(Token<T>, Span)>, CalcError> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut operator_stack: Vec<(Token<T>, Span)> = Vec::with_capacity(tokens.len() / 4 + 1);
+    // Argument count and first comma for each open parenthesis (only meaningful for
+    // function calls)
+    let mut arg_counts: Vec<(usize, Option<Span>)> = Vec::new();
+    let mut previous_was_left_paren = false;
+    // True while the next token must start an operand
+    let mut expects_operand = true;
+    let mut last_span = 0..0;
+
+    for (token, span) in tokens {
+        let is_left_paren = matches!(token, Token::LeftParen);
+        let starts_operand = matches!(
+            token,
+            Token::Number(_)
+                | Token::Unit(_)
+                | Token::UnaryOperator(_)
+                | Token::Function(..)
+                | Token::LeftParen
+        );
+        if starts_operand && !expects_operand {
+            return Err(CalcError::new(CalcErrorKind::MissingOperator, span));
+        }
+        let needs_operand_before = matches!(token, Token::Operator(_) | Token::Comma)
+            || (matches!(token, Token::RightParen) && !previous_was_left_paren);
+        if needs_operand_before && expects_operand {
+            return Err(CalcError::new(CalcErrorKind::MissingOperand, span));
+        }
+        expects_operand = !matches!(token, Token::Number(_) | Token::Unit(_) | Token::RightParen);
+        last_span = span.clone();
+
+        match token {
+            Token::Number(_) | Token::Unit(_) => output.push((token, span)),
+            Token::Operator(op) => {
+                // Pop operators with higher or equal precedence (respecting associativity)
+                while let Some(top_precedence) = operator_stack.last().and_then(stacked_precedence)
+                {
+                    let should_pop = if is_right_associative(op) {
+                        precedence(op) < top_precedence
+                    } else {
+                        precedence(op) <= top_precedence
+                    };
+
+                    if should_pop {
+                        output.push(operator_stack.pop().unwrap());
+                    } else {
+                        break;
+                    }
+                }
+                operator_stack.push((token, span));
+            }
+            // Prefix operators and functions wait for their operands
+            Token::UnaryOperator(_) | Token::Function(..) => operator_stack.push((token, span)),
+            Token::LeftParen => {
+                operator_stack.push((token, span));
+                arg_counts.push((1, None));
+            }
+            Token::Comma => {
+                while let Some((top, _)) = operator_stack.last() {
+                    if matches!(top, Token::LeftParen) {
+                        break;
+                    }
+                    output.push(operator_stack.pop().unwrap());
+                }
+                match arg_counts.last_mut() {
+                    Some((count, first_comma)) => {
+                        *count += 1;
+                        first_comma.get_or_insert(span);
+                    }
+                    None => return Err(CalcError::new(CalcErrorKind::UnexpectedComma, span)),
+                }
+            }
+            Token::RightParen => {
+                let mut found_left_paren = false;
+                while let Some(top) = operator_stack.pop() {
+                    if matches!(top.0, Token::LeftParen) {
+                        found_left_paren = true;
+                        break;
+                    }
+                    output.push(top);
+                }
+                if !found_left_paren {
+                    return Err(CalcError::new(CalcErrorKind::MismatchedParentheses, span));
+                }
+
+                // An empty pair of parentheses means a call without arguments
+                let (mut args, first_comma) = arg_counts.pop().unwrap_or((1, None));
+                if previous_was_left_paren {
+                    args = 0;
+                }
+
+                if let Some(&(Token::Function(function, _), _)) = operator_stack.last() {
+                    let (_, name_span) = operator_stack.pop().unwrap();
+                    output.push((Token::Function(function, args), name_span));
+                } else if args == 0 {
+                    return Err(CalcError::new(CalcErrorKind::MissingOperand, span));
+                } else if args != 1 {
+                    let span = first_comma.unwrap_or(span);
+                    return Err(CalcError::new(CalcErrorKind::UnexpectedComma, span));
+                }
+            }
+        }
+        previous_was_left_paren = is_left_paren;
+    }
+
+    if expects_operand {
+        return Err(CalcError::new(CalcErrorKind::MissingOperand, last_span));
+    }
+
+    // Pop remaining operators
+    while let Some((token, span)) = operator_stack.pop() {
+        if matches!(token, Token::LeftParen) {
+            return Err(CalcError::new(CalcErrorKind::MismatchedParentheses, span));
+        }
+        output.push((token, span));
+    }
+
+    Ok(output)
+}
+
+/// Evaluate a postfix expression
+fn evaluate_postfix<T: Operand>(
+    tokens: Vec<(Token<T>, Span)>,
+    exact: bool,
+) -> Result<T, CalcError> {
+    let mut stack = Vec::with_capacity(tokens.len() / 2 + 1);
+    let missing_operand = |span: &Span| CalcError::new(CalcErrorKind::MissingOperand, span.clone());
+
+    for (token, span) in tokens {
+        match token {
+            Token::Number(num) => stack.push(num),
+            Token::Operator(op) => {
+                if stack.len() < 2 {
+                    return Err(missing_operand(&span));
+                }
+                let right = stack.pop().unwrap();
+                let left = stack.pop().unwrap();
+                let result =
+                    T::apply_operator(op, left, right, exact).map_err(evaluation_error(&span))?;
+                stack.push(result);
+            }
+            Token::UnaryOperator(op) => {
+                let operand = stack.pop().ok_or_else(|| missing_operand(&span))?;
+                stack.push(T::apply_unary_operator(op, operand).map_err(evaluation_error(&span))?);
+            }
+            Token::Function(function, argc) => {
+                if stack.len() < argc {
+                    return Err(missing_operand(&span));
+                }
+                let args = stack.split_off(stack.len() - argc);
+                stack.push(T::apply_function(function, &args).map_err(evaluation_error(&span))?);
+            }
+            _ => return Err(missing_operand(&span)),
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(CalcError::without_span(CalcErrorKind::MissingOperator));
+    }
+
+    Ok(stack.pop().unwrap())
+}
+
+/// Attach the span of the operator or function to an arithmetic error
+#[inline]
+fn evaluation_error(span: &Span) -> impl FnOnce(String) -> CalcError + '_ {
+    move |message| CalcError::new(CalcErrorKind::Evaluation(message), span.clone())
+}
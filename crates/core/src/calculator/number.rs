@@ -0,0 +1,529 @@
+// Numeric tower used by the calculator
+//
+// Design decisions:
+// - i128 fast path for everyday integer math
+// - Integers that overflow i128 are promoted to BigInt instead of losing precision
+// - Exact fractions (BigRational) appear in exact mode, where decimals and inexact
+//   division would otherwise fall back to f64, and for quotients of integers from
+//   2^53 on, whose fractional part (and low digits) f64 cannot hold
+// - f64 is contagious: once an operand is a float, the result is a float
+// - Results are collapsed to the smallest exact representation after each step
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+/// Significant digits used to print exact fractions unless configured otherwise
+pub const DEFAULT_SIGNIFICANT_DIGITS: usize = 20;
+
+/// Largest result (in bits) that exact exponentiation and shifts may produce
+const MAX_RESULT_BITS: u64 = 1 << 20;
+
+/// Magnitude from which f64 can no longer hold every integer (2^53)
+const EXACT_FLOAT_LIMIT: i128 = 1 << 53;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Number {
+    Int(i128),
+    /// Integer outside the i128 range
+    Big(BigInt),
+    /// Exact fraction (exact mode, or a large quotient of integers)
+    Ratio(BigRational),
+    Float(f64),
+}
+
+/// Both operands of a binary operation promoted to a common representation
+enum Operands {
+    Int(i128, i128),
+    Big(BigInt, BigInt),
+    Ratio(BigRational, BigRational),
+    Float(f64, f64),
+}
+
+impl Number {
+    #[inline]
+    pub(super) fn from_big(value: BigInt) -> Number {
+        value
+            .to_i128()
+            .map(Number::Int)
+            .unwrap_or(Number::Big(value))
+    }
+
+    #[inline]
+    pub(super) fn from_ratio(value: BigRational) -> Number {
+        if value.is_integer() {
+            Number::from_big(value.to_integer())
+        } else {
+            Number::Ratio(value)
+        }
+    }
+
+    /// Parse a plain decimal literal (digits with an optional decimal point) exactly
+    pub(super) fn parse_exact_decimal(digits: &str) -> Option<Number> {
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let numerator: BigInt = format!("0{}{}", whole, fraction).parse().ok()?;
+        let denominator = BigInt::from(10).pow(fraction.len());
+        Some(Number::from_ratio(BigRational::new(numerator, denominator)))
+    }
+
+    #[inline]
+    pub(crate) fn to_float(&self) -> f64 {
+        match self {
+            Number::Int(i) => *i as f64,
+            Number::Big(b) => b.to_f64().unwrap_or(f64::NAN),
+            Number::Ratio(r) => r.to_f64().unwrap_or(f64::NAN),
+            Number::Float(f) => *f,
+        }
+    }
+
+    #[inline]
+    fn to_big(&self) -> Option<BigInt> {
+        match self {
+            Number::Int(i) => Some(BigInt::from(*i)),
+            Number::Big(b) => Some(b.clone()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(super) fn to_ratio(&self) -> Option<BigRational> {
+        match self {
+            Number::Ratio(r) => Some(r.clone()),
+            Number::Float(_) => None,
+            integer => integer.to_big().map(BigRational::from_integer),
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_float(&self) -> bool {
+        matches!(self, Number::Float(_))
+    }
+
+    pub(crate) fn negate(self) -> Number {
+        match self {
+            Number::Int(i) => i
+                .checked_neg()
+                .map(Number::Int)
+                .unwrap_or_else(|| Number::Big(-BigInt::from(i))),
+            Number::Big(b) => Number::from_big(-b),
+            Number::Ratio(r) => Number::Ratio(-r),
+            Number::Float(f) => Number::Float(-f),
+        }
+    }
+
+    /// Compare two numbers, exactly unless one of them is a float
+    pub(super) fn compare(&self, other: &Number) -> Ordering {
+        match (self.to_ratio(), other.to_ratio()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => self
+                .to_float()
+                .partial_cmp(&other.to_float())
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+
+    /// Format the number, printing exact fractions with the given significant digits
+    pub(crate) fn format(&self, significant_digits: usize) -> String {
+        match self {
+            Number::Int(i) => i.to_string(),
+            Number::Big(b) => b.to_string(),
+            Number::Ratio(r) => format_ratio(r, significant_digits),
+            Number::Float(f) => f.to_string(),
+        }
+    }
+}
+
+/// Promote both operands to the widest representation of the two
+#[inline]
+fn promote(left: Number, right: Number) -> Operands {
+    match (left, right) {
+        (Number::Int(a), Number::Int(b)) => Operands::Int(a, b),
+        (a, b) if a.is_float() || b.is_float() => Operands::Float(a.to_float(), b.to_float()),
+        (a @ Number::Ratio(_), b) | (a, b @ Number::Ratio(_)) => {
+            Operands::Ratio(a.to_ratio().unwrap(), b.to_ratio().unwrap())
+        }
+        (a, b) => Operands::Big(a.to_big().unwrap(), b.to_big().unwrap()),
+    }
+}
+
+/// Round `numerator / denominator` (both non-negative) to the nearest integer, halves up
+#[inline]
+fn round_half_up(numerator: &BigInt, denominator: &BigInt) -> BigInt {
+    (numerator * 2 + denominator) / (denominator * 2)
+}
+
+/// Render a fraction as a decimal rounded to the given significant digits
+fn format_ratio(value: &BigRational, significant_digits: usize) -> String {
+    let significant_digits = significant_digits.max(1);
+    let sign = if value.is_negative() { "-" } else { "" };
+    let numerator = value.numer().abs();
+    let denominator = value.denom().clone();
+
+    // Decimal places needed to show the requested significant digits
+    let whole = &numerator / &denominator;
+    let decimal_places = if whole.is_zero() {
+        let mut leading_zeros = 0;
+        let mut scaled = &numerator * 10;
+        while scaled < denominator {
+            scaled *= 10;
+            leading_zeros += 1;
+        }
+        significant_digits + leading_zeros
+    } else {
+        let whole_digits = whole.to_string().len();
+        if whole_digits >= significant_digits {
+            // No room left for the fraction, so show the digits that fit in scientific
+            // notation rather than an integer that looks exact
+            return format_ratio_scientific(
+                sign,
+                &numerator,
+                &denominator,
+                whole_digits,
+                significant_digits,
+            );
+        }
+        significant_digits - whole_digits
+    };
+
+    let scale = BigInt::from(10).pow(decimal_places);
+    let digits = round_half_up(&(numerator * &scale), &denominator).to_string();
+    if decimal_places == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimal_places + 1);
+    let (int_part, frac_part) = padded.split_at(padded.len() - decimal_places);
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.is_empty() {
+        format!("{}{}", sign, int_part)
+    } else {
+        format!("{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+/// Render `numerator / denominator`, whose integer part has `whole_digits` digits, as
+/// `d.ddde<exponent>` with the given significant digits
+fn format_ratio_scientific(
+    sign: &str,
+    numerator: &BigInt,
+    denominator: &BigInt,
+    whole_digits: usize,
+    significant_digits: usize,
+) -> String {
+    let divisor = denominator * BigInt::from(10).pow(whole_digits - significant_digits);
+    let mut digits = round_half_up(numerator, &divisor).to_string();
+    let mut exponent = whole_digits - 1;
+    // Rounding up may carry into a new digit, as in 9.99 -> 10.0
+    if digits.len() > significant_digits {
+        digits.truncate(significant_digits);
+        exponent += 1;
+    }
+
+    let (first, rest) = digits.split_at(1);
+    let rest = rest.trim_end_matches('0');
+    if rest.is_empty() {
+        format!("{}{}e{}", sign, first, exponent)
+    } else {
+        format!("{}{}.{}e{}", sign, first, rest, exponent)
+    }
+}
+
+#[inline]
+pub(super) fn add(left: Number, right: Number) -> Result<Number, String> {
+    Ok(match promote(left, right) {
+        Operands::Int(a, b) => a
+            .checked_add(b)
+            .map(Number::Int)
+            .unwrap_or_else(|| Number::from_big(BigInt::from(a) + b)),
+        Operands::Big(a, b) => Number::from_big(a + b),
+        Operands::Ratio(a, b) => Number::from_ratio(a + b),
+        Operands::Float(a, b) => Number::Float(a + b),
+    })
+}
+
+#[inline]
+pub(super) fn subtract(left: Number, right: Number) -> Result<Number, String> {
+    Ok(match promote(left, right) {
+        Operands::Int(a, b) => a
+            .checked_sub(b)
+            .map(Number::Int)
+            .unwrap_or_else(|| Number::from_big(BigInt::from(a) - b)),
+        Operands::Big(a, b) => Number::from_big(a - b),
+        Operands::Ratio(a, b) => Number::from_ratio(a - b),
+        Operands::Float(a, b) => Number::Float(a - b),
+    })
+}
+
+#[inline]
+pub(super) fn multiply(left: Number, right: Number) -> Result<Number, String> {
+    Ok(match promote(left, right) {
+        Operands::Int(a, b) => a
+            .checked_mul(b)
+            .map(Number::Int)
+            .unwrap_or_else(|| Number::from_big(BigInt::from(a) * b)),
+        Operands::Big(a, b) => Number::from_big(a * b),
+        Operands::Ratio(a, b) => Number::from_ratio(a * b),
+        Operands::Float(a, b) => Number::Float(a * b),
+    })
+}
+
+/// Divide two numbers; inexact integer division yields a fraction in exact mode
+/// and a float otherwise
+#[inline]
+pub(super) fn divide(left: Number, right: Number, exact: bool) -> Result<Number, String> {
+    match promote(left, right) {
+        Operands::Int(a, b) => {
+            if b == 0 {
+                return Err("Division by zero".to_string());
+            }
+            // Check for exact division (checked to guard i128::MIN / -1)
+            match (a.checked_rem(b), a.checked_div(b)) {
+                (Some(0), Some(quotient)) => Ok(Number::Int(quotient)),
+                (_, Some(quotient)) if !exact && quotient.abs() < EXACT_FLOAT_LIMIT => {
+                    Ok(Number::Float(a as f64 / b as f64))
+                }
+                _ => Ok(Number::from_ratio(BigRational::new(a.into(), b.into()))),
+            }
+        }
+        Operands::Big(a, b) => {
+            if b.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+            let quotient = BigRational::new(a, b);
+            if exact
+                || quotient.is_integer()
+                || quotient.abs().to_integer() >= EXACT_FLOAT_LIMIT.into()
+            {
+                Ok(Number::from_ratio(quotient))
+            } else {
+                Ok(Number::Float(quotient.to_f64().unwrap_or(f64::NAN)))
+            }
+        }
+        Operands::Ratio(a, b) => {
+            if b.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number::from_ratio(a / b))
+        }
+        Operands::Float(a, b) => {
+            // For floats, exact zero comparison is correct since we control the inputs
+            if b == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number::Float(a / b))
+        }
+    }
+}
+
+#[inline]
+pub(super) fn modulo(left: Number, right: Number) -> Result<Number, String> {
+    match promote(left, right) {
+        Operands::Int(a, b) => {
+            if b == 0 {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number::Int(a.checked_rem(b).unwrap_or(0)))
+        }
+        Operands::Big(a, b) => {
+            if b.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number::from_big(a % b))
+        }
+        Operands::Ratio(a, b) => {
+            if b.is_zero() {
+                return Err("Division by zero".to_string());
+            }
+            // Truncated remainder, matching the sign rules of the integer path
+            let quotient = (&a / &b).trunc();
+            Ok(Number::from_ratio(a - b * quotient))
+        }
+        Operands::Float(a, b) => {
+            if b == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number::Float(a % b))
+        }
+    }
+}
+
+#[inline]
+pub(super) fn power(left: Number, right: Number, exact: bool) -> Result<Number, String> {
+    match (&left, &right) {
+        (base, Number::Int(exponent)) if !base.is_float() => exact_power(base, *exponent, exact),
+        (base, Number::Big(_)) if !base.is_float() => {
+            let magnitude = base.to_ratio().unwrap().abs();
+            if magnitude.is_zero() || magnitude.is_one() {
+                // 0, 1 and -1 stay small no matter how large the exponent is
+                let exponent = right.to_ratio().unwrap().to_integer();
+                let reduced = if exponent.is_even() { 2 } else { 1 };
+                let reduced = if exponent.is_negative() {
+                    -reduced
+                } else {
+                    reduced
+                };
+                exact_power(base, reduced, exact)
+            } else {
+                Err("Exponent too large".to_string())
+            }
+        }
+        (base, exponent) if base.to_float() < 0.0 && exponent.to_float().fract() != 0.0 => {
+            // Negative base with fractional exponent is complex (not supported)
+            Err(
+                "Complex numbers not supported (negative base with fractional exponent)"
+                    .to_string(),
+            )
+        }
+        (base, exponent) => Ok(Number::Float(base.to_float().powf(exponent.to_float()))),
+    }
+}
+
+/// Raise an exact number to an integer power
+fn exact_power(base: &Number, exponent: i128, exact: bool) -> Result<Number, String> {
+    // Handle special cases for mathematical correctness
+    if base.to_ratio().is_some_and(|b| b.is_zero()) {
+        if exponent == 0 {
+            // 0^0 is often defined as 1 by convention in discrete mathematics and programming contexts
+            return Ok(Number::Int(1));
+        } else if exponent < 0 {
+            // 0^negative is undefined (division by zero)
+            return Err("Division by zero".to_string());
+        }
+        return Ok(Number::Int(0));
+    }
+
+    if exponent.unsigned_abs() > u32::MAX as u128 {
+        return Err("Exponent too large".to_string());
+    }
+    let magnitude = exponent.unsigned_abs() as u32;
+
+    if exponent < 0 && !exact {
+        // Negative exponent results in a float (fraction) outside exact mode
+        return Ok(Number::Float(base.to_float().powf(exponent as f64)));
+    }
+
+    // Try integer exponentiation first
+    if let (Number::Int(b), true) = (base, exponent >= 0)
+        && let Some(result) = b.checked_pow(magnitude)
+    {
+        return Ok(Number::Int(result));
+    }
+
+    let base = base.to_ratio().unwrap();
+    let base_bits = base.numer().bits().max(base.denom().bits());
+    if base_bits.saturating_mul(magnitude as u64) > MAX_RESULT_BITS {
+        return Err("Calculation overflow".to_string());
+    }
+
+    let result = Pow::pow(&base, magnitude);
+    if exponent < 0 {
+        Ok(Number::from_ratio(result.recip()))
+    } else {
+        Ok(Number::from_ratio(result))
+    }
+}
+
+/// Apply a bitwise or shift operator on the integer path
+#[inline]
+pub(super) fn bitwise(op: char, left: Number, right: Number) -> Result<Number, String> {
+    if matches!(left, Number::Ratio(_) | Number::Float(_))
+        || matches!(right, Number::Ratio(_) | Number::Float(_))
+    {
+        return Err("Bitwise operators require integer operands".to_string());
+    }
+
+    if op == super::SHIFT_LEFT || op == super::SHIFT_RIGHT {
+        return shift(op, left, right);
+    }
+
+    let result = match promote(left, right) {
+        Operands::Int(a, b) => Number::Int(match op {
+            '&' => a & b,
+            '|' => a | b,
+            _ => a ^ b,
+        }),
+        Operands::Big(a, b) => Number::from_big(match op {
+            '&' => a & b,
+            '|' => a | b,
+            _ => a ^ b,
+        }),
+        _ => unreachable!("bitwise operands are integers"),
+    };
+
+    Ok(result)
+}
+
+fn shift(op: char, left: Number, right: Number) -> Result<Number, String> {
+    let amount = match right {
+        Number::Int(amount) if (0..=MAX_RESULT_BITS as i128).contains(&amount) => amount as u32,
+        _ => {
+            return Err(format!(
+                "Shift amount must be between 0 and {}",
+                MAX_RESULT_BITS
+            ));
+        }
+    };
+
+    match (left, op == super::SHIFT_LEFT) {
+        (Number::Int(a), false) => Ok(Number::Int(a >> amount.min(127))),
+        (Number::Int(a), true) if amount < 128 && (a << amount) >> amount == a => {
+            Ok(Number::Int(a << amount))
+        }
+        // Bits shifted out of the i128 range continue on the big integer path
+        (value, left_shift) => {
+            let value = value.to_big().unwrap();
+            Ok(Number::from_big(if left_shift {
+                value << amount
+            } else {
+                value >> amount
+            }))
+        }
+    }
+}
+
+pub(super) fn bit_not(operand: Number) -> Result<Number, String> {
+    match operand {
+        Number::Int(i) => Ok(Number::Int(!i)),
+        Number::Big(b) => Ok(Number::from_big(!b)),
+        _ => Err("Bitwise operators require integer operands".to_string()),
+    }
+}
+
+/// Exact square root for perfect squares (and fractions of perfect squares)
+pub(super) fn exact_sqrt(value: &Number) -> Option<Number> {
+    let ratio = value.to_ratio()?;
+    if ratio.is_negative() {
+        return None;
+    }
+
+    let numerator = ratio.numer().sqrt();
+    let denominator = ratio.denom().sqrt();
+    let root = BigRational::new(numerator, denominator);
+    (&root * &root == ratio).then(|| Number::from_ratio(root))
+}
+
+/// Round an exact number to the given number of decimal digits, halves away from zero
+pub(super) fn exact_round(value: &BigRational, digits: i128) -> Number {
+    let scale = BigRational::from_integer(BigInt::from(10).pow(digits.unsigned_abs() as u32));
+    let rounded = if digits >= 0 {
+        (value * &scale).round() / scale
+    } else {
+        (value / &scale).round() * scale
+    };
+    Number::from_ratio(rounded)
+}
+
+/// Whether a whole-number float can be represented on the integer path
+///
+/// Floats from 2^53 on are only approximately the value they stand for, e.g. the
+/// float quotient of two big integers, so they stay floats rather than print as
+/// integers whose low digits look exact but are not.
+#[inline]
+pub(super) fn float_to_integer(value: f64) -> Option<Number> {
+    if !value.is_finite() || value.fract() != 0.0 || value.abs() >= EXACT_FLOAT_LIMIT as f64 {
+        return None;
+    }
+    Some(Number::Int(value as i128))
+}
@@ -0,0 +1,104 @@
+// Values the calculator pipeline evaluates
+//
+// The tokenizer, the shunting-yard pass and the evaluator are generic over
+// `Operand`, so unit expressions (`conversion::expression`) share the grammar,
+// number literals, functions, variables and `ans` of plain arithmetic. Plain
+// numbers never read units; quantities do, and carry their dimension along.
+
+use super::functions::{Function, apply_function};
+use super::number::{self, Number};
+use super::{SHIFT_LEFT, SHIFT_RIGHT, XOR};
+use crate::conversion::expression::Quantity;
+
+/// A stored variable or previous result
+#[derive(Debug, Clone)]
+pub(crate) enum Value {
+    Number(Number),
+    /// Result of a unit expression, e.g. `2 GiB / 30 s`
+    Quantity(Quantity),
+}
+
+impl Value {
+    pub(super) fn format(&self, significant_digits: usize) -> String {
+        match self {
+            Value::Number(number) => number.format(significant_digits),
+            Value::Quantity(quantity) => quantity.format(significant_digits),
+        }
+    }
+}
+
+pub(crate) trait Operand: Clone + From<Number> {
+    /// Operand for a stored value, or `None` when this kind of operand cannot hold it
+    fn from_value(value: &Value) -> Option<Self>;
+
+    fn into_value(self) -> Value;
+
+    /// Whether the operand carries a unit, as a literal like `1h30m` does
+    fn has_units(&self) -> bool {
+        false
+    }
+
+    /// Read a literal that is more than a plain number, such as `1h30m`, at the
+    /// start of `input`, returning it with its byte length
+    fn read_literal(_input: &str) -> Option<(Self, usize)> {
+        None
+    }
+
+    /// Read a unit name at the start of `input`, returning it with its byte length
+    ///
+    /// `None` means the operand has no units, so the name stays unknown.
+    fn read_unit(_input: &str) -> Option<(Result<Self, String>, usize)> {
+        None
+    }
+
+    fn apply_operator(op: char, left: Self, right: Self, exact: bool) -> Result<Self, String>;
+
+    fn apply_unary_operator(op: char, operand: Self) -> Result<Self, String>;
+
+    fn apply_function(function: Function, args: &[Self]) -> Result<Self, String>;
+}
+
+impl Operand for Number {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(number) => Some(number.clone()),
+            Value::Quantity(_) => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Number(self)
+    }
+
+    #[inline]
+    fn apply_operator(
+        op: char,
+        left: Number,
+        right: Number,
+        exact: bool,
+    ) -> Result<Number, String> {
+        match op {
+            '+' => number::add(left, right),
+            '-' => number::subtract(left, right),
+            '*' => number::multiply(left, right),
+            '/' => number::divide(left, right, exact),
+            '%' => number::modulo(left, right),
+            '^' => number::power(left, right, exact),
+            '&' | '|' | XOR | SHIFT_LEFT | SHIFT_RIGHT => number::bitwise(op, left, right),
+            _ => Err(format!("Unknown operator: {}", op)),
+        }
+    }
+
+    #[inline]
+    fn apply_unary_operator(op: char, operand: Number) -> Result<Number, String> {
+        match op {
+            '-' => Ok(operand.negate()),
+            '~' => number::bit_not(operand),
+            _ => Err(format!("Unknown operator: {}", op)),
+        }
+    }
+
+    fn apply_function(function: Function, args: &[Number]) -> Result<Number, String> {
+        apply_function(function, args)
+    }
+}
@@ -0,0 +1,94 @@
+//! Compound targets like `ft+in`, `h+min+s` and `d+h`.
+//!
+//! A compound result is carried as a value in the first (largest) unit. When it is
+//! formatted, every unit but the last takes the integer part and passes the remainder
+//! on, so `180 cm to ft+in` is shown as `5 ft 10.87 in`.
+
+use super::suggestions::unknown_unit_error;
+use super::types::{Unit, find_unit};
+
+/// Check whether a target names several units joined with `+`
+pub fn is_compound(target: &str) -> bool {
+    target.contains('+')
+}
+
+/// Resolve the units of a compound target, largest first
+///
+/// All units must be linear, in the same category, and ordered from largest to smallest.
+pub fn parse_target(target: &str) -> Result<Vec<(&'static Unit, String)>, String> {
+    let parts = target
+        .split('+')
+        .map(|name| {
+            let name = name.trim();
+            find_unit(name)
+                .map(|unit| (unit, name.to_string()))
+                .ok_or_else(|| {
+                    unknown_unit_error(
+                        &format!("Unknown unit in compound target '{}'", target),
+                        name,
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if parts.len() < 2 {
+        return Err(format!(
+            "Compound target '{}' needs at least two units, e.g. ft+in",
+            target
+        ));
+    }
+
+    let (first, first_name) = &parts[0];
+    for (unit, name) in &parts[1..] {
+        if unit.category != first.category {
+            return Err(format!(
+                "Cannot combine '{}' ({}) and '{}' ({}) in a compound target",
+                first_name, first.category, name, unit.category
+            ));
+        }
+    }
+
+    let mut previous: Option<f64> = None;
+    for (unit, name) in &parts {
+        let multiplier = unit
+            .to_base_multiplier
+            .ok_or_else(|| format!("Unit '{}' cannot be used in a compound target", name))?;
+        if previous.is_some_and(|p| multiplier >= p) {
+            return Err(format!(
+                "Units in compound target '{}' must go from largest to smallest, e.g. ft+in",
+                target
+            ));
+        }
+        previous = Some(multiplier);
+    }
+
+    Ok(parts)
+}
+
+/// Split the absolute value of a value given in the first unit into one value per unit
+///
+/// Every part but the last is an integer. The last part is rounded to `decimals`
+/// before splitting, so a remainder never rounds up to a whole larger unit
+/// (`119.999 min` becomes `2 h 0 min`, not `1 h 60 min`).
+pub fn split(value: f64, units: &[&Unit], decimals: usize) -> Vec<f64> {
+    let multiplier = |unit: &Unit| unit.to_base_multiplier.unwrap_or(1.0);
+    let Some(last) = units.last() else {
+        return Vec::new();
+    };
+    let scale = 10f64.powi(decimals as i32);
+
+    // Work in the smallest unit so integer parts divide cleanly
+    let total = value.abs() * multiplier(units[0]) / multiplier(last);
+    let mut remaining = (total * scale).round() / scale;
+
+    let mut parts = Vec::with_capacity(units.len());
+    for unit in &units[..units.len() - 1] {
+        let size = multiplier(unit) / multiplier(last);
+        // Tolerate ratios like 0.3048 / 0.0254 that are not exactly 12
+        let count = (remaining / size + 1e-9).floor();
+        remaining = (remaining - count * size).max(0.0);
+        parts.push(count);
+    }
+    parts.push((remaining * scale).round() / scale);
+    parts
+}
@@ -0,0 +1,234 @@
+//! Offline currency conversion from a rate table.
+//!
+//! Rates come from a file the user maintains; nothing is fetched from the network.
+//! A table has a base currency, the date its rates were taken, and the amount of each
+//! currency that one unit of the base buys. In JSON:
+//!
+//! ```json
+//! {"base": "USD", "date": "2026-10-01", "rates": {"EUR": 0.92, "GBP": 0.79}}
+//! ```
+//!
+//! or as CSV rows of `date,base,currency,rate`, with an optional header:
+//!
+//! ```csv
+//! date,base,currency,rate
+//! 2026-10-01,USD,EUR,0.92
+//! 2026-10-01,USD,GBP,0.79
+//! ```
+//!
+//! Loading a table registers each currency code as a unit of the Currency category,
+//! with the base currency as the base unit, so conversions between any two currencies
+//! go through the base (`EUR -> USD -> GBP`). Codes match case-insensitively, except
+//! where a lowercase code is already another unit: `cup` stays a volume, `CUP` is
+//! the Cuban peso.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use chrono::NaiveDate;
+use json::JsonValue;
+
+use super::types::{Unit, UnitCategory, find_unit, replace_units};
+
+/// Active ISO 4217 codes, used to tell a missing rate table from a misspelled unit
+const ISO_4217: &str = "AED AFN ALL AMD ANG AOA ARS AUD AWG AZN BAM BBD BDT BGN BHD BIF BMD \
+    BND BOB BRL BSD BTN BWP BYN BZD CAD CDF CHF CLP CNY COP CRC CUP CVE CZK DJF DKK DOP DZD \
+    EGP ERN ETB EUR FJD FKP GBP GEL GHS GIP GMD GNF GTQ GYD HKD HNL HTG HUF IDR ILS INR IQD \
+    IRR ISK JMD JOD JPY KES KGS KHR KMF KPW KRW KWD KYD KZT LAK LBP LKR LRD LSL LYD MAD MDL \
+    MGA MKD MMK MNT MOP MRU MUR MVR MWK MXN MYR MZN NAD NGN NIO NOK NPR NZD OMR PAB PEN PGK \
+    PHP PKR PLN PYG QAR RON RSD RUB RWF SAR SBD SCR SDG SEK SGD SHP SLE SOS SRD SSP STN SYP \
+    SZL THB TJS TMT TND TOP TRY TTD TWD TZS UAH UGX USD UYU UZS VES VND VUV WST XAF XCD XOF \
+    XPF YER ZAR ZMW ZWG";
+
+/// Exchange rates relative to a base currency, as of a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateTable {
+    /// Base currency code, e.g. `USD`
+    pub base: String,
+    /// Date the rates were taken, `YYYY-MM-DD`
+    pub date: String,
+    /// Amount of each currency that one unit of the base buys
+    pub rates: BTreeMap<String, f64>,
+}
+
+static RATE_TABLE: RwLock<Option<RateTable>> = RwLock::new(None);
+
+/// Parse a JSON rate table: `{"base": "USD", "date": "2026-10-01", "rates": {"EUR": 0.92}}`
+pub fn parse_json(input: &str) -> Result<RateTable, String> {
+    let value = json::parse(input).map_err(|e| format!("Invalid rates file: {}", e))?;
+    let field = |key: &str| {
+        value[key]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| format!("Invalid rates file: '{}' must be a string", key))
+    };
+    let (base, date) = (field("base")?, field("date")?);
+
+    let JsonValue::Object(entries) = &value["rates"] else {
+        return Err("Invalid rates file: 'rates' must be an object of currency codes".to_string());
+    };
+    let rates = entries
+        .iter()
+        .map(|(code, rate)| {
+            rate.as_f64()
+                .map(|rate| (code.to_string(), rate))
+                .ok_or_else(|| format!("Invalid rates file: rate of '{}' must be a number", code))
+        })
+        .collect::<Result<_, _>>()?;
+
+    validate(RateTable { base, date, rates })
+}
+
+/// Parse CSV rows of `date,base,currency,rate`, with an optional header row
+pub fn parse_csv(input: &str) -> Result<RateTable, String> {
+    let mut table: Option<RateTable> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.to_lowercase().starts_with("date,")) {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [date, base, code, rate] = fields[..] else {
+            return Err(format!(
+                "Invalid rates file: line {} should be 'date,base,currency,rate'",
+                i + 1
+            ));
+        };
+        let rate: f64 = rate.parse().map_err(|_| {
+            format!(
+                "Invalid rates file: invalid rate '{}' on line {}",
+                rate,
+                i + 1
+            )
+        })?;
+
+        let table = table.get_or_insert_with(|| RateTable {
+            base: base.to_string(),
+            date: date.to_string(),
+            rates: BTreeMap::new(),
+        });
+        if !table.base.eq_ignore_ascii_case(base) || table.date != date {
+            return Err(format!(
+                "Invalid rates file: line {} has base {} on {}, but the table is base {} on {}",
+                i + 1,
+                base,
+                date,
+                table.base,
+                table.date
+            ));
+        }
+        table.rates.insert(code.to_string(), rate);
+    }
+
+    validate(table.ok_or_else(|| "Invalid rates file: no rates found".to_string())?)
+}
+
+/// Normalize codes to uppercase and check codes, rates and the date
+fn validate(table: RateTable) -> Result<RateTable, String> {
+    let code = |code: &str| {
+        let upper = code.to_uppercase();
+        if upper.len() == 3 && upper.chars().all(|c| c.is_ascii_uppercase()) {
+            Ok(upper)
+        } else {
+            Err(format!(
+                "Invalid rates file: '{}' is not a three-letter currency code",
+                code
+            ))
+        }
+    };
+
+    let base = code(&table.base)?;
+    NaiveDate::parse_from_str(&table.date, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Invalid rates file: date '{}' should be YYYY-MM-DD",
+            table.date
+        )
+    })?;
+
+    let mut rates = BTreeMap::new();
+    for (name, rate) in &table.rates {
+        if !rate.is_finite() || *rate <= 0.0 {
+            return Err(format!(
+                "Invalid rates file: rate of '{}' must be a positive number",
+                name
+            ));
+        }
+        rates.insert(code(name)?, *rate);
+    }
+    if rates.get(&base).is_some_and(|rate| *rate != 1.0) {
+        return Err(format!(
+            "Invalid rates file: rate of base currency {} must be 1",
+            base
+        ));
+    }
+    rates.insert(base.clone(), 1.0);
+
+    Ok(RateTable {
+        base,
+        date: table.date,
+        rates,
+    })
+}
+
+/// Use a rate table for the Currency category, replacing any table set before
+pub fn set_rates(table: RateTable) -> Result<(), String> {
+    let table = validate(table)?;
+
+    for code in table.rates.keys() {
+        if let Some(existing) = find_unit(code).filter(|u| {
+            u.category != UnitCategory::Currency
+                && (u.canonical_name == code || u.aliases.contains(&code.as_str()))
+        }) {
+            return Err(format!(
+                "Currency code '{}' collides with unit '{}'",
+                code, existing.canonical_name
+            ));
+        }
+    }
+
+    // Units are leaked like custom units; a table is usually set once per process
+    let units: Vec<&'static Unit> = table
+        .rates
+        .iter()
+        .map(|(code, rate)| {
+            let unit: &'static Unit = Box::leak(Box::new(Unit {
+                canonical_name: Box::leak(code.clone().into_boxed_str()),
+                aliases: &[],
+                category: UnitCategory::Currency,
+                to_base_multiplier: Some(1.0 / rate),
+            }));
+            unit
+        })
+        .collect();
+
+    replace_units(UnitCategory::Currency, &units);
+    *RATE_TABLE.write().unwrap_or_else(|e| e.into_inner()) = Some(table);
+    Ok(())
+}
+
+/// The rate table in use, if one has been set
+pub fn rates() -> Option<RateTable> {
+    RATE_TABLE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Explain why a currency code is not a unit: no table is set, or it lacks the code
+///
+/// Returns `None` for names that are not ISO 4217 codes.
+pub fn missing_currency_error(input: &str) -> Option<String> {
+    let code = input.trim().to_uppercase();
+    if code.len() != 3 || !ISO_4217.split_whitespace().any(|c| c == code) {
+        return None;
+    }
+
+    Some(match rates() {
+        None => format!(
+            "No currency rates configured for '{}'. Provide a rates file (JSON or CSV) with --rates-file or ~/.config/strapd/rates.json",
+            input.trim()
+        ),
+        Some(table) => format!(
+            "No rate for {} in the rates table (base {}, {})",
+            code, table.base, table.date
+        ),
+    })
+}
@@ -0,0 +1,271 @@
+//! User-defined units and categories, loaded from a units file or registered directly.
+//!
+//! A units file lists units under `units`, in TOML or JSON:
+//!
+//! ```toml
+//! [[units]]
+//! name = "furlong"
+//! aliases = ["furlongs"]
+//! category = "length"
+//! multiplier = 201.168
+//!
+//! [[units]]
+//! name = "rankine"
+//! aliases = ["degR"]
+//! category = "temperature"
+//! multiplier = 0.5555555555555556
+//!
+//! [[units]]
+//! name = "storypoint"
+//! aliases = ["storypoints", "sp"]
+//! category = "effort"
+//! multiplier = 1
+//! ```
+//!
+//! A value converts to the base unit of its category as `value * multiplier + offset`
+//! (`offset` defaults to 0). Base units are the ones with a multiplier of 1: byte,
+//! second, meter, kilogram, and so on; temperature units are based on kelvin. A category
+//! that is not built in is created, and its base unit is whichever has a multiplier of 1.
+//!
+//! Units with an offset, and all temperature units, are formula-based: they convert,
+//! but cannot be used in arithmetic or compound targets.
+//!
+//! Names may not collide with built-in units or with units registered earlier.
+//! Registering the exact same definition again is a no-op, so a file can be loaded twice.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use json::JsonValue;
+
+use super::types::{Unit, UnitCategory, find_unit, register_units};
+
+/// Names that the conversion parser treats as keywords or duration formats
+const RESERVED_NAMES: [&str; 6] = ["to", "in", "human", "iso", "iso8601", "auto"];
+
+/// A unit definition from a units file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomUnit {
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// Built-in category name (`length`, `data_rate`, ...) or a new category
+    pub category: String,
+    /// Base units per one of this unit
+    pub multiplier: f64,
+    /// Added after multiplying, for units like temperature scales
+    pub offset: f64,
+}
+
+struct Registered {
+    unit: &'static Unit,
+    definition: CustomUnit,
+}
+
+static CUSTOM_UNITS: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+/// Parse the units of a TOML units file
+pub fn parse_toml(input: &str) -> Result<Vec<CustomUnit>, String> {
+    let table: toml::Table = input
+        .parse()
+        .map_err(|e| format!("Invalid units file: {}", e))?;
+    parse_units(&toml_to_json(&toml::Value::Table(table)))
+}
+
+/// Parse the units of a JSON units file
+pub fn parse_json(input: &str) -> Result<Vec<CustomUnit>, String> {
+    let value = json::parse(input).map_err(|e| format!("Invalid units file: {}", e))?;
+    parse_units(&value)
+}
+
+fn toml_to_json(value: &toml::Value) -> JsonValue {
+    match value {
+        toml::Value::String(s) => s.as_str().into(),
+        toml::Value::Integer(i) => (*i).into(),
+        toml::Value::Float(f) => (*f).into(),
+        toml::Value::Boolean(b) => (*b).into(),
+        toml::Value::Datetime(d) => d.to_string().into(),
+        toml::Value::Array(items) => JsonValue::Array(items.iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => {
+            let mut object = JsonValue::new_object();
+            for (key, item) in table {
+                object[key.as_str()] = toml_to_json(item);
+            }
+            object
+        }
+    }
+}
+
+fn parse_units(value: &JsonValue) -> Result<Vec<CustomUnit>, String> {
+    if !value["units"].is_array() {
+        return Err("Invalid units file: expected a 'units' list".to_string());
+    }
+
+    value["units"]
+        .members()
+        .enumerate()
+        .map(|(i, entry)| {
+            parse_unit(entry).map_err(|e| format!("Invalid unit #{} in units file: {}", i + 1, e))
+        })
+        .collect()
+}
+
+fn parse_unit(entry: &JsonValue) -> Result<CustomUnit, String> {
+    let string = |key: &str| {
+        entry[key]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| format!("'{}' must be a string", key))
+    };
+    let number = |key: &str, default: Option<f64>| match &entry[key] {
+        JsonValue::Null => default.ok_or_else(|| format!("'{}' is required", key)),
+        value => value
+            .as_f64()
+            .ok_or_else(|| format!("'{}' must be a number", key)),
+    };
+
+    let aliases = match &entry["aliases"] {
+        JsonValue::Null => Vec::new(),
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|alias| {
+                alias
+                    .as_str()
+                    .map(|s| s.trim().to_string())
+                    .ok_or_else(|| "'aliases' must be a list of strings".to_string())
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err("'aliases' must be a list of strings".to_string()),
+    };
+
+    Ok(CustomUnit {
+        name: string("name")?,
+        aliases,
+        category: string("category")?,
+        multiplier: number("multiplier", None)?,
+        offset: number("offset", Some(0.0))?,
+    })
+}
+
+/// Validate and register units, returning how many were added
+///
+/// Either every unit is registered or none is.
+pub fn register(units: &[CustomUnit]) -> Result<usize, String> {
+    let mut registered = CUSTOM_UNITS.write().unwrap_or_else(|e| e.into_inner());
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    let mut new_categories: HashMap<String, UnitCategory> = HashMap::new();
+    let mut added = Vec::new();
+
+    for definition in units {
+        validate(definition)?;
+
+        // The same definition loaded again is already in place
+        if registered.iter().any(|r| r.definition == *definition) {
+            continue;
+        }
+
+        for name in names(definition) {
+            if let Some(previous) = seen.insert(name.to_lowercase(), &definition.name) {
+                return Err(format!(
+                    "Unit name '{}' is used by both '{}' and '{}' in the units file",
+                    name, previous, definition.name
+                ));
+            }
+            if let Some(existing) = find_unit(name) {
+                let kind = if registered.iter().any(|r| std::ptr::eq(r.unit, existing)) {
+                    "custom"
+                } else {
+                    "built-in"
+                };
+                return Err(format!(
+                    "Unit name '{}' of '{}' collides with {} unit '{}'",
+                    name, definition.name, kind, existing.canonical_name
+                ));
+            }
+        }
+
+        let category = match UnitCategory::from_name(&definition.category) {
+            Some(category) => category,
+            None => *new_categories
+                .entry(definition.category.to_lowercase())
+                .or_insert_with(|| UnitCategory::Custom(leak(definition.category.clone()))),
+        };
+        added.push((definition, category));
+    }
+
+    let units: Vec<&'static Unit> = added
+        .iter()
+        .map(|(definition, category)| {
+            let aliases: Vec<&'static str> = definition.aliases.iter().cloned().map(leak).collect();
+            let formula_based = definition.offset != 0.0 || *category == UnitCategory::Temperature;
+            let unit: &'static Unit = Box::leak(Box::new(Unit {
+                canonical_name: leak(definition.name.clone()),
+                aliases: Box::leak(aliases.into_boxed_slice()),
+                category: *category,
+                to_base_multiplier: (!formula_based).then_some(definition.multiplier),
+            }));
+            registered.push(Registered {
+                unit,
+                definition: (*definition).clone(),
+            });
+            unit
+        })
+        .collect();
+
+    register_units(&units);
+    Ok(units.len())
+}
+
+fn validate(definition: &CustomUnit) -> Result<(), String> {
+    for name in names(definition) {
+        let starts_like_number =
+            name.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-');
+        if name.is_empty()
+            || starts_like_number
+            || name.contains(|c: char| c.is_whitespace() || "+*^(),=".contains(c))
+        {
+            return Err(format!("Invalid unit name '{}'", name));
+        }
+        if RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+            return Err(format!("Unit name '{}' is reserved", name));
+        }
+    }
+    if definition.category.is_empty() {
+        return Err(format!("Unit '{}' needs a category", definition.name));
+    }
+    if !definition.multiplier.is_finite() || definition.multiplier <= 0.0 {
+        return Err(format!(
+            "Multiplier of unit '{}' must be a positive number",
+            definition.name
+        ));
+    }
+    if !definition.offset.is_finite() {
+        return Err(format!(
+            "Offset of unit '{}' must be a number",
+            definition.name
+        ));
+    }
+    Ok(())
+}
+
+fn names(definition: &CustomUnit) -> impl Iterator<Item = &str> {
+    std::iter::once(definition.name.as_str()).chain(definition.aliases.iter().map(String::as_str))
+}
+
+// Registered units live for the rest of the program, like the built-in ones
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+/// Multiplier and offset of a formula-based custom unit
+///
+/// Returns `None` for built-in units and for custom units that convert linearly.
+pub fn formula(unit: &Unit) -> Option<(f64, f64)> {
+    if unit.to_base_multiplier.is_some() {
+        return None;
+    }
+    let registered = CUSTOM_UNITS.read().unwrap_or_else(|e| e.into_inner());
+    registered
+        .iter()
+        .find(|r| std::ptr::eq(r.unit, unit))
+        .map(|r| (r.definition.multiplier, r.definition.offset))
+}
@@ -0,0 +1,320 @@
+//! Compound durations: `1h30m15s`, `250ms`, `1h 30m`, and ISO 8601 `P1DT2H`, `PT1H30M`.
+//!
+//! Parsing returns seconds. Formatting renders seconds back as a compound duration
+//! (`1h 30m 15s`) or as ISO 8601 (`PT1H30M15S`).
+//!
+//! In compound durations `m` means minutes (as in Go and Kubernetes), while a plain
+//! `5m` conversion still means meters. Years and months have no fixed length, so
+//! they are only accepted by [`parse_calendar_duration`], which keeps years, months,
+//! weeks and days as calendar parts for date arithmetic.
+
+use super::types::{UnitCategory, find_unit};
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+const NO_FIXED_LENGTH: &str = "years and months have no fixed length; use weeks or days";
+
+/// How a duration in seconds is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationFormat {
+    /// Compound duration, e.g. `1h 30m 15s`
+    Human,
+    /// ISO 8601 duration, e.g. `PT1H30M15S`
+    Iso8601,
+}
+
+impl DurationFormat {
+    /// Look up a format by the target name used in conversions (`human`, `iso`, `iso8601`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "human" => Some(DurationFormat::Human),
+            "iso" | "iso8601" => Some(DurationFormat::Iso8601),
+            _ => None,
+        }
+    }
+
+    pub fn format(self, seconds: f64) -> String {
+        match self {
+            DurationFormat::Human => format_human(seconds),
+            DurationFormat::Iso8601 => format_iso8601(seconds),
+        }
+    }
+}
+
+/// One part of a duration parsed with [`parse_calendar_duration`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DurationPart {
+    /// Calendar months, twelve to a year
+    Months(f64),
+    /// Calendar days, seven to a week
+    Days(f64),
+    /// Exact seconds
+    Seconds(f64),
+}
+
+/// Parse a compound (`1h30m15s`, `1h 30m`, `250ms`) or ISO 8601 (`P1DT2H`) duration into seconds
+pub fn parse_duration(input: &str) -> Result<f64, String> {
+    let parts = parse_parts(input, false)?;
+    Ok(parts
+        .iter()
+        .map(|part| match part {
+            DurationPart::Seconds(seconds) => *seconds,
+            // Only calendar durations have calendar parts
+            DurationPart::Months(_) | DurationPart::Days(_) => 0.0,
+        })
+        .sum())
+}
+
+/// Parse a duration whose years, months, weeks and days follow the calendar, like
+/// `1y 2mo 3d 4h`, `2 weeks, 3 days` or `P1M2DT3H`
+///
+/// Calendar parts must be whole numbers. A leading `-` negates every part.
+pub fn parse_calendar_duration(input: &str) -> Result<Vec<DurationPart>, String> {
+    parse_parts(input, true)
+}
+
+fn parse_parts(input: &str, calendar: bool) -> Result<Vec<DurationPart>, String> {
+    let trimmed = input.trim();
+    let (negative, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (
+            false,
+            trimmed.strip_prefix('+').unwrap_or(trimmed).trim_start(),
+        ),
+    };
+
+    let parts = if body.starts_with(['P', 'p']) {
+        parse_iso8601(body, calendar)
+    } else {
+        parse_compound(body, calendar)
+    }
+    .map_err(|e| format!("Invalid duration '{}': {}", trimmed, e))?;
+
+    Ok(match negative {
+        true => parts.into_iter().map(negate).collect(),
+        false => parts,
+    })
+}
+
+fn negate(part: DurationPart) -> DurationPart {
+    match part {
+        DurationPart::Months(months) => DurationPart::Months(-months),
+        DurationPart::Days(days) => DurationPart::Days(-days),
+        DurationPart::Seconds(seconds) => DurationPart::Seconds(-seconds),
+    }
+}
+
+/// Parse `1h30m`, `1 hour 30 minutes` or `2 weeks, 3 days and 4 hours`
+fn parse_compound(input: &str, calendar: bool) -> Result<Vec<DurationPart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = input;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if let Some(after_and) = rest.strip_prefix("and ") {
+            rest = after_and.trim_start();
+        }
+        if rest.is_empty() {
+            break;
+        }
+
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, after) = rest.split_at(number_len);
+        if number.is_empty() {
+            return Err(format!("expected a number at '{}'", rest));
+        }
+        let value: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid number '{}'", number))?;
+
+        let after = after.trim_start();
+        let unit_len = after
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        if unit.is_empty() {
+            return Err(format!("missing unit after '{}'", number));
+        }
+
+        parts.push(unit_part(unit, value, calendar)?);
+        rest = after;
+    }
+
+    if parts.is_empty() {
+        return Err("empty duration".to_string());
+    }
+    Ok(parts)
+}
+
+/// A number of a compound duration unit, with `m` meaning minutes and `w` weeks
+fn unit_part(unit: &str, value: f64, calendar: bool) -> Result<DurationPart, String> {
+    let months = match unit.to_lowercase().as_str() {
+        "y" | "yr" | "yrs" | "year" | "years" => Some(12.0),
+        "mo" | "mon" | "month" | "months" => Some(1.0),
+        _ => None,
+    };
+    if let Some(months) = months {
+        return calendar_part(DurationPart::Months(value * months), value, calendar);
+    }
+
+    let name = match unit {
+        "m" => "min",
+        "w" => "week",
+        _ => unit,
+    };
+    let time_unit = |name: &str| find_unit(name).filter(|u| u.category == UnitCategory::Time);
+    let found = time_unit(name)
+        .or_else(|| time_unit(&name.to_lowercase()))
+        .ok_or_else(|| format!("unknown time unit '{}'", unit))?;
+    match (calendar, found.canonical_name) {
+        (true, "day") => calendar_part(DurationPart::Days(value), value, calendar),
+        (true, "week") => calendar_part(DurationPart::Days(value * 7.0), value, calendar),
+        _ => {
+            let multiplier = found
+                .to_base_multiplier
+                .ok_or_else(|| format!("unknown time unit '{}'", unit))?;
+            Ok(DurationPart::Seconds(value * multiplier))
+        }
+    }
+}
+
+/// Check a year, month, week or day part: only calendar durations take them, whole
+fn calendar_part(part: DurationPart, value: f64, calendar: bool) -> Result<DurationPart, String> {
+    match part {
+        DurationPart::Months(_) if !calendar => Err(NO_FIXED_LENGTH.to_string()),
+        _ if value.fract() != 0.0 => {
+            Err("years, months, weeks and days must be whole numbers".to_string())
+        }
+        _ => Ok(part),
+    }
+}
+
+fn parse_iso8601(input: &str, calendar: bool) -> Result<Vec<DurationPart>, String> {
+    let upper = input.to_uppercase();
+    let body = &upper[1..];
+    let (date, time) = match body.split_once('T') {
+        Some((date, time)) => {
+            if time.is_empty() {
+                return Err("'T' must be followed by hours, minutes or seconds".to_string());
+            }
+            (date, Some(time))
+        }
+        None => (body, None),
+    };
+
+    let mut parts = Vec::new();
+    for (part, in_time, designators) in [(date, false, "YMWD"), (time.unwrap_or(""), true, "HMS")] {
+        let mut number = String::new();
+        for c in part.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                number.push(if c == ',' { '.' } else { c });
+                continue;
+            }
+            if !designators.contains(c) {
+                return Err(format!("unexpected '{}'", c));
+            }
+            if number.is_empty() {
+                return Err(format!("missing number before '{}'", c));
+            }
+            let value: f64 = number
+                .parse()
+                .map_err(|_| format!("invalid number '{}'", number))?;
+            number.clear();
+
+            // `M` is months before `T` and minutes after it
+            let part = match c {
+                'Y' if !in_time => DurationPart::Months(value * 12.0),
+                'M' if !in_time => DurationPart::Months(value),
+                'W' if calendar => DurationPart::Days(value * 7.0),
+                'D' if calendar => DurationPart::Days(value),
+                'W' => DurationPart::Seconds(value * 604_800.0),
+                'D' => DurationPart::Seconds(value * 86_400.0),
+                'H' => DurationPart::Seconds(value * 3_600.0),
+                'M' => DurationPart::Seconds(value * 60.0),
+                _ => DurationPart::Seconds(value),
+            };
+            parts.push(match part {
+                DurationPart::Seconds(_) => part,
+                _ => calendar_part(part, value, calendar)?,
+            });
+        }
+        if !number.is_empty() {
+            return Err(format!("missing designator after '{}'", number));
+        }
+    }
+
+    if parts.is_empty() {
+        return Err("no duration components".to_string());
+    }
+    Ok(parts)
+}
+
+/// Render seconds as a compound duration like `1d 2h 30m 15s` or `1s 250ms`
+pub fn format_human(seconds: f64) -> String {
+    const PARTS: [(&str, u128); 7] = [
+        ("d", 86_400 * NANOS_PER_SECOND),
+        ("h", 3_600 * NANOS_PER_SECOND),
+        ("m", 60 * NANOS_PER_SECOND),
+        ("s", NANOS_PER_SECOND),
+        ("ms", 1_000_000),
+        ("us", 1_000),
+        ("ns", 1),
+    ];
+
+    let mut remaining = (seconds.abs() * NANOS_PER_SECOND as f64).round() as u128;
+    if remaining == 0 {
+        return "0s".to_string();
+    }
+
+    let mut parts = Vec::new();
+    for (suffix, size) in PARTS {
+        let count = remaining / size;
+        remaining %= size;
+        if count > 0 {
+            parts.push(format!("{}{}", count, suffix));
+        }
+    }
+
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// Render seconds as an ISO 8601 duration like `P1DT2H` or `PT1H30M15.5S`
+pub fn format_iso8601(seconds: f64) -> String {
+    let nanos = (seconds.abs() * NANOS_PER_SECOND as f64).round() as u128;
+    let days = nanos / (86_400 * NANOS_PER_SECOND);
+    let hours = nanos / (3_600 * NANOS_PER_SECOND) % 24;
+    let minutes = nanos / (60 * NANOS_PER_SECOND) % 60;
+    let second_nanos = nanos % (60 * NANOS_PER_SECOND);
+
+    let mut output = String::from(if seconds < 0.0 { "-P" } else { "P" });
+    if days > 0 {
+        output.push_str(&format!("{}D", days));
+    }
+
+    let mut time = String::new();
+    if hours > 0 {
+        time.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        time.push_str(&format!("{}M", minutes));
+    }
+    if second_nanos > 0 || nanos == 0 {
+        let whole = second_nanos / NANOS_PER_SECOND;
+        let fraction = second_nanos % NANOS_PER_SECOND;
+        if fraction == 0 {
+            time.push_str(&format!("{}S", whole));
+        } else {
+            let fraction = format!("{:09}", fraction);
+            time.push_str(&format!("{}.{}S", whole, fraction.trim_end_matches('0')));
+        }
+    }
+
+    if !time.is_empty() {
+        output.push('T');
+        output.push_str(&time);
+    }
+    output
+}
@@ -1,43 +1,117 @@
+use super::duration::DurationFormat;
+use super::suggestions::{category_units_hint, unknown_unit_error};
 use super::types::{
     ConversionRequest, ConversionResult, UnitCategory, find_unit, get_units_in_category,
 };
+use super::typography::{self, TypographySettings};
+use super::{compound, custom, expression, humanize, parser};
 use crate::calculator;
 
 pub fn convert(request: &ConversionRequest) -> Result<ConversionResult, String> {
+    convert_with(request, &TypographySettings::default())
+}
+
+/// Convert with explicit settings for typography units (root font size, viewport, DPI)
+pub fn convert_with(
+    request: &ConversionRequest,
+    settings: &TypographySettings,
+) -> Result<ConversionResult, String> {
     let to_unit = request
         .to_unit
         .as_ref()
         .ok_or_else(|| "Target unit required. Use --all to see all conversions.".to_string())?;
 
     // Look up units in registry
-    let from = find_unit(&request.from_unit).ok_or_else(|| {
-        format!(
-            "Unknown source unit: '{}'. Please check unit spelling.",
-            request.from_unit
-        )
-    })?;
+    let from = find_unit(&request.from_unit)
+        .ok_or_else(|| unknown_unit_error("Unknown source unit", &request.from_unit))?;
 
-    let to = find_unit(to_unit).ok_or_else(|| {
-        format!(
-            "Unknown target unit: '{}'. Please check unit spelling.",
-            to_unit
-        )
-    })?;
+    // `to auto` picks the target unit from the value, keeping the source's spelling
+    // when the value stays in its unit ("1 B" rather than "1 byte")
+    if humanize::is_auto(to_unit) {
+        let to_unit = humanize::best_unit(request.value, from)
+            .filter(|u| u.canonical_name != from.canonical_name)
+            .map_or_else(
+                || request.from_unit.clone(),
+                |u| u.canonical_name.to_string(),
+            );
+        return convert_with(
+            &ConversionRequest {
+                to_unit: Some(to_unit),
+                ..request.clone()
+            },
+            settings,
+        );
+    }
 
-    // Verify same category
-    if from.category != to.category {
+    // `to human` and `to iso` keep the value in seconds; the formatter renders it
+    if DurationFormat::from_name(to_unit).is_some() {
+        if from.category != UnitCategory::Time {
+            return Err(format!(
+                "Cannot show '{}' ({}) as a duration. Only time units can be converted to '{}'.",
+                request.from_unit, from.category, to_unit
+            ));
+        }
+        let seconds = find_unit("s").ok_or_else(|| "Unknown unit: 's'".to_string())?;
+        return Ok(ConversionResult {
+            input_value: request.value,
+            input_unit: request.from_unit.clone(),
+            output_value: convert_linear(request.value, from, seconds)?,
+            output_unit: to_unit.clone(),
+        });
+    }
+
+    // Compound targets (`ft+in`) carry the value in their first unit
+    if compound::is_compound(to_unit) {
+        let parts = compound::parse_target(to_unit)?;
+        let (first, first_name) = &parts[0];
+        if from.category != first.category {
+            return Err(format!(
+                "Cannot convert '{}' ({}) to '{}' ({}). Units must be in the same category. {}",
+                request.from_unit,
+                from.category,
+                first_name,
+                first.category,
+                category_units_hint(from.category)
+            ));
+        }
+        return Ok(ConversionResult {
+            input_value: request.value,
+            input_unit: request.from_unit.clone(),
+            output_value: convert_linear(request.value, from, first)?,
+            output_unit: to_unit.clone(),
+        });
+    }
+
+    let to =
+        find_unit(to_unit).ok_or_else(|| unknown_unit_error("Unknown target unit", to_unit))?;
+
+    // Verify same category; length and typography units meet through the DPI
+    if from.category != to.category && !typography::converts_through_px(from.category, to.category)
+    {
         return Err(format!(
-            "Cannot convert '{}' ({:?}) to '{}' ({:?}). Units must be in the same category.",
-            request.from_unit, from.category, to_unit, to.category
+            "Cannot convert '{}' ({}) to '{}' ({}). Units must be in the same category. {}",
+            request.from_unit,
+            from.category,
+            to_unit,
+            to.category,
+            category_units_hint(from.category)
         ));
     }
 
     // Perform conversion based on category
-    let output_value = match from.category {
-        UnitCategory::Temperature => {
-            convert_temperature(request.value, &request.from_unit, to_unit)?
+    let output_value = if typography::converts_through_px(from.category, to.category) {
+        let from_px = typography::px_per_unit(from, settings)?;
+        let to_px = typography::px_per_unit(to, settings)?;
+        evaluate(&format!("({} * {}) / {}", request.value, from_px, to_px))?
+    } else if custom::formula(from).is_some() || custom::formula(to).is_some() {
+        convert_formula(request.value, from, to)?
+    } else {
+        match from.category {
+            UnitCategory::Temperature => {
+                convert_temperature(request.value, &request.from_unit, to_unit)?
+            }
+            _ => convert_linear(request.value, from, to)?,
         }
-        _ => convert_linear(request.value, from, to)?,
     };
 
     Ok(ConversionResult {
@@ -48,15 +122,41 @@ pub fn convert(request: &ConversionRequest) -> Result<ConversionResult, String>
     })
 }
 
+/// Convert free-form input such as "5 ft 11 in to cm", "180 cm to ft+in" or "2 GiB / 30 s to MiB/s"
+///
+/// Typography settings can be given in the input, as in "24px to rem @base=18".
+pub fn convert_input(input: &str) -> Result<ConversionResult, String> {
+    convert_input_with(input, &TypographySettings::default())
+}
+
+/// Like `convert_input`, with settings that `@key=value` tokens in the input override
+pub fn convert_input_with(
+    input: &str,
+    settings: &TypographySettings,
+) -> Result<ConversionResult, String> {
+    let (input, settings) = typography::extract_settings(input, *settings)?;
+    if expression::is_expression(&input) {
+        return expression::evaluate(&input);
+    }
+    convert_with(&parser::parse_input(&input)?, &settings)
+}
+
 pub fn convert_to_all(value: f64, from_unit: &str) -> Result<Vec<ConversionResult>, String> {
-    let from = find_unit(from_unit).ok_or_else(|| {
-        format!(
-            "Unknown source unit: '{}'. Please check unit spelling.",
-            from_unit
-        )
-    })?;
+    convert_to_all_with(value, from_unit, &TypographySettings::default())
+}
+
+/// Convert to every other unit of the category, with explicit typography settings
+pub fn convert_to_all_with(
+    value: f64,
+    from_unit: &str,
+    settings: &TypographySettings,
+) -> Result<Vec<ConversionResult>, String> {
+    let from =
+        find_unit(from_unit).ok_or_else(|| unknown_unit_error("Unknown source unit", from_unit))?;
 
-    let units_in_category = get_units_in_category(from.category);
+    // Group SI and IEC units, keeping each group ordered by size
+    let mut units_in_category = get_units_in_category(from.category);
+    units_in_category.sort_by_key(|u| u.system());
     let mut results = Vec::new();
 
     for to_unit in units_in_category {
@@ -71,7 +171,7 @@ pub fn convert_to_all(value: f64, from_unit: &str) -> Result<Vec<ConversionResul
             to_unit: Some(to_unit.canonical_name.to_string()),
         };
 
-        match convert(&request) {
+        match convert_with(&request, settings) {
             Ok(result) => results.push(result),
             Err(_) => continue, // Skip units that fail conversion
         }
@@ -115,6 +215,56 @@ fn convert_linear(
     Ok(result)
 }
 
+// Formula conversion through the category's base unit, for custom units with an offset
+// or a temperature scale. Temperatures are based on kelvin.
+fn convert_formula(
+    value: f64,
+    from: &super::types::Unit,
+    to: &super::types::Unit,
+) -> Result<f64, String> {
+    let base = match custom::formula(from) {
+        Some((multiplier, offset)) => {
+            evaluate(&format!("{} * {} + ({})", value, multiplier, offset))?
+        }
+        None if from.category == UnitCategory::Temperature => {
+            convert_temperature(value, from.canonical_name, "k")?
+        }
+        None => evaluate(&format!("{} * {}", value, linear_multiplier(from)?))?,
+    };
+
+    let result = match custom::formula(to) {
+        Some((multiplier, offset)) => {
+            evaluate(&format!("({} - ({})) / {}", base, offset, multiplier))?
+        }
+        None if to.category == UnitCategory::Temperature => {
+            convert_temperature(base, "k", to.canonical_name)?
+        }
+        None => evaluate(&format!("{} / {}", base, linear_multiplier(to)?))?,
+    };
+
+    // Offsets that cancel out leave float noise (491.67 degR is 5.7e-14 C, not 0)
+    if result.abs() < base.abs().max(value.abs()) * 1e-12 {
+        return Ok(0.0);
+    }
+    Ok(result)
+}
+
+fn linear_multiplier(unit: &super::types::Unit) -> Result<f64, String> {
+    unit.to_base_multiplier.ok_or_else(|| {
+        format!(
+            "Unit '{}' cannot be converted linearly",
+            unit.canonical_name
+        )
+    })
+}
+
+fn evaluate(expr: &str) -> Result<f64, String> {
+    let result_str = calculator::evaluate(expr).map_err(|e| format!("Calculator error: {}", e))?;
+    result_str
+        .parse()
+        .map_err(|_| format!("Failed to parse result: {}", result_str))
+}
+
 fn convert_temperature(value: f64, from: &str, to: &str) -> Result<f64, String> {
     let from_normalized = from.trim().to_lowercase();
     let to_normalized = to.trim().to_lowercase();
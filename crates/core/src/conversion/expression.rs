@@ -0,0 +1,636 @@
+//! Unit-aware arithmetic over the unit registry.
+//!
+//! Numbers may carry units (`2 GiB / 30 s`, `1 h + 45 min to s`):
+//! - `+` and `-` require both sides to have the same dimension
+//! - `*` and `/` combine dimensions into derived units (`GiB/s`, `byte*s`)
+//! - `^` raises a quantity to an integer power
+//! - An optional `to <unit>` suffix converts the result, e.g. `to MB/s` or `to h`;
+//!   durations can also be shown `to human` (`1h 30m`) or `to iso` (`PT1H30M`), and
+//!   results split across units with a compound target like `to h+min`, or scaled
+//!   to the most readable unit with `to auto`
+//!
+//! Without a target, the result is shown in the first unit used for each category.
+//! Derived units (`mph`, `Mbps`, `Hz`, `m2`) are expanded into their base dimensions,
+//! so `10 MiB/s * 1 h to GiB` and `100 km / 2 h to mph` work.
+//! Temperature units are formula-based and cannot take part in arithmetic.
+//!
+//! Expressions run through the calculator's tokenizer and evaluator with `Quantity`
+//! as the operand, so literals, precedence, functions, variables and `ans` behave as
+//! in `calc`, and a unit result becomes the context's `ans`.
+
+use super::duration::{self, DurationFormat};
+use super::suggestions::unknown_unit_error;
+use super::types::{ConversionResult, Unit, UnitCategory, find_unit, get_units_in_category};
+use super::{compound, humanize};
+use crate::calculator::{CalcError, CalcErrorKind, Context, Function, Number, Operand, Value};
+
+/// One category of a quantity's dimension, with the unit used to display it
+#[derive(Debug, Clone)]
+struct Factor {
+    category: UnitCategory,
+    name: String,
+    multiplier: f64,
+    exponent: i32,
+}
+
+/// A value in its display unit together with its dimension
+#[derive(Debug, Clone)]
+pub(crate) struct Quantity {
+    /// Value in the display unit, so arithmetic within one unit stays exact
+    value: Number,
+    factors: Vec<Factor>,
+    /// Derived unit the quantity is displayed in (e.g. `mph`) and its multiplier,
+    /// until arithmetic changes its dimension
+    derived: Option<(String, f64)>,
+}
+
+impl From<Number> for Quantity {
+    fn from(value: Number) -> Self {
+        Self {
+            value,
+            factors: Vec::new(),
+            derived: None,
+        }
+    }
+}
+
+impl Quantity {
+    fn from_unit(unit: &'static Unit, name: String) -> Result<Self, String> {
+        let multiplier = unit.to_base_multiplier.ok_or_else(|| {
+            format!(
+                "Unit '{}' cannot be used in arithmetic. Use a plain conversion like '100 {} to f'",
+                name, name
+            )
+        })?;
+
+        let Some(dimensions) = unit.category.base_dimensions() else {
+            return Ok(Self {
+                value: Number::Int(1),
+                factors: vec![Factor {
+                    category: unit.category,
+                    name,
+                    multiplier,
+                    exponent: 1,
+                }],
+                derived: None,
+            });
+        };
+
+        // Derived units are expressed in the base units of their parts
+        let factors = dimensions
+            .iter()
+            .map(|&(category, exponent)| {
+                let base = get_units_in_category(category)
+                    .into_iter()
+                    .find(|u| u.to_base_multiplier == Some(1.0))
+                    .ok_or_else(|| format!("No base unit for {}", category))?;
+                Ok(Factor {
+                    category,
+                    name: base.canonical_name.to_string(),
+                    multiplier: 1.0,
+                    exponent,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            value: Number::Int(1),
+            factors,
+            derived: Some((name, multiplier)),
+        })
+    }
+
+    /// True for a plain number without units
+    fn is_plain(&self) -> bool {
+        self.factors.is_empty()
+    }
+
+    fn exponent_of(&self, category: UnitCategory) -> i32 {
+        self.factors
+            .iter()
+            .find(|f| f.category == category)
+            .map_or(0, |f| f.exponent)
+    }
+
+    fn same_dimension(&self, other: &Quantity) -> bool {
+        self.factors.len() == other.factors.len()
+            && self
+                .factors
+                .iter()
+                .all(|f| other.exponent_of(f.category) == f.exponent)
+    }
+
+    /// Multiply dimensions, raising `other` to `sign` (1 for `*`, -1 for `/`)
+    ///
+    /// Returns the combined quantity with the factor its value has to be scaled by:
+    /// derived units are expanded, and `other`'s units are shown in `self`'s units
+    /// where they share a category.
+    fn combine(mut self, other: Quantity, sign: i32) -> (Quantity, f64) {
+        let derived = |quantity: &Quantity| quantity.derived.as_ref().map_or(1.0, |(_, m)| *m);
+        let mut scale = derived(&self) * derived(&other).powi(sign);
+        for factor in other.factors {
+            match self
+                .factors
+                .iter_mut()
+                .find(|f| f.category == factor.category)
+            {
+                Some(existing) => {
+                    if existing.multiplier != factor.multiplier {
+                        scale *=
+                            (factor.multiplier / existing.multiplier).powi(sign * factor.exponent);
+                    }
+                    existing.exponent += sign * factor.exponent;
+                }
+                None => self.factors.push(Factor {
+                    exponent: sign * factor.exponent,
+                    ..factor
+                }),
+            }
+        }
+        self.factors.retain(|f| f.exponent != 0);
+        self.derived = None;
+        (self, scale)
+    }
+
+    /// Multiply the value by `scale`, unless it is exactly 1
+    fn scaled(mut self, scale: f64, exact: bool) -> Result<Quantity, String> {
+        if scale != 1.0 {
+            self.value = Number::apply_operator('*', self.value, Number::Float(scale), exact)?;
+        }
+        Ok(self)
+    }
+
+    /// Value in `other`'s display unit, which has the same dimension
+    fn value_in(&self, other: &Quantity, exact: bool) -> Result<Number, String> {
+        if self.unit_name() == other.unit_name() {
+            return Ok(self.value.clone());
+        }
+        let scale = self.display_multiplier() / other.display_multiplier();
+        Number::apply_operator('*', self.value.clone(), Number::Float(scale), exact)
+    }
+
+    /// Multiplier from the display unit to base units
+    fn display_multiplier(&self) -> f64 {
+        if let Some((_, multiplier)) = &self.derived {
+            return *multiplier;
+        }
+        self.factors
+            .iter()
+            .map(|f| f.multiplier.powi(f.exponent))
+            .product()
+    }
+
+    /// Value in the display unit
+    fn display_value(&self) -> f64 {
+        self.value.to_float()
+    }
+
+    /// Value in base units
+    fn base_value(&self) -> f64 {
+        self.value.to_float() * self.display_multiplier()
+    }
+
+    /// Dimension as category names, e.g. `Bytes/Time`
+    fn dimension_name(&self) -> String {
+        if self.factors.is_empty() {
+            return "dimensionless".to_string();
+        }
+        join_factors(&self.factors, |f| f.category.to_string())
+    }
+
+    /// Display unit, e.g. `GiB/s`
+    fn unit_name(&self) -> String {
+        if let Some((name, _)) = &self.derived {
+            return name.clone();
+        }
+        join_factors(&self.factors, |f| f.name.clone())
+    }
+
+    /// Value and display unit, e.g. `1.5 h`, as listed for stored variables
+    pub(crate) fn format(&self, significant_digits: usize) -> String {
+        if self.is_plain() {
+            return self.value.format(significant_digits);
+        }
+        format!(
+            "{} {}",
+            Number::Float(self.display_value()).format(significant_digits),
+            self.unit_name()
+        )
+    }
+}
+
+impl Operand for Quantity {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::Number(number) => number.clone().into(),
+            Value::Quantity(quantity) => quantity.clone(),
+        })
+    }
+
+    fn into_value(self) -> Value {
+        if self.is_plain() {
+            Value::Number(self.value)
+        } else {
+            Value::Quantity(self)
+        }
+    }
+
+    fn has_units(&self) -> bool {
+        !self.is_plain()
+    }
+
+    /// Compound durations like `1h30m` become seconds, as in plain conversions
+    fn read_literal(input: &str) -> Option<(Self, usize)> {
+        let (len, seconds) = compound_duration(input)?;
+        let unit = find_unit("s")?;
+        let quantity = Quantity::from_unit(unit, "s".to_string()).ok()?;
+        Some((
+            Quantity {
+                value: Number::Float(seconds),
+                ..quantity
+            },
+            len,
+        ))
+    }
+
+    /// A registered unit name; units spelled with a slash (`Mb/s`, `km/h`) are read
+    /// as one unit when registered, so case-sensitive names keep their meaning
+    fn read_unit(input: &str) -> Option<(Result<Self, String>, usize)> {
+        let len = name_len(input);
+        if len == 0 {
+            return None;
+        }
+        if let Some(per_end) = slash_unit_end(input, len)
+            && let Some(unit) = find_unit(&input[..per_end])
+        {
+            return Some((
+                Quantity::from_unit(unit, input[..per_end].to_string()),
+                per_end,
+            ));
+        }
+        let unit = find_unit(&input[..len])?;
+        Some((Quantity::from_unit(unit, input[..len].to_string()), len))
+    }
+
+    fn apply_operator(op: char, left: Self, right: Self, exact: bool) -> Result<Self, String> {
+        match op {
+            '+' | '-' => {
+                if !left.same_dimension(&right) {
+                    let verb = if op == '+' { "add" } else { "subtract" };
+                    return Err(format!(
+                        "Cannot {} {} ({}) and {} ({}): units must have the same dimension",
+                        verb,
+                        left.unit_name(),
+                        left.dimension_name(),
+                        right.unit_name(),
+                        right.dimension_name()
+                    ));
+                }
+                let right = right.value_in(&left, exact)?;
+                let value = Number::apply_operator(op, left.value.clone(), right, exact)?;
+                Ok(Quantity { value, ..left })
+            }
+            '*' | '/' => {
+                let value =
+                    Number::apply_operator(op, left.value.clone(), right.value.clone(), exact)?;
+                // Scaling by a plain number keeps the unit, so `60 mph` stays in mph
+                let (mut quantity, scale) = match (left.is_plain(), right.is_plain()) {
+                    (_, true) => (left, 1.0),
+                    (true, false) if op == '*' => (right, 1.0),
+                    _ => left.combine(right, if op == '*' { 1 } else { -1 }),
+                };
+                quantity.value = value;
+                quantity.scaled(scale, exact)
+            }
+            '^' => {
+                if !right.is_plain() {
+                    return Err("Exponent must be a plain number".to_string());
+                }
+                raise(left, right.value, exact)
+            }
+            _ => {
+                if let Some(quantity) = [&left, &right].into_iter().find(|q| !q.is_plain()) {
+                    return Err(format!(
+                        "Only +, -, *, / and ^ work with units, not with {}",
+                        quantity.unit_name()
+                    ));
+                }
+                Number::apply_operator(op, left.value, right.value, exact).map(Quantity::from)
+            }
+        }
+    }
+
+    fn apply_unary_operator(op: char, operand: Self) -> Result<Self, String> {
+        if op == '-' {
+            return Ok(Quantity {
+                value: operand.value.clone().negate(),
+                ..operand
+            });
+        }
+        if !operand.is_plain() {
+            return Err(format!(
+                "Bitwise operators need plain numbers, not {}",
+                operand.unit_name()
+            ));
+        }
+        Number::apply_unary_operator(op, operand.value).map(Quantity::from)
+    }
+
+    fn apply_function(function: Function, args: &[Self]) -> Result<Self, String> {
+        if let Some(quantity) = args.iter().find(|q| !q.is_plain()) {
+            return Err(format!(
+                "{}() needs plain numbers, not {}",
+                function.name(),
+                quantity.unit_name()
+            ));
+        }
+        let args: Vec<Number> = args.iter().map(|q| q.value.clone()).collect();
+        Number::apply_function(function, &args).map(Quantity::from)
+    }
+}
+
+/// Join factors as `a*b/c^2`, with `1/x` when there is no numerator
+fn join_factors(factors: &[Factor], label: impl Fn(&Factor) -> String) -> String {
+    let power = |factor: &Factor, exponent: i32| {
+        if exponent == 1 {
+            label(factor)
+        } else {
+            format!("{}^{}", label(factor), exponent)
+        }
+    };
+
+    let numerator: Vec<String> = factors
+        .iter()
+        .filter(|f| f.exponent > 0)
+        .map(|f| power(f, f.exponent))
+        .collect();
+    let denominator: Vec<String> = factors
+        .iter()
+        .filter(|f| f.exponent < 0)
+        .map(|f| power(f, -f.exponent))
+        .collect();
+
+    match (numerator.is_empty(), denominator.is_empty()) {
+        (_, true) => numerator.join("*"),
+        (true, false) => format!("1/{}", denominator.join("/")),
+        (false, false) => format!("{}/{}", numerator.join("*"), denominator.join("/")),
+    }
+}
+
+/// Check whether the input is a unit expression rather than a plain `<value> <unit>` conversion
+///
+/// True when the input contains an arithmetic operator, parentheses or a function call
+/// and at least one unit before any `to` target.
+pub fn is_expression(input: &str) -> bool {
+    let (expr, _) = split_target(input);
+    Context::new()
+        .shape::<Quantity>(expr)
+        .is_some_and(|shape| shape.units && shape.arithmetic)
+}
+
+/// Check whether the input refers to at least one known unit, reading names as the
+/// variables of `context` first
+pub fn contains_units(context: &Context, input: &str) -> bool {
+    let (expr, _) = split_target(input);
+    context
+        .shape::<Quantity>(expr)
+        .is_some_and(|shape| shape.units)
+}
+
+/// Report an unknown name as a misspelled unit, with suggestions, when it follows a
+/// number or comes right before a `to` target, as `kms` does in `5 kms to m`
+///
+/// Returns `None` for other errors, which are left to the calculator.
+pub fn misspelled_unit(input: &str, error: &CalcError) -> Option<CalcError> {
+    let (CalcErrorKind::UnknownVariable(_), Some(span)) = (error.kind(), error.span()) else {
+        return None;
+    };
+    let after_number = input[..span.start]
+        .trim_end()
+        .ends_with(|c: char| c.is_ascii_digit() || c == '.');
+    let after = input[span.end..].trim_start();
+    let before_target = after
+        .get(..2)
+        .is_some_and(|word| word.eq_ignore_ascii_case("to"))
+        && name_len(after) == 2;
+    (after_number || before_target).then(|| unknown_unit(error.clone()))
+}
+
+/// Evaluate a unit-aware expression like `2 GiB / 30 s to MB/s`
+///
+/// The returned `input_*` fields hold the result in its inferred unit and the
+/// `output_*` fields hold it in the target unit (the same when there is no target).
+pub fn evaluate(input: &str) -> Result<ConversionResult, String> {
+    evaluate_in(&mut Context::new(), input).map_err(String::from)
+}
+
+/// Evaluate a unit-aware expression or assignment with the variables and `ans` of
+/// `context`, storing the result (before any `to` conversion) as the new `ans`
+///
+/// Errors carry the byte span of the offending part of `input`.
+pub fn evaluate_in(context: &mut Context, input: &str) -> Result<ConversionResult, CalcError> {
+    let (expr, target) = split_target(input);
+    let quantity: Quantity = context.evaluate_operand(expr).map_err(unknown_unit)?;
+    let input_unit = quantity.unit_name();
+    let input_value = quantity.display_value();
+
+    let Some((offset, target)) = target else {
+        return Ok(ConversionResult {
+            input_value,
+            input_unit: input_unit.clone(),
+            output_value: input_value,
+            output_unit: input_unit,
+        });
+    };
+
+    let name = target.trim();
+    if name.is_empty() {
+        return Err(CalcError::new(
+            CalcErrorKind::Evaluation("Missing target unit after 'to'".to_string()),
+            expr.len()..offset,
+        ));
+    }
+    let start = offset + (target.len() - target.trim_start().len());
+    let span = start..start + name.len();
+    let target_error =
+        |message: String| CalcError::new(CalcErrorKind::Evaluation(message), span.clone());
+
+    if humanize::is_auto(name) {
+        return humanize::humanize(ConversionResult {
+            input_value,
+            input_unit: input_unit.clone(),
+            output_value: input_value,
+            output_unit: input_unit,
+        })
+        .map_err(target_error);
+    }
+    if DurationFormat::from_name(name).is_some() {
+        if quantity.factors.len() != 1 || quantity.exponent_of(UnitCategory::Time) != 1 {
+            return Err(target_error(format!(
+                "Cannot show {} ({}) as a duration",
+                input_unit,
+                quantity.dimension_name()
+            )));
+        }
+        // The formatter renders the value in seconds
+        return Ok(ConversionResult {
+            input_value,
+            input_unit,
+            output_value: quantity.base_value(),
+            output_unit: name.to_string(),
+        });
+    }
+    if is_compound_target(name) {
+        let parts = compound::parse_target(name).map_err(target_error)?;
+        let (unit, unit_name) = &parts[0];
+        let first = Quantity::from_unit(unit, unit_name.clone()).map_err(target_error)?;
+        if !quantity.same_dimension(&first) {
+            return Err(target_error(format!(
+                "Cannot convert {} ({}) to {} ({})",
+                input_unit,
+                quantity.dimension_name(),
+                name,
+                first.dimension_name()
+            )));
+        }
+        // The formatter splits the value, given in the first unit, across the parts
+        return Ok(ConversionResult {
+            input_value,
+            input_unit,
+            output_value: quantity.base_value() / first.base_value(),
+            output_unit: name.to_string(),
+        });
+    }
+
+    // Targets are units, so they are read without the context's variables
+    let target: Quantity = Context::new()
+        .evaluate_from(input, offset)
+        .map_err(unknown_unit)?;
+    if !quantity.same_dimension(&target) {
+        return Err(target_error(format!(
+            "Cannot convert {} ({}) to {} ({})",
+            input_unit,
+            quantity.dimension_name(),
+            target.unit_name(),
+            target.dimension_name()
+        )));
+    }
+    let target_value = target.display_value();
+    if target_value == 0.0 {
+        return Err(target_error("Target unit cannot be zero".to_string()));
+    }
+
+    // Only go through base units when the target is a different unit
+    let value = quantity.value_in(&target, false).map_err(target_error)?;
+    Ok(ConversionResult {
+        input_value,
+        input_unit,
+        output_value: value.to_float() / target_value,
+        output_unit: target.unit_name(),
+    })
+}
+
+/// Report a name the calculator could not resolve as an unknown unit
+fn unknown_unit(error: CalcError) -> CalcError {
+    match (error.kind(), error.span()) {
+        (CalcErrorKind::UnknownVariable(name), Some(span)) => CalcError::new(
+            CalcErrorKind::Evaluation(unknown_unit_error("Unknown unit", name)),
+            span,
+        ),
+        _ => error,
+    }
+}
+
+/// Split the input at the `to` keyword into the expression and the byte offset and
+/// text of the target
+fn split_target(input: &str) -> (&str, Option<(usize, &str)>) {
+    let mut pos = 0;
+    while pos < input.len() {
+        let len = name_len(&input[pos..]);
+        if len == 0 {
+            pos += input[pos..].chars().next().map_or(1, char::len_utf8);
+            continue;
+        }
+        if input[pos..pos + len].eq_ignore_ascii_case("to") {
+            return (&input[..pos], Some((pos + len, &input[pos + len..])));
+        }
+        pos += len;
+    }
+    (input, None)
+}
+
+/// Units joined with `+`, as in `h+min+s`
+fn is_compound_target(target: &str) -> bool {
+    target.contains('+')
+        && target
+            .split('+')
+            .all(|part| find_unit(part.trim()).is_some())
+}
+
+/// Byte length of the name (letters, digits, `_`, `°`, `µ`) at the start of `input`
+fn name_len(input: &str) -> usize {
+    input
+        .chars()
+        .take_while(|&c| c.is_alphanumeric() || matches!(c, '_' | '°' | 'µ'))
+        .map(char::len_utf8)
+        .sum()
+}
+
+/// Byte length and length in seconds of a compound duration like `1h30m` at the
+/// start of `rest`
+///
+/// Needs at least two number-unit pairs without spaces, so `5m` stays meters.
+fn compound_duration(rest: &str) -> Option<(usize, f64)> {
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .unwrap_or(rest.len());
+    let word = &rest[..len];
+    let pairs = word
+        .as_bytes()
+        .windows(2)
+        .filter(|pair| pair[0].is_ascii_alphabetic() && pair[1].is_ascii_digit())
+        .count();
+    if pairs == 0 || word.starts_with("0x") || word.starts_with("0b") || word.starts_with("0o") {
+        return None;
+    }
+    let seconds = duration::parse_duration(word).ok()?;
+    Some((len, seconds))
+}
+
+/// End of the name directly after a `/` at `pos`, if there is one
+fn slash_unit_end(input: &str, pos: usize) -> Option<usize> {
+    let len = name_len(input[pos..].strip_prefix('/')?);
+    (len > 0).then_some(pos + 1 + len)
+}
+
+/// Raise a quantity to a power; quantities with units need an integer exponent
+fn raise(mut base: Quantity, exponent: Number, exact: bool) -> Result<Quantity, String> {
+    if base.is_plain() {
+        return Number::apply_operator('^', base.value, exponent, exact).map(Quantity::from);
+    }
+    let power = exponent.to_float();
+    if power.fract() != 0.0 || power.abs() > i32::MAX as f64 {
+        return Err(format!(
+            "Cannot raise {} to a non-integer power",
+            base.unit_name()
+        ));
+    }
+    let power = power as i32;
+    if power == 0 {
+        return Ok(Number::Int(1).into());
+    }
+    base.value = Number::apply_operator('^', base.value, exponent, exact)?;
+    // A derived unit like `mph` is shown in its base units once raised
+    let mut scale = 1.0;
+    if power != 1
+        && let Some((_, multiplier)) = base.derived.take()
+    {
+        scale = multiplier.powi(power);
+    }
+    for factor in &mut base.factors {
+        factor.exponent *= power;
+    }
+    base.scaled(scale, exact)
+}
@@ -1,48 +1,147 @@
-use super::types::ConversionResult;
+use super::compound;
+use super::duration::DurationFormat;
+use super::types::{ConversionResult, UnitSystem, find_unit};
+use crate::calculator::{Notation, NumberFormat};
 
+/// Format results one per line
+///
+/// When the results mix SI and IEC units (e.g. `--all` for bytes), each system
+/// gets its own block with a heading.
 pub fn format_output(
     results: &[ConversionResult],
     precision: Option<usize>,
+) -> Result<String, String> {
+    format_output_with(
+        results,
+        &NumberFormat {
+            decimals: precision,
+            ..NumberFormat::default()
+        },
+    )
+}
+
+/// Format results one per line with a notation, rounding and digit grouping
+///
+/// Without `decimals`, `significant_figures` or another notation, values are rounded
+/// to 2 decimal places with trailing zeros trimmed.
+pub fn format_output_with(
+    results: &[ConversionResult],
+    format: &NumberFormat,
 ) -> Result<String, String> {
     if results.is_empty() {
         return Ok(String::new());
     }
 
     // Validate precision
-    if let Some(p) = precision
+    if let Some(p) = format.decimals
         && p > 10
     {
         return Err("Precision must be between 0 and 10".to_string());
     }
+    if format.significant_figures == Some(0) {
+        return Err("Significant figures must be at least 1".to_string());
+    }
+
+    let systems: Vec<Option<UnitSystem>> = results
+        .iter()
+        .map(|r| find_unit(&r.output_unit).and_then(|u| u.system()))
+        .collect();
+    let grouped =
+        systems.contains(&Some(UnitSystem::Si)) && systems.contains(&Some(UnitSystem::Iec));
 
     // Pre-allocate capacity to avoid reallocations
     let mut lines = Vec::with_capacity(results.len());
 
-    for result in results {
-        let formatted_value = format_value(result.output_value, precision);
-        let line = format!("{} {}", formatted_value, result.output_unit);
+    for (i, result) in results.iter().enumerate() {
+        if grouped
+            && let Some(system) = systems[i]
+            && (i == 0 || systems[i - 1] != Some(system))
+        {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}:", system.label()));
+        }
+        // Durations shown `to human` or `to iso` are rendered from seconds
+        if let Some(format) = DurationFormat::from_name(&result.output_unit) {
+            lines.push(format.format(result.output_value));
+            continue;
+        }
+
+        if compound::is_compound(&result.output_unit) {
+            lines.push(format_compound(
+                result.output_value,
+                &result.output_unit,
+                format,
+            )?);
+            continue;
+        }
+
+        let formatted_value = format_value(result.output_value, format);
+        // Dimensionless results from unit expressions have no unit
+        let line = if result.output_unit.is_empty() {
+            formatted_value
+        } else {
+            format!("{} {}", formatted_value, result.output_unit)
+        };
         lines.push(line);
     }
 
     Ok(lines.join("\n"))
 }
 
-fn format_value(value: f64, precision: Option<usize>) -> String {
-    match precision {
-        Some(p) => format!("{:.prec$}", value, prec = p),
-        None => {
-            // Default to 2 decimal places
-            // If value is very close to integer, show as integer
-            if (value.round() - value).abs() < 0.0001 {
-                format!("{}", value.round() as i64)
+/// Format a compound result like `5 ft 10.87 in`; the value is in the first unit
+fn format_compound(value: f64, target: &str, format: &NumberFormat) -> Result<String, String> {
+    let parts = compound::parse_target(target)?;
+    let units: Vec<_> = parts.iter().map(|(unit, _)| *unit).collect();
+    let values = compound::split(value, &units, format.decimals.unwrap_or(2));
+
+    let last = values.len() - 1;
+    let formatted: Vec<String> = values
+        .iter()
+        .zip(&parts)
+        .enumerate()
+        .map(|(i, (value, (_, name)))| {
+            let value = if i == last {
+                format_value(*value, format)
             } else {
-                // Show 2 decimal places, trim trailing zeros
-                let formatted = format!("{:.2}", value);
-                formatted
-                    .trim_end_matches('0')
-                    .trim_end_matches('.')
-                    .to_string()
-            }
-        }
+                format!("{}", value)
+            };
+            format!("{} {}", value, name)
+        })
+        .collect();
+
+    let sign = if value < 0.0 { "-" } else { "" };
+    Ok(format!("{}{}", sign, formatted.join(" ")))
+}
+
+fn format_value(value: f64, format: &NumberFormat) -> String {
+    if format.decimals.is_some()
+        || format.significant_figures.is_some()
+        || format.notation != Notation::Fixed
+    {
+        return format.format(value);
+    }
+
+    // Default to 2 decimal places
+    // If value is very close to integer, show as integer
+    let decimals = if (value.round() - value).abs() < 0.0001 {
+        0
+    } else {
+        2
+    };
+    let rounded = NumberFormat {
+        decimals: Some(decimals),
+        ..*format
+    }
+    .format_nonzero(value);
+    // Trim trailing zeros, unless tiny values fell back to scientific notation
+    if decimals > 0 && !rounded.contains('e') {
+        rounded
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        rounded
     }
 }
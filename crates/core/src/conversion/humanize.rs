@@ -0,0 +1,113 @@
+//! Auto-scaled output: `to auto` picks the most readable unit for a value.
+//!
+//! Each category has one or more scales, ordered from the smallest unit to the largest.
+//! A value is shown in the largest unit of its scale that keeps it at 1 or more, so
+//! 1536000 bytes are `1.46 MiB` and 93784 s are `1.09 day`. The scale is the one the
+//! source unit belongs to: kB stays in powers of 1000 and ft stays imperial. Units on
+//! no scale use the first scale of their category.
+//!
+//! Categories without a scale (temperature, speed, angle, typography, currency and
+//! custom categories) keep the source unit.
+
+use super::engine;
+use super::types::{ConversionRequest, ConversionResult, Unit, find_unit};
+
+/// Scales by canonical unit name, smallest unit first
+const SCALES: &[&[&str]] = &[
+    &["byte", "KiB", "MiB", "GiB", "TiB", "PiB"],
+    &["byte", "kB", "MB", "GB", "TB", "PB"],
+    &["bit", "kbit", "Mbit", "Gbit", "Tbit"],
+    &["ns", "us", "ms", "s", "min", "h", "day"],
+    &["mm", "cm", "m", "km"],
+    &["in", "ft", "mi"],
+    &["bps", "kbps", "Mbps", "Gbps", "Tbps"],
+    &["B/s", "KiB/s", "MiB/s", "GiB/s", "TiB/s"],
+    &["B/s", "kB/s", "MB/s", "GB/s", "TB/s"],
+    &["ug", "mg", "g", "kg", "t"],
+    &["oz", "lb"],
+    &["ml", "l", "m3"],
+    &["tsp", "tbsp", "floz", "cup", "gal"],
+    &["mm2", "cm2", "m2", "ha", "km2"],
+    &["in2", "ft2", "acre", "mi2"],
+    &["J", "kJ", "MJ"],
+    &["Wh", "kWh"],
+    &["cal", "kcal"],
+    &["Pa", "kPa", "MPa"],
+    &["mbar", "bar"],
+    &["Hz", "kHz", "MHz", "GHz"],
+];
+
+/// Check whether a target asks for auto-scaled output
+pub fn is_auto(target: &str) -> bool {
+    target.trim().eq_ignore_ascii_case("auto")
+}
+
+/// Pick the most readable unit for a value given in `from`
+///
+/// Returns `None` when the value should stay in `from`: its category has no scale,
+/// the unit has no fixed multiplier, or the value is zero or not finite.
+pub fn best_unit(value: f64, from: &Unit) -> Option<&'static Unit> {
+    let multiplier = from.to_base_multiplier?;
+    if value == 0.0 || !value.is_finite() {
+        return None;
+    }
+
+    let scale = scale_of(from)?;
+    let base = (value * multiplier).abs();
+    // Allow for rounding, so 1000 ms shows as 1 s
+    scale
+        .iter()
+        .rev()
+        .find(|unit| {
+            unit.to_base_multiplier
+                .is_some_and(|m| base / m >= 1.0 - 1e-9)
+        })
+        .or_else(|| scale.first())
+        .copied()
+}
+
+fn scale_of(unit: &Unit) -> Option<Vec<&'static Unit>> {
+    let scales: Vec<Vec<&'static Unit>> = SCALES
+        .iter()
+        .map(|names| names.iter().filter_map(|name| find_unit(name)).collect())
+        .filter(|units: &Vec<&'static Unit>| {
+            units.first().is_some_and(|u| u.category == unit.category)
+        })
+        .collect();
+
+    let position = scales
+        .iter()
+        .position(|scale| {
+            scale
+                .iter()
+                .any(|u| u.canonical_name == unit.canonical_name)
+        })
+        .unwrap_or(0);
+    scales.into_iter().nth(position)
+}
+
+/// Show a result in the most readable unit of its output unit's scale
+///
+/// Results whose output is not a single registered unit (compound targets, `to human`,
+/// unit expressions like `m*s`), or that are already in the best unit, are returned unchanged.
+pub fn humanize(result: ConversionResult) -> Result<ConversionResult, String> {
+    let Some(unit) = find_unit(&result.output_unit) else {
+        return Ok(result);
+    };
+    let Some(best) = best_unit(result.output_value, unit)
+        .filter(|best| best.canonical_name != unit.canonical_name)
+    else {
+        return Ok(result);
+    };
+
+    let scaled = engine::convert(&ConversionRequest {
+        value: result.output_value,
+        from_unit: result.output_unit,
+        to_unit: Some(best.canonical_name.to_string()),
+    })?;
+    Ok(ConversionResult {
+        input_value: result.input_value,
+        input_unit: result.input_unit,
+        ..scaled
+    })
+}
@@ -1,4 +1,12 @@
+pub mod compound;
+pub mod currency;
+pub mod custom;
+pub mod duration;
 pub mod engine;
+pub mod expression;
 pub mod formatter;
+pub mod humanize;
 pub mod parser;
+pub mod suggestions;
 pub mod types;
+pub mod typography;
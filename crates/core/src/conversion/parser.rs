@@ -4,12 +4,19 @@
 //! - With separator: "10 km to mi", "10 km in mi"
 //! - Without separator: "10 km mi", "10km mi"
 //! - Concatenated: "10km", "1.5mb"
+//! - Compound durations: "1h30m15s to min", "PT1H30M to s"
+//! - Duration output: "5415 s to human", "90 min to iso"
+//! - Mixed units: "5 ft 11 in to cm", "1 h 20 min to s"
+//! - Compound targets: "180 cm to ft+in", "5415 s to h+min+s"
 //!
 //! **Limitations**:
 //! - Scientific notation in concatenated format is not supported (e.g., "1e6km" will fail)
 //! - Use whitespace-separated format for scientific notation (e.g., "1e6 km" works)
 
+use super::duration::{self, DurationFormat};
+use super::suggestions::unknown_unit_error;
 use super::types::{ConversionRequest, find_unit};
+use super::{compound, humanize};
 
 pub fn parse_input(expression: &str) -> Result<ConversionRequest, String> {
     let expr = expression.trim();
@@ -23,10 +30,16 @@ pub fn parse_input(expression: &str) -> Result<ConversionRequest, String> {
         return Err("Empty expression".to_string());
     }
 
-    // Find separator keyword ("to" or "in")
+    // Find separator keyword: "to", or else an "in" that is not the inch unit
+    // right after a number (as in "5 ft 11 in in cm")
     let separator_pos = tokens
         .iter()
-        .position(|&t| t.eq_ignore_ascii_case("to") || t.eq_ignore_ascii_case("in"));
+        .position(|t| t.eq_ignore_ascii_case("to"))
+        .or_else(|| {
+            tokens.iter().enumerate().position(|(i, t)| {
+                t.eq_ignore_ascii_case("in") && i > 0 && tokens[i - 1].parse::<f64>().is_err()
+            })
+        });
 
     let (value_from_tokens, to_tokens) = match separator_pos {
         Some(pos) => {
@@ -80,11 +93,13 @@ pub fn parse_input(expression: &str) -> Result<ConversionRequest, String> {
         };
 
         // Validate unit exists
-        if find_unit(&unit_str).is_none() {
-            return Err(format!(
-                "Unknown target unit: '{}'. Please check unit spelling.",
-                unit_str
-            ));
+        if compound::is_compound(&unit_str) {
+            compound::parse_target(&unit_str)?;
+        } else if find_unit(&unit_str).is_none()
+            && DurationFormat::from_name(&unit_str).is_none()
+            && !humanize::is_auto(&unit_str)
+        {
+            return Err(unknown_unit_error("Unknown target unit", &unit_str));
         }
 
         Some(unit_str)
@@ -102,6 +117,74 @@ fn parse_value_and_unit(tokens: &[&str]) -> Result<(f64, String), String> {
         return Err("Missing value and unit".to_string());
     }
 
+    parse_single_value_and_unit(tokens).or_else(|error| {
+        // Compound durations like "1h30m", "1h 30m 15s" or "P1DT2H" become seconds
+        if let Ok(seconds) = duration::parse_duration(&tokens.join(" ")) {
+            return Ok((seconds, "s".to_string()));
+        }
+
+        // Several value-unit pairs like "5 ft 11 in"
+        let groups = split_value_groups(tokens);
+        if groups.len() > 1 {
+            parse_mixed_units(&groups)
+        } else {
+            Err(error)
+        }
+    })
+}
+
+/// Split tokens into groups that each start with a number: "5 ft 11in" -> ["5", "ft"], ["11in"]
+fn split_value_groups<'a>(tokens: &'a [&'a str]) -> Vec<&'a [&'a str]> {
+    let starts_number = |token: &str| {
+        let digits = token.strip_prefix(['-', '+']).unwrap_or(token);
+        digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+    };
+
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for i in 1..=tokens.len() {
+        if i == tokens.len() || starts_number(tokens[i]) {
+            groups.push(&tokens[start..i]);
+            start = i;
+        }
+    }
+    groups
+}
+
+/// Sum value-unit pairs of the same category, expressed in the first pair's unit
+fn parse_mixed_units(groups: &[&[&str]]) -> Result<(f64, String), String> {
+    let mut total = 0.0;
+    let mut first: Option<(&'static super::types::Unit, String)> = None;
+
+    for group in groups {
+        let (value, unit_str) = parse_single_value_and_unit(group)?;
+        let unit =
+            find_unit(&unit_str).ok_or_else(|| unknown_unit_error("Unknown unit", &unit_str))?;
+        let multiplier = unit
+            .to_base_multiplier
+            .ok_or_else(|| format!("Unit '{}' cannot be combined with other values", unit_str))?;
+
+        if let Some((first_unit, first_str)) = &first
+            && first_unit.category != unit.category
+        {
+            return Err(format!(
+                "Cannot combine '{}' ({}) and '{}' ({}). Units must be in the same category.",
+                first_str, first_unit.category, unit_str, unit.category
+            ));
+        }
+
+        total += value * multiplier;
+        if first.is_none() {
+            first = Some((unit, unit_str));
+        }
+    }
+
+    let (unit, unit_str) = first.ok_or_else(|| "Missing value and unit".to_string())?;
+    let multiplier = unit.to_base_multiplier.unwrap_or(1.0);
+    Ok((total / multiplier, unit_str))
+}
+
+fn parse_single_value_and_unit(tokens: &[&str]) -> Result<(f64, String), String> {
     // Case 1: Single token like "10km" - value and unit concatenated
     if tokens.len() == 1 {
         return parse_concatenated_value_unit(tokens[0]);
@@ -125,10 +208,7 @@ fn parse_value_and_unit(tokens: &[&str]) -> Result<(f64, String), String> {
 
     // Validate unit exists (find_unit handles case sensitivity for data rate)
     if find_unit(&unit_str).is_none() {
-        return Err(format!(
-            "Unknown unit: '{}'. Please check unit spelling.",
-            unit_str
-        ));
+        return Err(unknown_unit_error("Unknown unit", &unit_str));
     }
 
     Ok((value, unit_str))
@@ -183,10 +263,7 @@ fn parse_concatenated_value_unit(token: &str) -> Result<(f64, String), String> {
 
     // Validate unit exists (find_unit handles case sensitivity for data rate)
     if find_unit(&trimmed_unit).is_none() {
-        return Err(format!(
-            "Unknown unit: '{}'. Please check unit spelling.",
-            unit_str
-        ));
+        return Err(unknown_unit_error("Unknown unit", unit_str));
     }
 
     Ok((value, trimmed_unit))
@@ -0,0 +1,98 @@
+//! "Did you mean" suggestions for unknown units.
+//!
+//! Candidates are every canonical name and alias in the unit registry. A name is a
+//! candidate when the input is a prefix of it (`kilom` -> `kilometer`) or when it is
+//! within a small edit distance (`kms` -> `km`). Matching ignores case.
+
+use super::currency::missing_currency_error;
+use super::types::{Unit, UnitCategory, all_units, get_units_in_category};
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Registered unit names closest to `input`, best first, at most one per unit
+pub fn suggest_units(input: &str) -> Vec<&'static str> {
+    let needle = input.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    // Allow one typo per three characters
+    let max_distance = (needle.chars().count() / 3).max(1);
+
+    let mut matches: Vec<(usize, &'static str)> = all_units()
+        .into_iter()
+        .filter_map(|unit| {
+            unit_names(unit)
+                .filter_map(|name| score(&needle, name, max_distance).map(|s| (s, name)))
+                .min_by_key(|&(s, name)| (s, name.len()))
+        })
+        .collect();
+
+    matches.sort_by_key(|&(s, name)| (s, name.len(), name));
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Error message for an unknown unit, e.g. `Unknown unit: 'kms'. Did you mean 'km' or 'ms'?`
+///
+/// Currency codes explain instead that no rate table is configured, or that it lacks the code.
+pub fn unknown_unit_error(label: &str, input: &str) -> String {
+    if let Some(error) = missing_currency_error(input) {
+        return error;
+    }
+    let suggestions = suggest_units(input);
+    if suggestions.is_empty() {
+        return format!("{}: '{}'. Please check unit spelling.", label, input);
+    }
+
+    let quoted: Vec<String> = suggestions.iter().map(|s| format!("'{}'", s)).collect();
+    let list = match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.join(""),
+    };
+    format!("{}: '{}'. Did you mean {}?", label, input, list)
+}
+
+/// List the units of a category, e.g. `Valid Length units: mm, cm, m, km, ...`
+pub fn category_units_hint(category: UnitCategory) -> String {
+    let names: Vec<&str> = get_units_in_category(category)
+        .iter()
+        .map(|u| u.canonical_name)
+        .collect();
+    format!("Valid {} units: {}", category, names.join(", "))
+}
+
+fn unit_names(unit: &'static Unit) -> impl Iterator<Item = &'static str> {
+    std::iter::once(unit.canonical_name).chain(unit.aliases.iter().copied())
+}
+
+/// Prefix matches rank first, then names by edit distance
+fn score(needle: &str, name: &str, max_distance: usize) -> Option<usize> {
+    let candidate = name.to_lowercase();
+    if candidate == needle {
+        return Some(0);
+    }
+    if needle.chars().count() >= 2 && candidate.starts_with(needle) {
+        return Some(1);
+    }
+    let distance = edit_distance(needle, &candidate);
+    (distance <= max_distance).then_some(distance)
+}
+
+/// Levenshtein distance over characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UnitCategory {
@@ -7,6 +8,78 @@ pub enum UnitCategory {
     Time,
     Length,
     Temperature,
+    DataRate,
+    Mass,
+    Volume,
+    Area,
+    Speed,
+    Angle,
+    Energy,
+    Pressure,
+    Frequency,
+    /// CSS and print units, parameterised by `conversion::typography::TypographySettings`
+    Typography,
+    /// Currencies from a rate table, see `conversion::currency`
+    Currency,
+    /// Category defined in a units file, e.g. `Effort`
+    Custom(&'static str),
+}
+
+impl UnitCategory {
+    /// Look up a category by name, e.g. `length`, `data_rate` or a custom category
+    pub fn from_name(name: &str) -> Option<Self> {
+        let category = match name.trim().to_lowercase().as_str() {
+            "bytes" => UnitCategory::Bytes,
+            "time" => UnitCategory::Time,
+            "length" => UnitCategory::Length,
+            "temperature" => UnitCategory::Temperature,
+            "datarate" | "data_rate" => UnitCategory::DataRate,
+            "mass" => UnitCategory::Mass,
+            "volume" => UnitCategory::Volume,
+            "area" => UnitCategory::Area,
+            "speed" => UnitCategory::Speed,
+            "angle" => UnitCategory::Angle,
+            "energy" => UnitCategory::Energy,
+            "pressure" => UnitCategory::Pressure,
+            "frequency" => UnitCategory::Frequency,
+            "typography" => UnitCategory::Typography,
+            "currency" => UnitCategory::Currency,
+            _ => {
+                return all_units().into_iter().find_map(|u| match u.category {
+                    UnitCategory::Custom(custom) if custom.eq_ignore_ascii_case(name.trim()) => {
+                        Some(u.category)
+                    }
+                    _ => None,
+                });
+            }
+        };
+        Some(category)
+    }
+
+    /// Base categories a derived category is made of, e.g. Speed is Length/Time
+    ///
+    /// Base units of derived categories are the product of the base units of their
+    /// parts (m/s, byte/s, m², m³, 1/s), so their multipliers combine directly in
+    /// unit arithmetic. Returns `None` for categories that are not derived.
+    pub fn base_dimensions(self) -> Option<&'static [(UnitCategory, i32)]> {
+        match self {
+            UnitCategory::DataRate => Some(&[(UnitCategory::Bytes, 1), (UnitCategory::Time, -1)]),
+            UnitCategory::Area => Some(&[(UnitCategory::Length, 2)]),
+            UnitCategory::Volume => Some(&[(UnitCategory::Length, 3)]),
+            UnitCategory::Speed => Some(&[(UnitCategory::Length, 1), (UnitCategory::Time, -1)]),
+            UnitCategory::Frequency => Some(&[(UnitCategory::Time, -1)]),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UnitCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitCategory::Custom(name) => write!(f, "{}", name),
+            other => write!(f, "{:?}", other),
+        }
+    }
 }
 
 // Unit struct is small (~41 bytes) so cloning is efficient
@@ -16,7 +89,46 @@ pub struct Unit {
     pub canonical_name: &'static str,
     pub aliases: &'static [&'static str],
     pub category: UnitCategory,
-    pub to_base_multiplier: Option<f64>, // None for temperature (formula-based)
+    pub to_base_multiplier: Option<f64>, // None for temperature and offset units (formula-based)
+}
+
+/// Prefix system of byte and data rate units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnitSystem {
+    /// Powers of 1000: kB, MB, GB, kbps
+    Si,
+    /// Powers of 1024: KiB, MiB, GiB
+    Iec,
+}
+
+impl UnitSystem {
+    pub fn label(self) -> &'static str {
+        match self {
+            UnitSystem::Si => "SI (powers of 1000)",
+            UnitSystem::Iec => "IEC (powers of 1024)",
+        }
+    }
+}
+
+impl Unit {
+    /// Prefix system of a prefixed byte or data rate unit
+    ///
+    /// IEC canonical names carry an `i` after the prefix (`KiB`, `MiB/s`); every
+    /// other prefixed unit is SI. Unprefixed units (`bit`, `byte`, `B/s`) have none.
+    pub fn system(&self) -> Option<UnitSystem> {
+        if !matches!(self.category, UnitCategory::Bytes | UnitCategory::DataRate) {
+            return None;
+        }
+        let mut chars = self.canonical_name.chars();
+        if !matches!(chars.next(), Some('k' | 'K' | 'M' | 'G' | 'T' | 'P')) {
+            return None;
+        }
+        if chars.as_str().starts_with("iB") {
+            Some(UnitSystem::Iec)
+        } else {
+            Some(UnitSystem::Si)
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,12 +146,16 @@ pub struct ConversionResult {
     pub output_unit: String,
 }
 
-static UNIT_REGISTRY: OnceLock<HashMap<String, Unit>> = OnceLock::new();
-
-fn build_unit_registry() -> HashMap<String, Unit> {
-    let mut registry = HashMap::new();
+static BUILTIN_UNITS: OnceLock<Vec<Unit>> = OnceLock::new();
+static UNIT_REGISTRY: OnceLock<RwLock<HashMap<String, &'static Unit>>> = OnceLock::new();
 
+fn build_builtin_units() -> Vec<Unit> {
     // Bytes / Data Size
+    //
+    // Case rules:
+    // - `b` and `bit` are bits, `B` and `byte` are bytes, so `Mb` is a megabit and `MB` a megabyte
+    // - SI prefixes are powers of 1000: `kB`, `MB`, `GB` (also `KB`, `kilobyte`)
+    // - IEC prefixes are powers of 1024 and always carry an `i`: `KiB`, `MiB`, `GiB` in any case
     let bytes_units = vec![
         // Bits
         Unit {
@@ -48,81 +164,96 @@ fn build_unit_registry() -> HashMap<String, Unit> {
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(0.125), // 1 bit = 0.125 bytes
         },
+        Unit {
+            canonical_name: "kbit",
+            aliases: &["kb", "Kb", "Kbit", "kilobit", "kilobits"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(125.0), // 1000 bits
+        },
+        Unit {
+            canonical_name: "Mbit",
+            aliases: &["Mb", "megabit", "megabits"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(125_000.0), // 1000^2 bits
+        },
+        Unit {
+            canonical_name: "Gbit",
+            aliases: &["Gb", "gigabit", "gigabits"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(125_000_000.0), // 1000^3 bits
+        },
+        Unit {
+            canonical_name: "Tbit",
+            aliases: &["Tb", "terabit", "terabits"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(125_000_000_000.0), // 1000^4 bits
+        },
         // Bytes (base unit)
         Unit {
             canonical_name: "byte",
-            aliases: &["bytes"],
+            aliases: &["B", "bytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1.0),
         },
-        // Binary (1024-based) - both lowercase (kb, mb) and uppercase (KiB, MiB) are aliases
+        // SI (1000-based)
         Unit {
-            canonical_name: "kb",
-            aliases: &[
-                "KB",
-                "kib",
-                "KiB",
-                "kilobyte",
-                "kilobytes",
-                "kibibyte",
-                "kibibytes",
-            ],
+            canonical_name: "kB",
+            aliases: &["KB", "kilobyte", "kilobytes"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "MB",
+            aliases: &["megabyte", "megabytes"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(1_000_000.0), // 1000^2
+        },
+        Unit {
+            canonical_name: "GB",
+            aliases: &["gigabyte", "gigabytes"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(1_000_000_000.0), // 1000^3
+        },
+        Unit {
+            canonical_name: "TB",
+            aliases: &["terabyte", "terabytes"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(1_000_000_000_000.0), // 1000^4
+        },
+        Unit {
+            canonical_name: "PB",
+            aliases: &["petabyte", "petabytes"],
+            category: UnitCategory::Bytes,
+            to_base_multiplier: Some(1_000_000_000_000_000.0), // 1000^5
+        },
+        // IEC (1024-based)
+        Unit {
+            canonical_name: "KiB",
+            aliases: &["kib", "kibibyte", "kibibytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1024.0),
         },
         Unit {
-            canonical_name: "mb",
-            aliases: &[
-                "MB",
-                "mib",
-                "MiB",
-                "megabyte",
-                "megabytes",
-                "mebibyte",
-                "mebibytes",
-            ],
+            canonical_name: "MiB",
+            aliases: &["mib", "mebibyte", "mebibytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1_048_576.0), // 1024^2
         },
         Unit {
-            canonical_name: "gb",
-            aliases: &[
-                "GB",
-                "gib",
-                "GiB",
-                "gigabyte",
-                "gigabytes",
-                "gibibyte",
-                "gibibytes",
-            ],
+            canonical_name: "GiB",
+            aliases: &["gib", "gibibyte", "gibibytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1_073_741_824.0), // 1024^3
         },
         Unit {
-            canonical_name: "tb",
-            aliases: &[
-                "TB",
-                "tib",
-                "TiB",
-                "terabyte",
-                "terabytes",
-                "tebibyte",
-                "tebibytes",
-            ],
+            canonical_name: "TiB",
+            aliases: &["tib", "tebibyte", "tebibytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1_099_511_627_776.0), // 1024^4
         },
         Unit {
-            canonical_name: "pb",
-            aliases: &[
-                "PB",
-                "pib",
-                "PiB",
-                "petabyte",
-                "petabytes",
-                "pebibyte",
-                "pebibytes",
-            ],
+            canonical_name: "PiB",
+            aliases: &["pib", "pebibyte", "pebibytes"],
             category: UnitCategory::Bytes,
             to_base_multiplier: Some(1_125_899_906_842_624.0), // 1024^5
         },
@@ -258,31 +389,743 @@ fn build_unit_registry() -> HashMap<String, Unit> {
         },
     ];
 
-    // Register all units with lowercase normalization
-    for unit in bytes_units
+    // Data rate (base unit: bytes per second)
+    // Bit rates are decimal (1 kbps = 1000 bit/s); byte rates follow the byte case rules above
+    let data_rate_units = vec![
+        Unit {
+            canonical_name: "bps",
+            aliases: &["bit/s", "bits/s", "b/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(0.125),
+        },
+        Unit {
+            canonical_name: "kbps",
+            aliases: &[
+                "Kbps",
+                "kbit/s",
+                "Kbit/s",
+                "kb/s",
+                "Kb/s",
+                "kilobit/s",
+                "kilobits/s",
+            ],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(125.0), // 1000 bits
+        },
+        Unit {
+            canonical_name: "Mbps",
+            aliases: &[
+                "mbps",
+                "Mbit/s",
+                "mbit/s",
+                "Mb/s",
+                "megabit/s",
+                "megabits/s",
+            ],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(125_000.0),
+        },
+        Unit {
+            canonical_name: "Gbps",
+            aliases: &[
+                "gbps",
+                "Gbit/s",
+                "gbit/s",
+                "Gb/s",
+                "gigabit/s",
+                "gigabits/s",
+            ],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(125_000_000.0),
+        },
+        Unit {
+            canonical_name: "Tbps",
+            aliases: &[
+                "tbps",
+                "Tbit/s",
+                "tbit/s",
+                "Tb/s",
+                "terabit/s",
+                "terabits/s",
+            ],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(125_000_000_000.0),
+        },
+        // B/s (base unit)
+        Unit {
+            canonical_name: "B/s",
+            aliases: &["Bps", "byte/s", "bytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "kB/s",
+            aliases: &["KB/s", "kBps", "KBps", "kilobyte/s", "kilobytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "MB/s",
+            aliases: &["MBps", "megabyte/s", "megabytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_000_000.0),
+        },
+        Unit {
+            canonical_name: "GB/s",
+            aliases: &["GBps", "gigabyte/s", "gigabytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_000_000_000.0),
+        },
+        Unit {
+            canonical_name: "TB/s",
+            aliases: &["TBps", "terabyte/s", "terabytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_000_000_000_000.0),
+        },
+        Unit {
+            canonical_name: "KiB/s",
+            aliases: &["kib/s", "KiBps", "kibibyte/s", "kibibytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1024.0),
+        },
+        Unit {
+            canonical_name: "MiB/s",
+            aliases: &["mib/s", "MiBps", "mebibyte/s", "mebibytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_048_576.0),
+        },
+        Unit {
+            canonical_name: "GiB/s",
+            aliases: &["gib/s", "GiBps", "gibibyte/s", "gibibytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_073_741_824.0),
+        },
+        Unit {
+            canonical_name: "TiB/s",
+            aliases: &["tib/s", "TiBps", "tebibyte/s", "tebibytes/s"],
+            category: UnitCategory::DataRate,
+            to_base_multiplier: Some(1_099_511_627_776.0),
+        },
+    ];
+
+    // Mass (base unit: kilogram)
+    let mass_units = vec![
+        Unit {
+            canonical_name: "ug",
+            aliases: &["µg", "mcg", "microgram", "micrograms"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(0.000_000_001),
+        },
+        Unit {
+            canonical_name: "mg",
+            aliases: &["milligram", "milligrams"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(0.000_001),
+        },
+        Unit {
+            canonical_name: "g",
+            aliases: &["gram", "grams"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(0.001),
+        },
+        // kg (base unit)
+        Unit {
+            canonical_name: "kg",
+            aliases: &["kilogram", "kilograms", "kilo", "kilos"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "t",
+            aliases: &["tonne", "tonnes"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "oz",
+            aliases: &["ounce", "ounces"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(0.028_349_523_125),
+        },
+        Unit {
+            canonical_name: "lb",
+            aliases: &["lbs", "pound", "pounds"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(0.453_592_37),
+        },
+        Unit {
+            canonical_name: "st",
+            aliases: &["stone", "stones"],
+            category: UnitCategory::Mass,
+            to_base_multiplier: Some(6.350_293_18), // 14 lb
+        },
+    ];
+
+    // Volume (base unit: cubic meter)
+    // US customary units; pints have no `pt` alias since pt is a point in typography
+    let volume_units = vec![
+        Unit {
+            canonical_name: "ml",
+            aliases: &[
+                "mL",
+                "cc",
+                "milliliter",
+                "milliliters",
+                "millilitre",
+                "millilitres",
+            ],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_001),
+        },
+        Unit {
+            canonical_name: "tsp",
+            aliases: &["teaspoon", "teaspoons"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_004_928_921_593_75),
+        },
+        Unit {
+            canonical_name: "tbsp",
+            aliases: &["tablespoon", "tablespoons"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_014_786_764_781_25),
+        },
+        Unit {
+            canonical_name: "floz",
+            aliases: &["fl_oz", "fluid ounce", "fluid ounces"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_029_573_529_562_5),
+        },
+        Unit {
+            canonical_name: "cup",
+            aliases: &["cups"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_236_588_236_5),
+        },
+        Unit {
+            canonical_name: "pint",
+            aliases: &["pints"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_473_176_473),
+        },
+        Unit {
+            canonical_name: "qt",
+            aliases: &["quart", "quarts"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.000_946_352_946),
+        },
+        Unit {
+            canonical_name: "l",
+            aliases: &["L", "liter", "liters", "litre", "litres"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.001),
+        },
+        Unit {
+            canonical_name: "gal",
+            aliases: &["gallon", "gallons"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.003_785_411_784),
+        },
+        Unit {
+            canonical_name: "ft3",
+            aliases: &["ft³", "cubic foot", "cubic feet"],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(0.028_316_846_592),
+        },
+        // m3 (base unit)
+        Unit {
+            canonical_name: "m3",
+            aliases: &[
+                "m³",
+                "cubic meter",
+                "cubic meters",
+                "cubic metre",
+                "cubic metres",
+            ],
+            category: UnitCategory::Volume,
+            to_base_multiplier: Some(1.0),
+        },
+    ];
+
+    // Area (base unit: square meter)
+    let area_units = vec![
+        Unit {
+            canonical_name: "mm2",
+            aliases: &[
+                "mm²",
+                "square millimeter",
+                "square millimeters",
+                "square millimetre",
+                "square millimetres",
+            ],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(0.000_001),
+        },
+        Unit {
+            canonical_name: "cm2",
+            aliases: &[
+                "cm²",
+                "square centimeter",
+                "square centimeters",
+                "square centimetre",
+                "square centimetres",
+            ],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(0.0001),
+        },
+        Unit {
+            canonical_name: "in2",
+            aliases: &["in²", "square inch", "square inches"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(0.000_645_16),
+        },
+        Unit {
+            canonical_name: "ft2",
+            aliases: &["ft²", "sqft", "sq ft", "square foot", "square feet"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(0.092_903_04),
+        },
+        Unit {
+            canonical_name: "yd2",
+            aliases: &["yd²", "square yard", "square yards"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(0.836_127_36),
+        },
+        // m2 (base unit)
+        Unit {
+            canonical_name: "m2",
+            aliases: &[
+                "m²",
+                "sqm",
+                "square meter",
+                "square meters",
+                "square metre",
+                "square metres",
+            ],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "acre",
+            aliases: &["acres", "ac"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(4_046.856_422_4),
+        },
+        Unit {
+            canonical_name: "ha",
+            aliases: &["hectare", "hectares"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(10_000.0),
+        },
+        Unit {
+            canonical_name: "km2",
+            aliases: &[
+                "km²",
+                "square kilometer",
+                "square kilometers",
+                "square kilometre",
+                "square kilometres",
+            ],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(1_000_000.0),
+        },
+        Unit {
+            canonical_name: "mi2",
+            aliases: &["mi²", "sq mi", "square mile", "square miles"],
+            category: UnitCategory::Area,
+            to_base_multiplier: Some(2_589_988.110_336),
+        },
+    ];
+
+    // Speed (base unit: meters per second)
+    let speed_units = vec![
+        Unit {
+            canonical_name: "km/h",
+            aliases: &["kph", "kmh", "kmph", "km/hr"],
+            category: UnitCategory::Speed,
+            to_base_multiplier: Some(0.277_777_777_777_777_8), // 1000 m / 3600 s
+        },
+        Unit {
+            canonical_name: "ft/s",
+            aliases: &["feet/s", "foot/s"],
+            category: UnitCategory::Speed,
+            to_base_multiplier: Some(0.3048),
+        },
+        Unit {
+            canonical_name: "mph",
+            aliases: &["mi/h", "mi/hr", "miles/h"],
+            category: UnitCategory::Speed,
+            to_base_multiplier: Some(0.447_04),
+        },
+        Unit {
+            canonical_name: "kn",
+            aliases: &["knot", "knots"],
+            category: UnitCategory::Speed,
+            to_base_multiplier: Some(0.514_444_444_444_444_5), // 1852 m / 3600 s
+        },
+        // m/s (base unit)
+        Unit {
+            canonical_name: "m/s",
+            aliases: &["mps", "meter/s", "meters/s", "metre/s", "metres/s"],
+            category: UnitCategory::Speed,
+            to_base_multiplier: Some(1.0),
+        },
+    ];
+
+    // Angle (base unit: degree)
+    let angle_units = vec![
+        Unit {
+            canonical_name: "arcsec",
+            aliases: &["arcsecond", "arcseconds"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(0.000_277_777_777_777_778), // 1/3600 degree
+        },
+        Unit {
+            canonical_name: "arcmin",
+            aliases: &["arcminute", "arcminutes"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(0.016_666_666_666_666_666), // 1/60 degree
+        },
+        Unit {
+            canonical_name: "mrad",
+            aliases: &["milliradian", "milliradians"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(0.057_295_779_513_082_325),
+        },
+        Unit {
+            canonical_name: "grad",
+            aliases: &["gon", "gradian", "gradians"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(0.9),
+        },
+        // deg (base unit)
+        Unit {
+            canonical_name: "deg",
+            aliases: &["degree", "degrees", "°"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "rad",
+            aliases: &["radian", "radians"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(57.295_779_513_082_32), // 180 / pi
+        },
+        Unit {
+            canonical_name: "turn",
+            aliases: &["turns", "rev", "revolution", "revolutions"],
+            category: UnitCategory::Angle,
+            to_base_multiplier: Some(360.0),
+        },
+    ];
+
+    // Energy (base unit: joule)
+    let energy_units = vec![
+        Unit {
+            canonical_name: "eV",
+            aliases: &["ev", "electronvolt", "electronvolts"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(1.602_176_634e-19),
+        },
+        // J (base unit)
+        Unit {
+            canonical_name: "J",
+            aliases: &["j", "joule", "joules"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "cal",
+            aliases: &["calorie", "calories"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(4.184),
+        },
+        Unit {
+            canonical_name: "kJ",
+            aliases: &["kj", "kilojoule", "kilojoules"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "BTU",
+            aliases: &["btu", "british thermal unit", "british thermal units"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(1_055.055_852_62),
+        },
+        Unit {
+            canonical_name: "Wh",
+            aliases: &["wh", "watt-hour", "watt-hours"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(3600.0),
+        },
+        Unit {
+            canonical_name: "kcal",
+            aliases: &["Cal", "kilocalorie", "kilocalories"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(4184.0), // food calorie
+        },
+        Unit {
+            canonical_name: "MJ",
+            aliases: &["mj", "megajoule", "megajoules"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(1_000_000.0),
+        },
+        Unit {
+            canonical_name: "kWh",
+            aliases: &["kwh", "kilowatt-hour", "kilowatt-hours"],
+            category: UnitCategory::Energy,
+            to_base_multiplier: Some(3_600_000.0),
+        },
+    ];
+
+    // Pressure (base unit: pascal)
+    let pressure_units = vec![
+        // Pa (base unit)
+        Unit {
+            canonical_name: "Pa",
+            aliases: &["pa", "pascal", "pascals"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "hPa",
+            aliases: &["hpa", "hectopascal", "hectopascals"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(100.0),
+        },
+        Unit {
+            canonical_name: "mbar",
+            aliases: &["millibar", "millibars"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(100.0),
+        },
+        Unit {
+            canonical_name: "torr",
+            aliases: &["Torr"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(133.322_368_421_052_63), // 101325 / 760
+        },
+        Unit {
+            canonical_name: "mmHg",
+            aliases: &["mmhg"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(133.322_387_415),
+        },
+        Unit {
+            canonical_name: "kPa",
+            aliases: &["kpa", "kilopascal", "kilopascals"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "psi",
+            aliases: &["PSI"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(6_894.757_293_168),
+        },
+        Unit {
+            canonical_name: "bar",
+            aliases: &["bars"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(100_000.0),
+        },
+        Unit {
+            canonical_name: "atm",
+            aliases: &["atmosphere", "atmospheres"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(101_325.0),
+        },
+        Unit {
+            canonical_name: "MPa",
+            aliases: &["mpa", "megapascal", "megapascals"],
+            category: UnitCategory::Pressure,
+            to_base_multiplier: Some(1_000_000.0),
+        },
+    ];
+
+    // Frequency (base unit: hertz)
+    let frequency_units = vec![
+        Unit {
+            canonical_name: "rpm",
+            aliases: &["RPM"],
+            category: UnitCategory::Frequency,
+            to_base_multiplier: Some(0.016_666_666_666_666_666), // 1/60 Hz
+        },
+        // Hz (base unit)
+        Unit {
+            canonical_name: "Hz",
+            aliases: &["hz", "hertz"],
+            category: UnitCategory::Frequency,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "kHz",
+            aliases: &["khz", "kilohertz"],
+            category: UnitCategory::Frequency,
+            to_base_multiplier: Some(1000.0),
+        },
+        Unit {
+            canonical_name: "MHz",
+            aliases: &["mhz", "megahertz"],
+            category: UnitCategory::Frequency,
+            to_base_multiplier: Some(1_000_000.0),
+        },
+        Unit {
+            canonical_name: "GHz",
+            aliases: &["ghz", "gigahertz"],
+            category: UnitCategory::Frequency,
+            to_base_multiplier: Some(1_000_000_000.0),
+        },
+    ];
+
+    // Typography (base unit: CSS pixel)
+    //
+    // Only px is fixed. The size of the others depends on the root font size (rem, em),
+    // the viewport (vw, vh) and the DPI (pt, pc), so the engine resolves them through
+    // `typography::px_per_unit`. `pt` is the typographic point; pint has no `pt` alias.
+    let typography_units = vec![
+        Unit {
+            canonical_name: "px",
+            aliases: &["pixel", "pixels"],
+            category: UnitCategory::Typography,
+            to_base_multiplier: Some(1.0),
+        },
+        Unit {
+            canonical_name: "rem",
+            aliases: &["rems"],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // root font size
+        },
+        Unit {
+            canonical_name: "em",
+            aliases: &["ems"],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // font size, taken to be the root font size
+        },
+        Unit {
+            canonical_name: "pt",
+            aliases: &["point", "points"],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // 1/72 in at the DPI
+        },
+        Unit {
+            canonical_name: "pc",
+            aliases: &["pica", "picas"],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // 12 pt
+        },
+        Unit {
+            canonical_name: "vw",
+            aliases: &[],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // 1% of the viewport width
+        },
+        Unit {
+            canonical_name: "vh",
+            aliases: &[],
+            category: UnitCategory::Typography,
+            to_base_multiplier: None, // 1% of the viewport height
+        },
+    ];
+
+    bytes_units
         .into_iter()
         .chain(time_units)
         .chain(length_units)
         .chain(temperature_units)
-    {
-        // Register canonical name (lowercase)
-        registry.insert(unit.canonical_name.to_lowercase(), unit.clone());
+        .chain(data_rate_units)
+        .chain(mass_units)
+        .chain(volume_units)
+        .chain(area_units)
+        .chain(speed_units)
+        .chain(angle_units)
+        .chain(energy_units)
+        .chain(pressure_units)
+        .chain(frequency_units)
+        .chain(typography_units)
+        .collect()
+}
 
-        // Register all aliases (lowercase)
-        for alias in unit.aliases {
-            registry.insert(alias.to_lowercase(), unit.clone());
+/// Add every name of a unit to the registry
+///
+/// Names are registered exactly as written, so case-sensitive spellings like
+/// `Mb/s` (megabits) and `MB/s` (megabytes) resolve to different units. Folded
+/// forms (see `fold_name`) are added for case-insensitive lookup, without replacing
+/// a name that is spelled that way exactly.
+fn insert_names(registry: &mut HashMap<String, &'static Unit>, units: &[&'static Unit]) {
+    for unit in units {
+        for name in std::iter::once(&unit.canonical_name).chain(unit.aliases) {
+            registry.insert(name.to_string(), unit);
         }
     }
+    for unit in units {
+        for name in std::iter::once(&unit.canonical_name).chain(unit.aliases) {
+            registry
+                .entry(fold_name(name, is_data_unit(unit)))
+                .or_insert(unit);
+        }
+    }
+}
 
-    registry
+fn is_data_unit(unit: &Unit) -> bool {
+    matches!(unit.category, UnitCategory::Bytes | UnitCategory::DataRate)
+}
+
+/// Case-insensitive lookup key for a unit name
+///
+/// Data unit symbols keep the case of `b` (bit) and `B` (byte) so that folding
+/// never turns `MB` into `Mb`; spelled-out names like `megabytes` fold fully.
+fn fold_name(name: &str, data: bool) -> String {
+    let lowercase = name.to_lowercase();
+    if !data || lowercase.contains("bit") || lowercase.contains("byte") {
+        return lowercase;
+    }
+    let mut folded = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == 'B' {
+            folded.push(c);
+        } else {
+            folded.extend(c.to_lowercase());
+        }
+    }
+    folded
 }
 
-fn get_unit_registry() -> &'static HashMap<String, Unit> {
-    UNIT_REGISTRY.get_or_init(build_unit_registry)
+/// Replace every unit of a category, e.g. when a new currency rate table is loaded
+pub(crate) fn replace_units(category: UnitCategory, units: &[&'static Unit]) {
+    let mut registry = get_unit_registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    registry.retain(|_, unit| unit.category != category);
+    insert_names(&mut registry, units);
+}
+
+fn get_unit_registry() -> &'static RwLock<HashMap<String, &'static Unit>> {
+    UNIT_REGISTRY.get_or_init(|| {
+        let units: Vec<&'static Unit> = BUILTIN_UNITS
+            .get_or_init(build_builtin_units)
+            .iter()
+            .collect();
+        let mut registry = HashMap::new();
+        insert_names(&mut registry, &units);
+        RwLock::new(registry)
+    })
+}
+
+/// Add units to the registry at runtime
+///
+/// Callers are expected to have checked the names for collisions; see
+/// `conversion::custom` for validated registration from a units file.
+pub(crate) fn register_units(units: &[&'static Unit]) {
+    let mut registry = get_unit_registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    insert_names(&mut registry, units);
 }
 
 pub fn find_unit(unit_str: &str) -> Option<&'static Unit> {
-    let registry = get_unit_registry();
+    let registry = get_unit_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
     let trimmed = unit_str.trim();
 
     // Try case-preserved lookup first
@@ -290,15 +1133,33 @@ pub fn find_unit(unit_str: &str) -> Option<&'static Unit> {
         return Some(unit);
     }
 
-    // Fall back to lowercase lookup
-    let normalized = trimmed.to_lowercase();
-    registry.get(&normalized)
+    // Fall back to case-insensitive lookup, keeping bits and bytes apart
+    [true, false].into_iter().find_map(|data| {
+        registry
+            .get(&fold_name(trimmed, data))
+            .copied()
+            .filter(|unit| is_data_unit(unit) == data)
+    })
+}
+
+/// Every registered unit once, sorted by canonical name
+pub fn all_units() -> Vec<&'static Unit> {
+    let registry = get_unit_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut units: Vec<&'static Unit> = registry.values().copied().collect();
+    units.sort_by_key(|u| u.canonical_name);
+    units.dedup_by_key(|u| u.canonical_name);
+    units
 }
 
 pub fn get_units_in_category(category: UnitCategory) -> Vec<&'static Unit> {
-    let registry = get_unit_registry();
-    let mut units: Vec<&Unit> = registry
+    let registry = get_unit_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut units: Vec<&'static Unit> = registry
         .values()
+        .copied()
         .filter(|u| u.category == category)
         .collect();
 
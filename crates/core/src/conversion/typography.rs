@@ -0,0 +1,151 @@
+//! CSS and print units: px, rem, em, pt, pc, vw and vh.
+//!
+//! Everything converts through CSS pixels. How many pixels a unit is depends on the
+//! settings: the root font size for `rem` and `em`, the viewport for `vw` and `vh`, and
+//! the DPI for the physical units `pt` (1/72 in) and `pc` (12 pt). At the default 96 DPI,
+//! 1 pt is 4/3 px, as in CSS. Length units such as `in`, `cm` and `mm` convert to and
+//! from typography units through the DPI too, so `1 in to px` is 96 px.
+//!
+//! Settings can be given in the input as `@key=value` tokens, e.g. `24px to rem @base=18`:
+//!
+//! - `@base` (or `@root`): root font size in px, default 16
+//! - `@vw`: viewport width in px, default 1920
+//! - `@vh`: viewport height in px, default 1080
+//! - `@dpi`: dots per inch for pt, pc and length units, default 96
+
+use super::types::{Unit, UnitCategory};
+
+/// Parameters that typography units are resolved with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypographySettings {
+    /// Root font size in px, used for rem and em
+    pub root_font_size: f64,
+    /// Viewport width in px, used for vw
+    pub viewport_width: f64,
+    /// Viewport height in px, used for vh
+    pub viewport_height: f64,
+    /// Pixels per inch, used for pt, pc and length units
+    pub dpi: f64,
+}
+
+impl Default for TypographySettings {
+    fn default() -> Self {
+        Self {
+            root_font_size: 16.0,
+            viewport_width: 1920.0,
+            viewport_height: 1080.0,
+            dpi: 96.0,
+        }
+    }
+}
+
+impl TypographySettings {
+    /// Check that every setting is a positive number
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("Root font size", self.root_font_size),
+            ("Viewport width", self.viewport_width),
+            ("Viewport height", self.viewport_height),
+            ("DPI", self.dpi),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(format!("{} must be a positive number, got {}", name, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remove `@key=value` tokens from the input, applying them on top of `settings`
+///
+/// Returns the input without the tokens. `24px to rem @base=18` becomes `24px to rem`
+/// with a root font size of 18.
+pub fn extract_settings(
+    input: &str,
+    settings: TypographySettings,
+) -> Result<(String, TypographySettings), String> {
+    let mut settings = settings;
+    let mut rest = Vec::new();
+
+    for token in input.split_whitespace() {
+        let Some(setting) = token.strip_prefix('@') else {
+            rest.push(token);
+            continue;
+        };
+        let (key, value) = setting
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid setting '{}'. Use @key=value, e.g. @base=18", token))?;
+        let value: f64 = value
+            .trim_end_matches("px")
+            .parse()
+            .map_err(|_| format!("Invalid value in setting '{}'", token))?;
+
+        match key.to_lowercase().as_str() {
+            "base" | "root" => settings.root_font_size = value,
+            "vw" => settings.viewport_width = value,
+            "vh" => settings.viewport_height = value,
+            "dpi" => settings.dpi = value,
+            _ => {
+                return Err(format!(
+                    "Unknown setting '@{}'. Use @base, @vw, @vh or @dpi",
+                    key
+                ));
+            }
+        }
+    }
+
+    settings.validate()?;
+    Ok((rest.join(" "), settings))
+}
+
+/// Meters per inch, for converting length units through the DPI
+const METERS_PER_INCH: f64 = 0.0254;
+
+/// Whether a conversion between the two categories goes through CSS pixels
+///
+/// True when either side is a typography unit and the other is a typography or length unit.
+pub fn converts_through_px(from: UnitCategory, to: UnitCategory) -> bool {
+    matches!(
+        (from, to),
+        (UnitCategory::Typography, UnitCategory::Typography)
+            | (UnitCategory::Typography, UnitCategory::Length)
+            | (UnitCategory::Length, UnitCategory::Typography)
+    )
+}
+
+/// Size of one unit in CSS pixels
+///
+/// Length units are physical, so they are mapped through the DPI: at 96 DPI, 1 in is 96 px.
+pub fn px_per_unit(unit: &Unit, settings: &TypographySettings) -> Result<f64, String> {
+    if unit.category == UnitCategory::Length {
+        let meters = unit.to_base_multiplier.ok_or_else(|| {
+            format!(
+                "Unit '{}' cannot be converted linearly",
+                unit.canonical_name
+            )
+        })?;
+        return Ok(meters / METERS_PER_INCH * settings.dpi);
+    }
+    if unit.category != UnitCategory::Typography {
+        return Err(format!(
+            "Unit '{}' is not a typography unit",
+            unit.canonical_name
+        ));
+    }
+
+    let px = match unit.canonical_name {
+        "rem" | "em" => settings.root_font_size,
+        "pt" => settings.dpi / 72.0,
+        "pc" => settings.dpi / 6.0,
+        "vw" => settings.viewport_width / 100.0,
+        "vh" => settings.viewport_height / 100.0,
+        // px, and custom units registered with a multiplier in px
+        _ => unit.to_base_multiplier.ok_or_else(|| {
+            format!(
+                "Unit '{}' cannot be converted linearly",
+                unit.canonical_name
+            )
+        })?,
+    };
+    Ok(px)
+}
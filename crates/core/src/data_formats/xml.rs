@@ -158,15 +158,17 @@ fn read_element(reader: &mut Reader<&[u8]>, tag_name: &str) -> Result<JsonValue,
                 children.entry(child_tag).or_default().push(child_value);
             }
             Ok(Event::Text(ref e)) => {
-                // Convert bytes to string and unescape XML entities
-                let text = String::from_utf8(e.as_ref().to_vec())
+                // Convert bytes to string and unescape XML entities. The bytes are
+                // copied through Deref: `as_ref()` has no single target type once
+                // the toml crate's AsRef impls are in the build
+                let text = String::from_utf8(e.to_vec())
                     .map_err(|_| "Failed to decode text".to_string())?;
                 let unescaped = unescape(&text);
                 text_content.push_str(&unescaped);
             }
             Ok(Event::GeneralRef(ref e)) => {
                 // Handle XML entity references (e.g., &amp; &lt; &gt; &quot; &apos;)
-                let entity_name = String::from_utf8(e.as_ref().to_vec())
+                let entity_name = String::from_utf8(e.to_vec())
                     .map_err(|_| "Failed to decode entity name".to_string())?;
                 if let Some(entity_char) = entity_to_char(&entity_name) {
                     text_content.push_str(entity_char);
@@ -0,0 +1,296 @@
+//! Readable timestamps inside text such as log lines.
+//!
+//! Finds Unix timestamps (seconds, milliseconds, microseconds or nanoseconds, told
+//! apart by magnitude) and ISO 8601 date-times, and appends or substitutes a rendering
+//! in a zone. Only integers that land between 1990 and 2100 count as timestamps, so
+//! ports, sizes and counters are left alone. Numbers that are part of a word, a
+//! decimal or a version (`v2`, `1.5`, `a1741075200`) are skipped.
+//!
+//! With `keys`, only values of those JSON keys are looked at, e.g. `"ts": 1741075200`
+//! or `"created_at": "2025-03-04T08:00:00Z"`. In either mode a number that is a JSON
+//! value becomes a string holding the rendering, so JSON lines stay valid.
+
+use chrono::{DateTime, Utc};
+
+use super::parse;
+use super::timestamp::{self, TimestampFormat, TimestampUnit};
+use super::timezone::Zone;
+
+/// 1990-01-01 and 2100-01-01 in Unix seconds
+const PLAUSIBLE_SECONDS: std::ops::Range<i64> = 631_152_000..4_102_444_800;
+
+/// What to do with a timestamp that was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnnotateMode {
+    /// Keep it and add the rendering after it in parentheses
+    #[default]
+    Append,
+    /// Put the rendering in its place
+    Replace,
+}
+
+/// How to render timestamps found in text, and which ones to look at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotateOptions {
+    pub format: TimestampFormat,
+    pub zone: Zone,
+    pub mode: AnnotateMode,
+    /// JSON keys whose values are annotated; empty for every timestamp in the text
+    pub keys: Vec<String>,
+}
+
+impl Default for AnnotateOptions {
+    fn default() -> Self {
+        AnnotateOptions {
+            format: TimestampFormat::Human,
+            zone: Zone::Local,
+            mode: AnnotateMode::Append,
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// Annotate every line of a text
+pub fn annotate(text: &str, options: &AnnotateOptions) -> String {
+    text.lines()
+        .map(|line| annotate_line(line, options))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Annotate the timestamps in one line
+pub fn annotate_line(line: &str, options: &AnnotateOptions) -> String {
+    match options.keys.is_empty() {
+        true => annotate_all(line, options),
+        false => annotate_keys(line, options),
+    }
+}
+
+fn annotate_all(line: &str, options: &AnnotateOptions) -> String {
+    let bytes = line.as_bytes();
+    let mut out = String::with_capacity(line.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() || (i > 0 && is_word_byte(bytes[i - 1])) {
+            i += 1;
+            continue;
+        }
+
+        let iso_len = iso_length(&bytes[i..]);
+        let digits_len = bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let (len, rendered) = match iso_len {
+            Some(len) => (len, render_iso(&line[i..i + len], options)),
+            None if is_boundary(&bytes[i + digits_len..]) => {
+                (digits_len, render_epoch(&line[i..i + digits_len], options))
+            }
+            None => (digits_len, None),
+        };
+
+        if let Some(rendered) = rendered {
+            out.push_str(&line[copied..i]);
+            // A bare number after `"key":` is quoted, as in key mode, to keep JSON valid
+            let quote = iso_len.is_none() && is_json_value(&line[..i]);
+            if quote {
+                out.push('"');
+            }
+            push_annotated(&mut out, &line[i..i + len], &rendered, options.mode);
+            if quote {
+                out.push('"');
+            }
+            copied = i + len;
+        }
+        i += len;
+    }
+
+    out.push_str(&line[copied..]);
+    out
+}
+
+fn annotate_keys(line: &str, options: &AnnotateOptions) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some((before, key, after_key)) = next_key(rest) {
+        out.push_str(before);
+        out.push_str(key);
+        rest = after_key;
+
+        let name = &key[1..key.len() - 1];
+        if !options.keys.iter().any(|wanted| wanted == name) {
+            continue;
+        }
+        // "key" : value
+        let Some(after_colon) = rest.trim_start().strip_prefix(':') else {
+            continue;
+        };
+        let value_start = after_colon.trim_start();
+        out.push_str(&rest[..rest.len() - value_start.len()]);
+        rest = value_start;
+
+        let (value, consumed) = match value_start.strip_prefix('"') {
+            Some(string) => match closing_quote(string) {
+                Some(end) => (&string[..end], end + 2),
+                None => continue,
+            },
+            None => {
+                let end = value_start
+                    .find(|c: char| {
+                        !c.is_ascii_digit() && !matches!(c, '-' | '+' | '.' | 'e' | 'E')
+                    })
+                    .unwrap_or(value_start.len());
+                (&value_start[..end], end)
+            }
+        };
+
+        let rendered = match value.bytes().all(|b| b.is_ascii_digit()) {
+            true => render_epoch(value, options),
+            false => render_iso(value, options),
+        };
+        match rendered {
+            // Quoted even when the value was a number, so the line stays valid JSON
+            Some(rendered) => {
+                out.push('"');
+                push_annotated(&mut out, value, &rendered, options.mode);
+                out.push('"');
+            }
+            None => out.push_str(&rest[..consumed]),
+        }
+        rest = &rest[consumed..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Whether text ending here puts the next value after a JSON key, as in `{"ts": `
+fn is_json_value(before: &str) -> bool {
+    before
+        .trim_end()
+        .strip_suffix(':')
+        .is_some_and(|key| key.trim_end().ends_with('"'))
+}
+
+/// The text before the next JSON string, the string with its quotes, and the rest
+fn next_key(text: &str) -> Option<(&str, &str, &str)> {
+    let start = text.find('"')?;
+    let end = start + 1 + closing_quote(&text[start + 1..])?;
+    Some((&text[..start], &text[start..=end], &text[end + 1..]))
+}
+
+/// Position of the quote that ends a JSON string, skipping escaped quotes
+fn closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn push_annotated(out: &mut String, original: &str, rendered: &str, mode: AnnotateMode) {
+    match mode {
+        AnnotateMode::Append => {
+            out.push_str(original);
+            out.push_str(" (");
+            out.push_str(rendered);
+            out.push(')');
+        }
+        AnnotateMode::Replace => out.push_str(rendered),
+    }
+}
+
+/// Render an integer that is a plausible Unix timestamp in any unit
+fn render_epoch(digits: &str, options: &AnnotateOptions) -> Option<String> {
+    let value: i64 = digits.parse().ok()?;
+    let unit = TimestampUnit::detect(value);
+    let seconds = match unit {
+        TimestampUnit::Seconds => value,
+        TimestampUnit::Millis => value / 1_000,
+        TimestampUnit::Micros => value / 1_000_000,
+        TimestampUnit::Nanos => value / 1_000_000_000,
+    };
+    if !PLAUSIBLE_SECONDS.contains(&seconds) {
+        return None;
+    }
+    timestamp::from_timestamp_unit_in(value, unit, options.format.clone(), options.zone).ok()
+}
+
+/// Render an ISO 8601 date-time; inputs without an offset are read in the zone
+fn render_iso(text: &str, options: &AnnotateOptions) -> Option<String> {
+    let instant: DateTime<Utc> = parse::parse_datetime(text, options.zone).ok()?;
+    let (value, unit) = match instant.timestamp_nanos_opt() {
+        Some(nanos) => (nanos, TimestampUnit::Nanos),
+        None => (instant.timestamp(), TimestampUnit::Seconds),
+    };
+    timestamp::from_timestamp_unit_in(value, unit, options.format.clone(), options.zone).ok()
+}
+
+/// Length of an ISO 8601 date-time at the start, like `2025-03-04T08:00:00.5+02:00`
+///
+/// A time of day is required, so plain dates and version numbers do not match.
+fn iso_length(bytes: &[u8]) -> Option<usize> {
+    let digits = |at: usize, count: usize| {
+        bytes.len() >= at + count && bytes[at..at + count].iter().all(u8::is_ascii_digit)
+    };
+    let byte = |at: usize| bytes.get(at).copied();
+
+    // 2025-03-04T08:00
+    if !(digits(0, 4)
+        && byte(4) == Some(b'-')
+        && digits(5, 2)
+        && byte(7) == Some(b'-')
+        && digits(8, 2)
+        && matches!(byte(10), Some(b'T' | b't' | b' '))
+        && digits(11, 2)
+        && byte(13) == Some(b':')
+        && digits(14, 2))
+    {
+        return None;
+    }
+    let mut len = 16;
+    if byte(len) == Some(b':') && digits(len + 1, 2) {
+        len += 3;
+        if matches!(byte(len), Some(b'.' | b',')) && digits(len + 1, 1) {
+            len += 1;
+            while byte(len).is_some_and(|b| b.is_ascii_digit()) {
+                len += 1;
+            }
+        }
+    }
+    match byte(len) {
+        Some(b'Z' | b'z') => len += 1,
+        Some(b'+' | b'-') if digits(len + 1, 2) => {
+            len += 3;
+            if byte(len) == Some(b':') && digits(len + 1, 2) {
+                len += 3;
+            } else if digits(len, 2) {
+                len += 2;
+            }
+        }
+        _ => {}
+    }
+
+    is_boundary(&bytes[len..]).then_some(len)
+}
+
+/// Whether a match may end before these bytes: not inside a word or a decimal
+///
+/// A `.` that does not start a fraction may follow, as at the end of a sentence.
+fn is_boundary(rest: &[u8]) -> bool {
+    match rest {
+        [] => true,
+        [b'.' | b',', next, ..] if next.is_ascii_digit() => false,
+        [next, ..] => !next.is_ascii_alphanumeric() && *next != b'_',
+    }
+}
+
+/// Bytes that may not come right before a match, so `v2`, `1.5` and `x_1` are skipped
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.'
+}
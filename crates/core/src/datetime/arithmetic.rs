@@ -0,0 +1,245 @@
+//! Date arithmetic: adding durations to instants and measuring between them.
+//!
+//! Years, months, weeks and days are calendar units. They move the local date in a
+//! zone and keep the wall-clock time, so Jan 31 + 1 month is the last day of February
+//! and a day across a daylight saving change is 23 or 25 hours. Hours and smaller
+//! units are exact. A duration like `1mo2d3h` applies its calendar part first.
+
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDateTime, TimeDelta, Utc};
+
+use crate::conversion::duration::{self, DurationPart};
+
+use super::parse;
+use super::timestamp::{self, TimestampUnit};
+use super::timezone::Zone;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// A duration with calendar parts, e.g. `1y 2mo 3d 4h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CalendarDuration {
+    /// Calendar months, twelve to a year
+    pub months: i64,
+    /// Calendar days, seven to a week
+    pub days: i64,
+    /// Exact milliseconds
+    pub millis: i64,
+}
+
+impl CalendarDuration {
+    /// Parse `90d`, `1h30m`, `2 weeks 3 days`, an ISO 8601 duration like `P1M2DT3H`
+    /// or a number of seconds like `3600`; a leading `-` negates the whole duration
+    ///
+    /// The grammar is [`duration::parse_calendar_duration`], shared with unit conversion.
+    pub fn parse(input: &str) -> Result<CalendarDuration, String> {
+        let text = input.trim();
+        let hint = "Use e.g. 90d, 1h30m, 2 weeks, P1M or 3600 (seconds)";
+        let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text).trim_start();
+
+        let parts =
+            if !unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                let seconds: f64 = unsigned
+                    .parse()
+                    .map_err(|_| format!("Invalid duration '{}'. {}", text, hint))?;
+                match text.starts_with('-') {
+                    true => vec![DurationPart::Seconds(-seconds)],
+                    false => vec![DurationPart::Seconds(seconds)],
+                }
+            } else {
+                duration::parse_calendar_duration(text).map_err(|e| format!("{}. {}", e, hint))?
+            };
+
+        let too_large = || format!("Duration '{}' is too large", text);
+        let whole = |value: f64| match value.abs() < i64::MAX as f64 {
+            true => Ok(value as i64),
+            false => Err(too_large()),
+        };
+        let mut duration = CalendarDuration::default();
+        for part in parts {
+            match part {
+                DurationPart::Months(months) => {
+                    duration.months = duration
+                        .months
+                        .checked_add(whole(months)?)
+                        .ok_or_else(too_large)?;
+                }
+                DurationPart::Days(days) => {
+                    duration.days = duration
+                        .days
+                        .checked_add(whole(days)?)
+                        .ok_or_else(too_large)?;
+                }
+                DurationPart::Seconds(seconds) => {
+                    let millis = (seconds * 1000.0).round();
+                    if !millis.is_finite() {
+                        return Err(too_large());
+                    }
+                    duration.millis = duration
+                        .millis
+                        .checked_add(whole(millis)?)
+                        .ok_or_else(too_large)?;
+                }
+            }
+        }
+        Ok(duration)
+    }
+
+    pub fn negate(self) -> CalendarDuration {
+        CalendarDuration {
+            months: -self.months,
+            days: -self.days,
+            millis: -self.millis,
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.months < 0 || self.days < 0 || self.millis < 0
+    }
+}
+
+/// An exact duration, with days of 24 hours
+impl From<TimeDelta> for CalendarDuration {
+    fn from(delta: TimeDelta) -> CalendarDuration {
+        let millis = delta.num_milliseconds();
+        CalendarDuration {
+            months: 0,
+            days: millis / MILLIS_PER_DAY,
+            millis: millis % MILLIS_PER_DAY,
+        }
+    }
+}
+
+impl fmt::Display for CalendarDuration {
+    /// Compound form like `1y 2mo 3d 4h 5m 6.5s`, or `0s`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+        let months = self.months.unsigned_abs();
+        let millis = self.millis.unsigned_abs();
+        let seconds = millis / 1000 % 60;
+        let fraction = millis % 1000;
+
+        let mut parts = Vec::new();
+        for (value, unit) in [
+            (months / 12, "y"),
+            (months % 12, "mo"),
+            (self.days.unsigned_abs(), "d"),
+            (millis / 3_600_000, "h"),
+            (millis / 60_000 % 60, "m"),
+        ] {
+            if value != 0 {
+                parts.push(format!("{}{}", value, unit));
+            }
+        }
+        if fraction != 0 {
+            let fraction = format!("{:03}", fraction);
+            parts.push(format!("{}.{}s", seconds, fraction.trim_end_matches('0')));
+        } else if seconds != 0 || parts.is_empty() {
+            parts.push(format!("{}s", seconds));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Parse an instant: `now`, a Unix timestamp (in milliseconds if `millis`) or a date string
+///
+/// `zone` is the timezone of date strings that have no offset.
+pub fn parse_instant(input: &str, millis: bool, zone: Zone) -> Result<DateTime<Utc>, String> {
+    let unit = match millis {
+        true => TimestampUnit::Millis,
+        false => TimestampUnit::Seconds,
+    };
+    parse_instant_in_unit(input, unit, zone)
+}
+
+/// Parse an instant like [`parse_instant`], reading Unix timestamps in `unit`
+pub fn parse_instant_in_unit(
+    input: &str,
+    unit: TimestampUnit,
+    zone: Zone,
+) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("now") {
+        return Ok(Utc::now());
+    }
+    if let Ok(value) = input.parse::<i64>() {
+        return timestamp::to_datetime(value, unit)
+            .ok_or_else(|| format!("Invalid timestamp '{}'", input));
+    }
+    parse::parse_datetime(input, zone)
+}
+
+/// Add a duration to an instant, moving calendar parts in `zone`
+pub fn add(
+    instant: &DateTime<Utc>,
+    duration: &CalendarDuration,
+    zone: Zone,
+) -> Result<DateTime<Utc>, String> {
+    let out_of_range = || "The result is out of range".to_string();
+
+    let shifted = if duration.months == 0 && duration.days == 0 {
+        *instant
+    } else {
+        let local = zone.to_fixed(instant).naive_local();
+        let local = add_months(local, duration.months).ok_or_else(out_of_range)?;
+        let local = add_days(local, duration.days).ok_or_else(out_of_range)?;
+        zone.resolve(&local)?
+    };
+    shifted
+        .checked_add_signed(TimeDelta::milliseconds(duration.millis))
+        .ok_or_else(out_of_range)
+}
+
+/// Subtract a duration from an instant, moving calendar parts in `zone`
+pub fn sub(
+    instant: &DateTime<Utc>,
+    duration: &CalendarDuration,
+    zone: Zone,
+) -> Result<DateTime<Utc>, String> {
+    add(instant, &duration.negate(), zone)
+}
+
+/// Exact time from `from` to `to`; negative if `to` is earlier
+pub fn diff(from: &DateTime<Utc>, to: &DateTime<Utc>) -> TimeDelta {
+    *to - *from
+}
+
+/// Time from `from` to `to` in whole calendar months and days of `zone`, then the rest
+///
+/// Adding the result to `from` gives `to` back.
+pub fn calendar_diff(from: &DateTime<Utc>, to: &DateTime<Utc>, zone: Zone) -> CalendarDuration {
+    if to < from {
+        return calendar_diff(to, from, zone).negate();
+    }
+    let start = zone.to_fixed(from).naive_local();
+    let end = zone.to_fixed(to).naive_local();
+
+    let mut months =
+        (end.year() as i64 - start.year() as i64) * 12 + end.month() as i64 - start.month() as i64;
+    while months > 0 && add_months(start, months).is_none_or(|date| date > end) {
+        months -= 1;
+    }
+    let after_months = add_months(start, months).unwrap_or(start);
+    let rest = CalendarDuration::from(end - after_months);
+
+    CalendarDuration { months, ..rest }
+}
+
+fn add_months(local: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let count = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    match months < 0 {
+        true => local.checked_sub_months(count),
+        false => local.checked_add_months(count),
+    }
+}
+
+fn add_days(local: NaiveDateTime, days: i64) -> Option<NaiveDateTime> {
+    let count = Days::new(days.unsigned_abs());
+    match days < 0 {
+        true => local.checked_sub_days(count),
+        false => local.checked_add_days(count),
+    }
+}
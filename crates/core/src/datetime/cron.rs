@@ -0,0 +1,805 @@
+//! Cron expressions: parsing, plain-English explanations and upcoming run times.
+//!
+//! Expressions have 5 fields (minute hour day-of-month month day-of-week), 6 with a
+//! leading seconds field, or 7 with a trailing year as in Quartz. Macros like `@daily`
+//! stand for 5-field expressions. Fields take `*`, values, `a-b` ranges, `/` steps and
+//! `,` lists; months and weekdays also take names (`JAN`, `MON`). Quartz additions:
+//! `?` for no specific value, `L` (last day, `L-3`, `5L` last Friday), `W` (nearest
+//! weekday, `LW` last weekday) and `#` (`FRI#3`, the third Friday).
+//!
+//! Weekdays are numbered 0-7 with Sunday as 0 and 7, except in Quartz expressions
+//! (7 fields, or any `?`), where Sunday is 1 and Saturday 7. When both the day of the
+//! month and the day of the week are restricted, a day matching either one runs, as
+//! in Vixie cron.
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+
+use super::timezone::Zone;
+
+/// How many years ahead to look for runs; leap days on a weekday repeat within 28
+const SEARCH_YEARS: i32 = 30;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const ORDINALS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+const MACROS: &[(&[&str], &str)] = &[
+    (&["@yearly", "@annually"], "0 0 1 1 *"),
+    (&["@monthly"], "0 0 1 * *"),
+    (&["@weekly"], "0 0 * * 0"),
+    (&["@daily", "@midnight"], "0 0 * * *"),
+    (&["@hourly"], "0 * * * *"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Second,
+    Minute,
+    Hour,
+    DayOfMonth,
+    Month,
+    DayOfWeek,
+    Year,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Second => "second",
+            Kind::Minute => "minute",
+            Kind::Hour => "hour",
+            Kind::DayOfMonth => "day-of-month",
+            Kind::Month => "month",
+            Kind::DayOfWeek => "day-of-week",
+            Kind::Year => "year",
+        }
+    }
+
+    /// Smallest and largest value as written
+    fn range(&self, quartz: bool) -> (u32, u32) {
+        match self {
+            Kind::Second | Kind::Minute => (0, 59),
+            Kind::Hour => (0, 23),
+            Kind::DayOfMonth => (1, 31),
+            Kind::Month => (1, 12),
+            Kind::DayOfWeek if quartz => (1, 7),
+            Kind::DayOfWeek => (0, 7),
+            Kind::Year => (1970, 2099),
+        }
+    }
+}
+
+/// One comma-separated part of a field, with values as written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Item {
+    /// `*`, `?` or `*/n`
+    Every(u32),
+    Value(u32),
+    /// `a-b` or `a-b/n`
+    Range(u32, u32, u32),
+    /// `a/n`
+    From(u32, u32),
+    /// `L` or `L-n` in the day of the month
+    LastDay(u32),
+    /// `LW`
+    LastWeekday,
+    /// `15W`
+    NearestWeekday(u32),
+    /// `5L`, by weekday from Sunday as 0
+    LastOf(u32),
+    /// `FRI#3`, by weekday from Sunday as 0
+    Nth(u32, u32),
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    items: Vec<Item>,
+    /// Matching values, by value; weekdays from Sunday as 0
+    allowed: Vec<bool>,
+}
+
+impl Field {
+    /// `*` or `?`, matching everything
+    fn is_any(&self) -> bool {
+        self.items == [Item::Every(1)]
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        self.allowed.get(value as usize).copied().unwrap_or(false)
+    }
+
+    fn values(&self) -> Vec<u32> {
+        (0..self.allowed.len() as u32)
+            .filter(|v| self.contains(*v))
+            .collect()
+    }
+
+    fn single_value(&self) -> Option<u32> {
+        match self.items.as_slice() {
+            [Item::Value(v)] => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed cron expression
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    expression: String,
+    quartz: bool,
+    seconds: Option<Field>,
+    minutes: Field,
+    hours: Field,
+    days: Field,
+    months: Field,
+    weekdays: Field,
+    years: Option<Field>,
+}
+
+impl CronSchedule {
+    /// Parse a 5-, 6- or 7-field expression or a macro like `@daily`
+    pub fn parse(expression: &str) -> Result<CronSchedule, String> {
+        let expression = expression.trim();
+        let expanded = match expression.strip_prefix('@') {
+            Some(_) => MACROS
+                .iter()
+                .find(|(names, _)| names.contains(&expression.to_lowercase().as_str()))
+                .map(|(_, fields)| *fields)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown cron macro '{}'. Use @yearly, @annually, @monthly, @weekly, @daily, @midnight or @hourly",
+                        expression
+                    )
+                })?,
+            None => expression,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let kinds: &[Kind] = match fields.len() {
+            5 => &[
+                Kind::Minute,
+                Kind::Hour,
+                Kind::DayOfMonth,
+                Kind::Month,
+                Kind::DayOfWeek,
+            ],
+            6 => &[
+                Kind::Second,
+                Kind::Minute,
+                Kind::Hour,
+                Kind::DayOfMonth,
+                Kind::Month,
+                Kind::DayOfWeek,
+            ],
+            7 => &[
+                Kind::Second,
+                Kind::Minute,
+                Kind::Hour,
+                Kind::DayOfMonth,
+                Kind::Month,
+                Kind::DayOfWeek,
+                Kind::Year,
+            ],
+            count => {
+                return Err(format!(
+                    "Invalid cron expression '{}': expected 5 fields (minute hour day-of-month month day-of-week), 6 with seconds or 7 with a year, found {}",
+                    expression, count
+                ));
+            }
+        };
+        let quartz = fields.len() == 7 || expanded.contains('?');
+
+        let mut parsed = Vec::with_capacity(fields.len());
+        for (position, (text, kind)) in fields.iter().zip(kinds).enumerate() {
+            let field = parse_field(text, *kind, quartz).map_err(|reason| {
+                format!(
+                    "Invalid {} field '{}' (field {} of {}): {}",
+                    kind.name(),
+                    text,
+                    position + 1,
+                    fields.len(),
+                    reason
+                )
+            })?;
+            parsed.push(field);
+        }
+
+        let mut parsed = parsed.into_iter();
+        let seconds = (fields.len() > 5).then(|| parsed.next()).flatten();
+        let mut next = || parsed.next().expect("one field per kind");
+        Ok(CronSchedule {
+            expression: expression.to_string(),
+            quartz,
+            seconds,
+            minutes: next(),
+            hours: next(),
+            days: next(),
+            months: next(),
+            weekdays: next(),
+            years: parsed.next(),
+        })
+    }
+
+    /// The expression as given
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Whether weekdays are numbered Quartz style, Sunday as 1
+    pub fn is_quartz(&self) -> bool {
+        self.quartz
+    }
+
+    /// Explain the schedule in English, e.g. `At 09:00, on Monday through Friday`
+    pub fn describe(&self) -> String {
+        let mut parts = vec![self.describe_time()];
+
+        let days = self.describe_days();
+        let weekdays = self.describe_weekdays();
+        match (days, weekdays) {
+            (Some(days), Some(weekdays)) => parts.push(format!("{} or {}", days, weekdays)),
+            (Some(days), None) => parts.push(days),
+            (None, Some(weekdays)) => parts.push(weekdays),
+            (None, None) => {}
+        }
+        if !self.months.is_any() {
+            parts.push(describe_items(
+                &self.months,
+                ("in", "in", ""),
+                ("month", "months"),
+                |v| MONTHS[v as usize - 1].to_string(),
+            ));
+        }
+        if let Some(years) = self.years.as_ref().filter(|years| !years.is_any()) {
+            parts.push(describe_items(
+                years,
+                ("in", "in", ""),
+                ("year", "years"),
+                |v| v.to_string(),
+            ));
+        }
+
+        let description = parts.join(", ");
+        let mut chars = description.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => description,
+        }
+    }
+
+    /// The next `count` runs after an instant, with times of day read in `zone`
+    ///
+    /// Local times skipped by a daylight saving change do not run and repeated ones
+    /// run once. Looks up to 30 years ahead, so a schedule that never runs (Feb 30)
+    /// returns fewer runs.
+    pub fn next_runs(&self, after: &DateTime<Utc>, count: usize, zone: Zone) -> Vec<DateTime<Utc>> {
+        let mut runs = Vec::with_capacity(count);
+        if count == 0 {
+            return runs;
+        }
+
+        let start = zone.to_fixed(after).naive_local();
+        let seconds = self.seconds.as_ref().map_or(vec![0], Field::values);
+        let minutes = self.minutes.values();
+        let hours = self.hours.values();
+
+        for year in start.year()..=start.year() + SEARCH_YEARS {
+            if self
+                .years
+                .as_ref()
+                .is_some_and(|years| !years.contains(year as u32))
+            {
+                continue;
+            }
+            for month in self.months.values() {
+                let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+                    continue;
+                };
+                let last = last_day_of_month(first);
+                if last < start.date() {
+                    continue;
+                }
+
+                for date in first.iter_days().take_while(|date| *date <= last) {
+                    if date < start.date() || !self.day_matches(date) {
+                        continue;
+                    }
+                    for &hour in &hours {
+                        for &minute in &minutes {
+                            for &second in &seconds {
+                                let Some(local) = date.and_hms_opt(hour, minute, second) else {
+                                    continue;
+                                };
+                                if local < start {
+                                    continue;
+                                }
+                                if let Ok(instant) = zone.resolve(&local)
+                                    && instant > *after
+                                    && runs.last().is_none_or(|last| instant > *last)
+                                {
+                                    runs.push(instant);
+                                    if runs.len() == count {
+                                        return runs;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        runs
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = self.days.contains(date.day())
+            || self
+                .days
+                .items
+                .iter()
+                .any(|item| special_day_matches(*item, date));
+        let weekday = self
+            .weekdays
+            .contains(date.weekday().num_days_from_sunday())
+            || self
+                .weekdays
+                .items
+                .iter()
+                .any(|item| special_day_matches(*item, date));
+
+        match (self.days.is_any(), self.weekdays.is_any()) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+
+    fn describe_time(&self) -> String {
+        let second = match &self.seconds {
+            Some(seconds) => seconds.single_value(),
+            None => Some(0),
+        };
+        let hours_are_values = self
+            .hours
+            .items
+            .iter()
+            .all(|item| matches!(item, Item::Value(_)));
+
+        // Fixed times of day: at 09:00 and 17:00
+        if let (Some(second), Some(minute), true) =
+            (second, self.minutes.single_value(), hours_are_values)
+        {
+            let times: Vec<String> = self
+                .hours
+                .items
+                .iter()
+                .map(|item| match item {
+                    Item::Value(hour) if second == 0 => format!("{:02}:{:02}", hour, minute),
+                    Item::Value(hour) => format!("{:02}:{:02}:{:02}", hour, minute, second),
+                    _ => unreachable!("hours are values"),
+                })
+                .collect();
+            return format!("at {}", join_and(&times));
+        }
+
+        let mut parts = Vec::new();
+        if let Some(seconds) = &self.seconds
+            && seconds.single_value() != Some(0)
+        {
+            parts.push(match seconds.single_value() {
+                Some(second) => format!("at {} seconds past the minute", second),
+                None => describe_items(
+                    seconds,
+                    ("at second", "at seconds", ""),
+                    ("second", "seconds"),
+                    |v| v.to_string(),
+                ),
+            });
+        }
+        match self.minutes.single_value() {
+            // On the hour: every hour, or just the hours phrase
+            Some(0) if parts.is_empty() && self.hours.is_any() => {
+                parts.push("every hour".to_string())
+            }
+            Some(0) if parts.is_empty() => {}
+            Some(minute) => parts.push(format!("at {} minutes past the hour", minute)),
+            None if self.minutes.is_any() && !parts.is_empty() => {}
+            None => parts.push(describe_items(
+                &self.minutes,
+                ("at minute", "at minutes", ""),
+                ("minute", "minutes"),
+                |v| v.to_string(),
+            )),
+        }
+        if !self.hours.is_any() {
+            parts.push(match self.hours.items.as_slice() {
+                [Item::Range(from, to, 1)] => {
+                    format!("between {:02}:00 and {:02}:59", from, to)
+                }
+                _ => describe_items(
+                    &self.hours,
+                    ("during hour", "during hours", ""),
+                    ("hour", "hours"),
+                    |v| format!("{:02}", v),
+                ),
+            });
+        }
+        parts.join(", ")
+    }
+
+    fn describe_days(&self) -> Option<String> {
+        if self.days.is_any() {
+            return None;
+        }
+        let mut phrases = Vec::new();
+        if self.days.items.iter().any(|item| is_plain(*item)) {
+            phrases.push(describe_items(
+                &self.days,
+                ("on day", "on days", " of the month"),
+                ("day", "days"),
+                |v| v.to_string(),
+            ));
+        }
+        for item in &self.days.items {
+            match item {
+                Item::LastDay(0) => phrases.push("on the last day of the month".to_string()),
+                Item::LastDay(1) => {
+                    phrases.push("1 day before the last day of the month".to_string())
+                }
+                Item::LastDay(offset) => {
+                    phrases.push(format!("{} days before the last day of the month", offset))
+                }
+                Item::LastWeekday => phrases.push("on the last weekday of the month".to_string()),
+                Item::NearestWeekday(day) => {
+                    phrases.push(format!("on the weekday nearest day {} of the month", day))
+                }
+                _ => {}
+            }
+        }
+        Some(join_and(&phrases))
+    }
+
+    fn describe_weekdays(&self) -> Option<String> {
+        if self.weekdays.is_any() {
+            return None;
+        }
+        let quartz = self.quartz;
+        let mut phrases = Vec::new();
+        let plain = self.weekdays.items.iter().any(|item| is_plain(*item));
+        if plain && (0..7).all(|weekday| self.weekdays.contains(weekday)) {
+            phrases.push("every day".to_string());
+        } else if plain {
+            phrases.push(describe_items(
+                &self.weekdays,
+                ("on", "on", ""),
+                ("day of the week", "days of the week"),
+                |v| WEEKDAYS[weekday_index(v, quartz) as usize].to_string(),
+            ));
+        }
+        for item in &self.weekdays.items {
+            match item {
+                Item::LastOf(weekday) => phrases.push(format!(
+                    "on the last {} of the month",
+                    WEEKDAYS[*weekday as usize]
+                )),
+                Item::Nth(weekday, n) => phrases.push(format!(
+                    "on the {} {} of the month",
+                    ORDINALS[*n as usize - 1],
+                    WEEKDAYS[*weekday as usize]
+                )),
+                _ => {}
+            }
+        }
+        Some(join_and(&phrases))
+    }
+}
+
+fn parse_field(text: &str, kind: Kind, quartz: bool) -> Result<Field, String> {
+    let (min, max) = kind.range(quartz);
+    let items = text
+        .split(',')
+        .map(|item| parse_item(&item.to_uppercase(), kind, quartz))
+        .map(|item| item.map(|item| normalize_sunday(item, kind, quartz)))
+        .collect::<Result<Vec<Item>, String>>()?;
+
+    let mut allowed = vec![false; max as usize + 1];
+    let mut allow = |value: u32| {
+        let index = match kind {
+            Kind::DayOfWeek => weekday_index(value, quartz),
+            _ => value,
+        };
+        allowed[index as usize] = true;
+    };
+    for item in &items {
+        match *item {
+            // Weekdays repeat at both ends of 0-7, so stop at Saturday
+            Item::Every(step) if kind == Kind::DayOfWeek && !quartz => {
+                (0..=6).step_by(step as usize).for_each(&mut allow)
+            }
+            Item::Every(step) => (min..=max).step_by(step as usize).for_each(&mut allow),
+            Item::Value(value) => allow(value),
+            Item::Range(from, to, step) => (from..=to).step_by(step as usize).for_each(&mut allow),
+            Item::From(from, step) => (from..=max).step_by(step as usize).for_each(&mut allow),
+            _ => {}
+        }
+    }
+
+    Ok(Field { items, allowed })
+}
+
+fn parse_item(text: &str, kind: Kind, quartz: bool) -> Result<Item, String> {
+    if text.is_empty() {
+        return Err("empty value".to_string());
+    }
+    if text == "?" {
+        return match matches!(kind, Kind::DayOfMonth | Kind::DayOfWeek) {
+            true => Ok(Item::Every(1)),
+            false => {
+                Err("'?' is only allowed in the day-of-month and day-of-week fields".to_string())
+            }
+        };
+    }
+    if kind == Kind::DayOfMonth {
+        if text == "L" {
+            return Ok(Item::LastDay(0));
+        }
+        if text == "LW" || text == "WL" {
+            return Ok(Item::LastWeekday);
+        }
+        if let Some(offset) = text.strip_prefix("L-") {
+            let offset = parse_number(offset)?;
+            if offset > 30 {
+                return Err(format!("L-{} is out of range L-0 to L-30", offset));
+            }
+            return Ok(Item::LastDay(offset));
+        }
+        if let Some(day) = text.strip_suffix('W') {
+            return Ok(Item::NearestWeekday(parse_value(day, kind, quartz)?));
+        }
+    }
+    if kind == Kind::DayOfWeek {
+        if text == "L" {
+            return Ok(Item::Value(if quartz { 7 } else { 6 }));
+        }
+        if let Some(weekday) = text.strip_suffix('L') {
+            let weekday = parse_value(weekday, kind, quartz)?;
+            return Ok(Item::LastOf(weekday_index(weekday, quartz)));
+        }
+        if let Some((weekday, n)) = text.split_once('#') {
+            let weekday = parse_value(weekday, kind, quartz)?;
+            let n = parse_number(n)?;
+            if !(1..=5).contains(&n) {
+                return Err(format!("#{} needs a week of the month from 1 to 5", n));
+            }
+            return Ok(Item::Nth(weekday_index(weekday, quartz), n));
+        }
+    }
+    let (range, step) = match text.split_once('/') {
+        Some((range, step)) => {
+            let step = parse_number(step)?;
+            if step == 0 {
+                return Err("step must be at least 1".to_string());
+            }
+            (range, Some(step))
+        }
+        None => (text, None),
+    };
+
+    if range == "*" {
+        return Ok(Item::Every(step.unwrap_or(1)));
+    }
+    if let Some((from, to)) = range.split_once('-') {
+        let from = parse_value(from, kind, quartz)?;
+        let to = parse_value(to, kind, quartz)?;
+        if from > to {
+            return Err(format!("range {}-{} goes backwards", from, to));
+        }
+        return Ok(Item::Range(from, to, step.unwrap_or(1)));
+    }
+
+    let value = parse_value(range, kind, quartz)?;
+    Ok(match step {
+        Some(step) => Item::From(value, step),
+        None => Item::Value(value),
+    })
+}
+
+/// A number or, for months and weekdays, a name, checked against the field's range
+fn parse_value(text: &str, kind: Kind, quartz: bool) -> Result<u32, String> {
+    let (min, max) = kind.range(quartz);
+    let value = match parse_name(text, kind) {
+        Some(index) => match kind {
+            Kind::Month => index + 1,
+            _ if quartz => index + 1,
+            _ => index,
+        },
+        None if text.chars().all(|c| c.is_ascii_digit()) && !text.is_empty() => parse_number(text)?,
+        None if text.contains(['L', 'W', '#'])
+            && !matches!(kind, Kind::DayOfMonth | Kind::DayOfWeek) =>
+        {
+            return Err(format!(
+                "'{}' uses L, W or #, which are only allowed in the day fields",
+                text
+            ));
+        }
+        None => {
+            let hint = match kind {
+                Kind::Month => format!(", use {}-{} or JAN-DEC", min, max),
+                Kind::DayOfWeek => format!(", use {}-{} or SUN-SAT", min, max),
+                _ => String::new(),
+            };
+            return Err(format!("unknown value '{}'{}", text, hint));
+        }
+    };
+
+    if !(min..=max).contains(&value) {
+        return Err(format!("{} is out of range {}-{}", value, min, max));
+    }
+    Ok(value)
+}
+
+fn parse_name(text: &str, kind: Kind) -> Option<u32> {
+    let names: &[&str] = match kind {
+        Kind::Month => &MONTH_NAMES,
+        Kind::DayOfWeek => &WEEKDAY_NAMES,
+        _ => return None,
+    };
+    names
+        .iter()
+        .position(|name| *name == text)
+        .map(|index| index as u32)
+}
+
+fn parse_number(text: &str) -> Result<u32, String> {
+    text.parse()
+        .map_err(|_| format!("'{}' is not a number", text))
+}
+
+/// Write Sunday as 0 rather than 7, so `7` reads as `0` and `0-7` as `0-6`
+fn normalize_sunday(item: Item, kind: Kind, quartz: bool) -> Item {
+    if kind != Kind::DayOfWeek || quartz {
+        return item;
+    }
+    match item {
+        Item::Value(7) => Item::Value(0),
+        Item::Range(0, 7, step) => Item::Range(0, 6, step),
+        item => item,
+    }
+}
+
+/// Weekday as written to a weekday from Sunday as 0
+fn weekday_index(value: u32, quartz: bool) -> u32 {
+    match quartz {
+        true => value - 1,
+        false => value % 7,
+    }
+}
+
+fn is_plain(item: Item) -> bool {
+    matches!(
+        item,
+        Item::Every(_) | Item::Value(_) | Item::Range(..) | Item::From(..)
+    )
+}
+
+fn special_day_matches(item: Item, date: NaiveDate) -> bool {
+    let last = last_day_of_month(date).day();
+    let weekday = date.weekday().num_days_from_sunday();
+    match item {
+        Item::LastDay(offset) => last.checked_sub(offset) == Some(date.day()),
+        Item::LastWeekday => {
+            let mut day = last_day_of_month(date);
+            while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                day = day.pred_opt().unwrap_or(day);
+            }
+            day == date
+        }
+        Item::NearestWeekday(target) => {
+            let Some(target) = date.with_day(target) else {
+                return false;
+            };
+            // Move off weekends without leaving the month
+            let nearest = match target.weekday() {
+                Weekday::Sat if target.day() == 1 => target + chrono::Days::new(2),
+                Weekday::Sat => target - chrono::Days::new(1),
+                Weekday::Sun if target.day() == last => target - chrono::Days::new(2),
+                Weekday::Sun => target + chrono::Days::new(1),
+                _ => target,
+            };
+            nearest == date
+        }
+        Item::LastOf(day) => weekday == day && date.day() + 7 > last,
+        Item::Nth(day, n) => weekday == day && (date.day() - 1) / 7 + 1 == n,
+        _ => false,
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+/// Describe the plain items of a field, e.g. `on days 1 and 15 of the month` or
+/// `every 15 minutes`
+///
+/// `labels` is the lead-in for one value, for several, and the words after them.
+fn describe_items(
+    field: &Field,
+    labels: (&str, &str, &str),
+    units: (&str, &str),
+    name: impl Fn(u32) -> String,
+) -> String {
+    let (one, many, after) = labels;
+    let (unit, units) = units;
+    let mut values = Vec::new();
+    let mut steps = Vec::new();
+    for item in &field.items {
+        match *item {
+            Item::Every(1) => steps.push(format!("every {}", unit)),
+            Item::Every(step) => steps.push(format!("every {} {}", step, units)),
+            Item::Value(value) if !values.contains(&name(value)) => values.push(name(value)),
+            Item::Range(from, to, 1) => values.push(format!("{} through {}", name(from), name(to))),
+            Item::Range(from, to, step) => steps.push(format!(
+                "every {} {} from {} through {}",
+                step,
+                units,
+                name(from),
+                name(to)
+            )),
+            Item::From(from, step) => steps.push(format!(
+                "every {} {} starting at {}",
+                step,
+                units,
+                name(from)
+            )),
+            _ => {}
+        }
+    }
+
+    if !values.is_empty() {
+        let single = values.len() == 1 && !values[0].contains(" through ");
+        let lead = if single { one } else { many };
+        steps.insert(0, format!("{} {}{}", lead, join_and(&values), after));
+    }
+    join_and(&steps)
+}
+
+fn join_and(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
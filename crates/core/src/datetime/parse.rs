@@ -0,0 +1,138 @@
+//! Lenient parsing of date strings into instants, the reverse of formatting a timestamp.
+//!
+//! Inputs are tried against, in order: RFC 3339, RFC 2822 (which covers HTTP dates),
+//! common log formats with an offset, then dates and times without one. Inputs without
+//! an offset are read in the given zone; a trailing `Z`, `UTC` or `GMT` reads them in UTC.
+
+use chrono::format::ParseErrorKind;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use super::timezone::Zone;
+
+/// Formats with an offset, e.g. Apache logs `04/Mar/2025:10:00:00 +0000`
+const OFFSET_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%dT%H:%M%z",
+    "%Y-%m-%d %H:%M%z",
+    "%Y-%m-%d %H:%M %z",
+    "%d/%b/%Y:%H:%M:%S %z",
+];
+
+/// Dates and times without an offset
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    // Python logging and log4j: 2025-03-04 10:00:00,123
+    "%Y-%m-%d %H:%M:%S,%3f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y/%m/%d %H:%M",
+    "%d/%b/%Y:%H:%M:%S",
+    // asctime, e.g. Tue Mar  4 10:00:00 2025
+    "%a %b %e %H:%M:%S %Y",
+    // RFC 850, an obsolete HTTP date: Tuesday, 04-Mar-25 10:00:00
+    "%A, %d-%b-%y %H:%M:%S",
+];
+
+/// Dates without a time, read as midnight
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%d %b %Y",
+    "%d %B %Y",
+    "%b %d %Y",
+    "%B %d, %Y",
+    "%b %d, %Y",
+];
+
+/// Parse a date string in any of the supported formats
+///
+/// `zone` is the timezone of inputs that have no offset.
+pub fn parse_datetime(input: &str, zone: Zone) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    // Log lines often wrap the date in brackets: [04/Mar/2025:10:00:00 +0000]
+    let input = input
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(input)
+        .trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.to_utc());
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(input) {
+        return Ok(dt.to_utc());
+    }
+    if let Some(dt) = OFFSET_FORMATS
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(input, format).ok())
+    {
+        return Ok(dt.to_utc());
+    }
+
+    let (naive, zone) = match strip_utc_suffix(input) {
+        Some(rest) => (rest, Zone::Utc),
+        None => (input, zone),
+    };
+    if let Some(dt) = NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+    {
+        return zone.resolve(&dt);
+    }
+    if let Some(date) = DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(naive, format).ok())
+    {
+        return zone.resolve(&date.and_time(Default::default()));
+    }
+
+    Err(format!(
+        "Unrecognized date '{}'. Use RFC 3339 (2025-03-04T10:00:00+02:00), RFC 2822, an HTTP date, YYYY-MM-DD [HH:MM[:SS]] or --format",
+        input
+    ))
+}
+
+/// Parse a date string with a strftime-style pattern, e.g. `%d.%m.%Y %H:%M`
+///
+/// The pattern may leave out the offset, read in `zone`, or the time, read as midnight.
+pub fn parse_datetime_with_format(
+    input: &str,
+    pattern: &str,
+    zone: Zone,
+) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    let error = |e: chrono::ParseError| {
+        format!(
+            "Could not parse '{}' with format '{}': {}",
+            input, pattern, e
+        )
+    };
+
+    // Each attempt fails with NotEnough when the pattern lacks what it needs,
+    // so fall through to the next one only then
+    match DateTime::parse_from_str(input, pattern) {
+        Ok(dt) => return Ok(dt.to_utc()),
+        Err(e) if e.kind() != ParseErrorKind::NotEnough => return Err(error(e)),
+        Err(_) => {}
+    }
+    match NaiveDateTime::parse_from_str(input, pattern) {
+        Ok(dt) => return zone.resolve(&dt),
+        Err(e) if e.kind() != ParseErrorKind::NotEnough => return Err(error(e)),
+        Err(_) => {}
+    }
+    let date = NaiveDate::parse_from_str(input, pattern).map_err(error)?;
+    zone.resolve(&date.and_time(Default::default()))
+}
+
+/// The input without a trailing `Z`, `UTC` or `GMT`, if it has one
+fn strip_utc_suffix(input: &str) -> Option<&str> {
+    let upper = input.to_ascii_uppercase();
+    [" UTC", " GMT", "Z"]
+        .iter()
+        .find(|suffix| upper.ends_with(*suffix))
+        .map(|suffix| input[..input.len() - suffix.len()].trim_end())
+}
@@ -0,0 +1,125 @@
+//! Relative times like `3 hours ago` or `in 2 days`.
+//!
+//! The distance is measured with [`arithmetic::calendar_diff`], so months and years
+//! follow the calendar of a zone. Smaller units are dropped rather than rounded: an
+//! instant 1 day and 23 hours away is `1 day` away.
+
+use chrono::{DateTime, Utc};
+
+use super::arithmetic;
+use super::timezone::Zone;
+
+/// Units of a relative time, from the largest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelativeUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl RelativeUnit {
+    /// Parse a unit name or abbreviation like `h`, `hour` or `hours`
+    pub fn parse(input: &str) -> Result<RelativeUnit, String> {
+        match input.trim().to_lowercase().as_str() {
+            "y" | "year" | "years" => Ok(RelativeUnit::Year),
+            "mo" | "month" | "months" => Ok(RelativeUnit::Month),
+            "w" | "week" | "weeks" => Ok(RelativeUnit::Week),
+            "d" | "day" | "days" => Ok(RelativeUnit::Day),
+            "h" | "hour" | "hours" => Ok(RelativeUnit::Hour),
+            "m" | "min" | "minute" | "minutes" => Ok(RelativeUnit::Minute),
+            "s" | "sec" | "second" | "seconds" => Ok(RelativeUnit::Second),
+            _ => Err(format!(
+                "Unknown unit '{}'. Use year, month, week, day, hour, minute or second",
+                input
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RelativeUnit::Year => "year",
+            RelativeUnit::Month => "month",
+            RelativeUnit::Week => "week",
+            RelativeUnit::Day => "day",
+            RelativeUnit::Hour => "hour",
+            RelativeUnit::Minute => "minute",
+            RelativeUnit::Second => "second",
+        }
+    }
+}
+
+/// How much detail a relative time shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelativeOptions {
+    /// Most units to show, e.g. 2 for `1 day, 3 hours ago`
+    pub parts: usize,
+    /// Smallest unit to show
+    pub granularity: RelativeUnit,
+}
+
+impl Default for RelativeOptions {
+    fn default() -> Self {
+        RelativeOptions {
+            parts: 1,
+            granularity: RelativeUnit::Second,
+        }
+    }
+}
+
+/// Describe `instant` relative to `since`, e.g. `3 hours ago` or `in 2 days`
+///
+/// Calendar units are counted in `zone`. Units that are zero are skipped without
+/// using up a part, so 2 parts of 1 day and 5 minutes are `1 day, 5 minutes`.
+/// Distances below the granularity are `just now` for seconds and e.g.
+/// `less than a day ago` otherwise.
+pub fn relative(
+    instant: &DateTime<Utc>,
+    since: &DateTime<Utc>,
+    zone: Zone,
+    options: &RelativeOptions,
+) -> String {
+    let future = instant > since;
+    let distance = match future {
+        true => arithmetic::calendar_diff(since, instant, zone),
+        false => arithmetic::calendar_diff(instant, since, zone),
+    };
+
+    let units = [
+        (distance.months / 12, RelativeUnit::Year),
+        (distance.months % 12, RelativeUnit::Month),
+        (distance.days / 7, RelativeUnit::Week),
+        (distance.days % 7, RelativeUnit::Day),
+        (distance.millis / 3_600_000, RelativeUnit::Hour),
+        (distance.millis / 60_000 % 60, RelativeUnit::Minute),
+        (distance.millis / 1000 % 60, RelativeUnit::Second),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .filter(|(value, unit)| *value != 0 && *unit <= options.granularity)
+        .take(options.parts.max(1))
+        .map(|(value, unit)| match value {
+            1 => format!("1 {}", unit.name()),
+            _ => format!("{} {}s", value, unit.name()),
+        })
+        .collect();
+
+    let amount = match (parts.is_empty(), options.granularity) {
+        (true, RelativeUnit::Second) => return "just now".to_string(),
+        (true, RelativeUnit::Hour) => "less than an hour".to_string(),
+        (true, unit) => format!("less than a {}", unit.name()),
+        (false, _) => parts.join(", "),
+    };
+    match future {
+        true => format!("in {}", amount),
+        false => format!("{} ago", amount),
+    }
+}
+
+/// Describe `instant` relative to now
+pub fn relative_to_now(instant: &DateTime<Utc>, zone: Zone, options: &RelativeOptions) -> String {
+    relative(instant, &Utc::now(), zone, options)
+}
@@ -1,10 +1,113 @@
-use chrono::{DateTime, Local};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Utc};
 
+use super::parse;
+use super::relative;
+use super::timezone::Zone;
+
+/// Preset names accepted by [`TimestampFormat::parse`]
+pub const FORMAT_NAMES: [&str; 8] = [
+    "human", "iso", "rfc2822", "http", "sql", "compact", "date", "relative",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TimestampFormat {
     /// Human-readable format (YYYY-MM-DD HH:MM:SS UTC)
     Human,
     /// ISO 8601 / RFC 3339 format
     Iso,
+    /// RFC 2822 format, as in email headers (Tue, 4 Mar 2025 10:00:00 +0200)
+    Rfc2822,
+    /// HTTP date, always in GMT (Tue, 04 Mar 2025 08:00:00 GMT)
+    Http,
+    /// SQL DATETIME format (2025-03-04 10:00:00)
+    Sql,
+    /// Digits only (20250304100000)
+    Compact,
+    /// Date without the time (2025-03-04)
+    Date,
+    /// Distance from now (3 hours ago, in 2 days)
+    Relative,
+    /// A strftime pattern, e.g. `%Y-%m-%d %H:%M`; build it with [`TimestampFormat::custom`]
+    Custom(String),
+}
+
+impl TimestampFormat {
+    /// Parse a preset name (case-insensitive) or a strftime pattern containing `%`
+    pub fn parse(input: &str) -> Result<TimestampFormat, String> {
+        match input.trim().to_lowercase().as_str() {
+            "human" => Ok(TimestampFormat::Human),
+            "iso" | "rfc3339" => Ok(TimestampFormat::Iso),
+            "rfc2822" | "email" => Ok(TimestampFormat::Rfc2822),
+            "http" => Ok(TimestampFormat::Http),
+            "sql" => Ok(TimestampFormat::Sql),
+            "compact" => Ok(TimestampFormat::Compact),
+            "date" => Ok(TimestampFormat::Date),
+            "relative" | "ago" => Ok(TimestampFormat::Relative),
+            _ if input.contains('%') => TimestampFormat::custom(input),
+            _ => Err(format!(
+                "Unknown format '{}'. Use {} or a strftime pattern like '%Y-%m-%d %H:%M'",
+                input,
+                FORMAT_NAMES.join(", ")
+            )),
+        }
+    }
+
+    /// A strftime pattern, checked so that formatting with it cannot fail
+    pub fn custom(pattern: &str) -> Result<TimestampFormat, String> {
+        if StrftimeItems::new(pattern).any(|item| item == Item::Error) {
+            return Err(format!("Invalid format pattern '{}'", pattern));
+        }
+        Ok(TimestampFormat::Custom(pattern.to_string()))
+    }
+}
+
+/// Precision of a Unix timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampUnit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimestampUnit {
+    /// Guess the unit of a timestamp from its magnitude
+    ///
+    /// Seconds up to 10^11 (the year 5138), then milliseconds up to 10^14,
+    /// microseconds up to 10^17 and nanoseconds above, so present-day values from
+    /// JWTs, Kafka, Postgres and Go are told apart.
+    pub fn detect(timestamp: i64) -> TimestampUnit {
+        match timestamp.unsigned_abs() {
+            0..100_000_000_000 => TimestampUnit::Seconds,
+            100_000_000_000..100_000_000_000_000 => TimestampUnit::Millis,
+            100_000_000_000_000..100_000_000_000_000_000 => TimestampUnit::Micros,
+            _ => TimestampUnit::Nanos,
+        }
+    }
+
+    /// Parse `s`, `ms`, `us` (or `µs`) and `ns`, or the unit's name
+    pub fn parse(input: &str) -> Result<TimestampUnit, String> {
+        match input.trim().to_lowercase().as_str() {
+            "s" | "sec" | "seconds" => Ok(TimestampUnit::Seconds),
+            "ms" | "millis" | "milliseconds" => Ok(TimestampUnit::Millis),
+            "us" | "µs" | "micros" | "microseconds" => Ok(TimestampUnit::Micros),
+            "ns" | "nanos" | "nanoseconds" => Ok(TimestampUnit::Nanos),
+            _ => Err(format!(
+                "Unknown timestamp unit '{}'. Use s, ms, us or ns",
+                input
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimestampUnit::Seconds => "seconds",
+            TimestampUnit::Millis => "milliseconds",
+            TimestampUnit::Micros => "microseconds",
+            TimestampUnit::Nanos => "nanoseconds",
+        }
+    }
 }
 
 pub fn now() -> i64 {
@@ -15,24 +118,141 @@ pub fn now_millis() -> i64 {
     Local::now().timestamp_millis()
 }
 
+pub fn now_micros() -> i64 {
+    Local::now().timestamp_micros()
+}
+
+/// Current time in nanoseconds; representable until the year 2262
+pub fn now_nanos() -> i64 {
+    Local::now().timestamp_nanos_opt().unwrap_or(i64::MAX)
+}
+
+/// Current time in the given unit
+pub fn now_in(unit: TimestampUnit) -> i64 {
+    match unit {
+        TimestampUnit::Seconds => now(),
+        TimestampUnit::Millis => now_millis(),
+        TimestampUnit::Micros => now_micros(),
+        TimestampUnit::Nanos => now_nanos(),
+    }
+}
+
 pub fn from_timestamp(timestamp: i64, format: TimestampFormat) -> Result<String, &'static str> {
-    DateTime::from_timestamp(timestamp, 0)
-        .map(|dt| format_datetime(&dt.with_timezone(&Local), format))
-        .ok_or("Invalid timestamp")
+    from_timestamp_in(timestamp, format, Zone::Local)
 }
 
 pub fn from_timestamp_millis(
     timestamp: i64,
     format: TimestampFormat,
 ) -> Result<String, &'static str> {
-    DateTime::from_timestamp_millis(timestamp)
-        .map(|dt| format_datetime(&dt.with_timezone(&Local), format))
-        .ok_or("Invalid timestamp")
+    from_timestamp_millis_in(timestamp, format, Zone::Local)
 }
 
-fn format_datetime(dt: &DateTime<Local>, format: TimestampFormat) -> String {
-    match format {
-        TimestampFormat::Human => dt.to_string(),
-        TimestampFormat::Iso => dt.to_rfc3339(),
+pub fn from_timestamp_micros(
+    timestamp: i64,
+    format: TimestampFormat,
+) -> Result<String, &'static str> {
+    from_timestamp_unit_in(timestamp, TimestampUnit::Micros, format, Zone::Local)
+}
+
+pub fn from_timestamp_nanos(
+    timestamp: i64,
+    format: TimestampFormat,
+) -> Result<String, &'static str> {
+    from_timestamp_unit_in(timestamp, TimestampUnit::Nanos, format, Zone::Local)
+}
+
+/// Format a Unix timestamp in the given unit as a date and time in the given zone
+pub fn from_timestamp_unit_in(
+    timestamp: i64,
+    unit: TimestampUnit,
+    format: TimestampFormat,
+    zone: Zone,
+) -> Result<String, &'static str> {
+    let dt = to_datetime(timestamp, unit).ok_or("Invalid timestamp")?;
+    format_datetime(&dt, &format, zone)
+}
+
+/// The instant of a Unix timestamp in the given unit, if it is in range
+pub fn to_datetime(timestamp: i64, unit: TimestampUnit) -> Option<DateTime<Utc>> {
+    match unit {
+        TimestampUnit::Seconds => DateTime::from_timestamp(timestamp, 0),
+        TimestampUnit::Millis => DateTime::from_timestamp_millis(timestamp),
+        TimestampUnit::Micros => DateTime::from_timestamp_micros(timestamp),
+        TimestampUnit::Nanos => Some(DateTime::from_timestamp_nanos(timestamp)),
+    }
+}
+
+/// Format a Unix timestamp in seconds as a date and time in the given zone
+pub fn from_timestamp_in(
+    timestamp: i64,
+    format: TimestampFormat,
+    zone: Zone,
+) -> Result<String, &'static str> {
+    from_timestamp_unit_in(timestamp, TimestampUnit::Seconds, format, zone)
+}
+
+/// Format a Unix timestamp in milliseconds as a date and time in the given zone
+pub fn from_timestamp_millis_in(
+    timestamp: i64,
+    format: TimestampFormat,
+    zone: Zone,
+) -> Result<String, &'static str> {
+    from_timestamp_unit_in(timestamp, TimestampUnit::Millis, format, zone)
+}
+
+/// Parse a date string into a Unix timestamp in seconds
+///
+/// Without a `pattern` the format is detected (see [`parse::parse_datetime`]);
+/// `zone` is the timezone of inputs that have no offset.
+pub fn to_timestamp(input: &str, pattern: Option<&str>, zone: Zone) -> Result<i64, String> {
+    parse_instant(input, pattern, zone).map(|dt| dt.timestamp())
+}
+
+/// Parse a date string into a Unix timestamp in milliseconds
+pub fn to_timestamp_millis(input: &str, pattern: Option<&str>, zone: Zone) -> Result<i64, String> {
+    parse_instant(input, pattern, zone).map(|dt| dt.timestamp_millis())
+}
+
+fn parse_instant(input: &str, pattern: Option<&str>, zone: Zone) -> Result<DateTime<Utc>, String> {
+    match pattern {
+        Some(pattern) => parse::parse_datetime_with_format(input, pattern, zone),
+        None => parse::parse_datetime(input, zone),
     }
 }
+
+/// Show one instant, a Unix timestamp in milliseconds, in several zones
+///
+/// Returns `(zone, date and time)` pairs in the order of `zones`.
+pub fn world_clock(
+    timestamp_millis: i64,
+    zones: &[Zone],
+    format: TimestampFormat,
+) -> Result<Vec<(String, String)>, &'static str> {
+    let dt = DateTime::from_timestamp_millis(timestamp_millis).ok_or("Invalid timestamp")?;
+    zones
+        .iter()
+        .map(|zone| Ok((zone.to_string(), format_datetime(&dt, &format, *zone)?)))
+        .collect()
+}
+
+fn format_datetime(
+    dt: &DateTime<Utc>,
+    format: &TimestampFormat,
+    zone: Zone,
+) -> Result<String, &'static str> {
+    let pattern = match format {
+        TimestampFormat::Human => return Ok(zone.format_human(dt)),
+        TimestampFormat::Iso => return Ok(zone.format_rfc3339(dt)),
+        TimestampFormat::Rfc2822 => return Ok(zone.to_fixed(dt).to_rfc2822()),
+        TimestampFormat::Http => return Ok(dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+        TimestampFormat::Sql => "%Y-%m-%d %H:%M:%S",
+        TimestampFormat::Compact => "%Y%m%d%H%M%S",
+        TimestampFormat::Date => "%Y-%m-%d",
+        TimestampFormat::Relative => {
+            return Ok(relative::relative_to_now(dt, zone, &Default::default()));
+        }
+        TimestampFormat::Custom(pattern) => pattern,
+    };
+    zone.format(dt, pattern).ok_or("Invalid format pattern")
+}
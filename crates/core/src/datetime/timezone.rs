@@ -0,0 +1,192 @@
+//! Timezones to show instants in: the local zone, UTC, fixed offsets and IANA zones.
+//!
+//! IANA zones such as `America/New_York` come from the tz database embedded by
+//! chrono-tz, so they resolve the same offline, in WASM and whatever `TZ` is set to.
+//! Offsets are written `+05:30`, `-0800` or `+05`, optionally after `UTC` or `GMT`.
+
+use std::fmt::{self, Write};
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::{TZ_VARIANTS, Tz};
+
+/// Zones a world clock shows when none are configured
+pub const DEFAULT_WORLD_CLOCK: [&str; 6] = [
+    "UTC",
+    "America/Los_Angeles",
+    "America/New_York",
+    "Europe/London",
+    "Asia/Kolkata",
+    "Asia/Tokyo",
+];
+
+/// A timezone an instant can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    /// The timezone of the machine
+    Local,
+    Utc,
+    /// A fixed offset from UTC, e.g. `+05:30`
+    Fixed(FixedOffset),
+    /// An IANA zone with its daylight saving rules, e.g. `Europe/London`
+    Named(Tz),
+}
+
+impl Zone {
+    /// Parse `local`, `UTC`, an offset like `+05:30` or an IANA name (case-insensitive)
+    pub fn parse(input: &str) -> Result<Zone, String> {
+        let name = input.trim();
+        match name.to_lowercase().as_str() {
+            "local" => return Ok(Zone::Local),
+            "utc" | "z" => return Ok(Zone::Utc),
+            _ => {}
+        }
+
+        if let Some(offset) = parse_offset(name)? {
+            return Ok(Zone::Fixed(offset));
+        }
+
+        TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(name))
+            .map(|tz| Zone::Named(*tz))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown timezone '{}'. Use an IANA name like America/New_York, UTC or an offset like +05:30",
+                    name
+                )
+            })
+    }
+
+    /// Local date and time of an instant in this zone, e.g. `2024-03-10 03:00:00 EDT`
+    ///
+    /// IANA zones show their abbreviation; fixed offsets and the local zone show
+    /// the offset.
+    pub fn format_human(&self, instant: &DateTime<Utc>) -> String {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).to_string(),
+            Zone::Utc => instant.to_string(),
+            Zone::Fixed(offset) => instant.with_timezone(offset).to_string(),
+            Zone::Named(tz) => instant.with_timezone(tz).to_string(),
+        }
+    }
+
+    /// RFC 3339 date and time of an instant in this zone, e.g. `2024-03-10T03:00:00-04:00`
+    pub fn format_rfc3339(&self, instant: &DateTime<Utc>) -> String {
+        self.to_fixed(instant).to_rfc3339()
+    }
+
+    /// Local date and time of an instant in this zone with a strftime pattern
+    ///
+    /// `%Z` is the abbreviation of IANA zones and the offset otherwise. Returns
+    /// `None` for an invalid pattern.
+    pub fn format(&self, instant: &DateTime<Utc>, pattern: &str) -> Option<String> {
+        let mut out = String::new();
+        let written = match self {
+            Zone::Local => write!(out, "{}", instant.with_timezone(&Local).format(pattern)),
+            Zone::Utc => write!(out, "{}", instant.format(pattern)),
+            Zone::Fixed(offset) => write!(out, "{}", instant.with_timezone(offset).format(pattern)),
+            Zone::Named(tz) => write!(out, "{}", instant.with_timezone(tz).format(pattern)),
+        };
+        written.ok().map(|_| out)
+    }
+
+    /// The instant a local date and time in this zone stands for
+    ///
+    /// Times repeated when clocks go back resolve to the earlier instant; times
+    /// skipped when clocks go forward are an error.
+    pub fn resolve(&self, local: &NaiveDateTime) -> Result<DateTime<Utc>, String> {
+        let instant = match self {
+            Zone::Local => Local
+                .from_local_datetime(local)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+            Zone::Utc => Some(local.and_utc()),
+            Zone::Fixed(offset) => offset
+                .from_local_datetime(local)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+            Zone::Named(tz) => tz
+                .from_local_datetime(local)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+        };
+        instant.ok_or_else(|| {
+            format!(
+                "{} does not exist in {} (skipped by a daylight saving change)",
+                local, self
+            )
+        })
+    }
+
+    /// The instant at this zone's offset from UTC
+    pub fn to_fixed(&self, instant: &DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).fixed_offset(),
+            Zone::Utc => instant.fixed_offset(),
+            Zone::Fixed(offset) => instant.with_timezone(offset),
+            Zone::Named(tz) => instant.with_timezone(tz).fixed_offset(),
+        }
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Local => write!(f, "Local"),
+            Zone::Utc => write!(f, "UTC"),
+            Zone::Fixed(offset) => write!(f, "{}", offset),
+            Zone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+/// Parse `+05:30`, `-0800`, `+05`, `UTC+5:30` or `GMT-8`; `None` if it is not an offset
+fn parse_offset(input: &str) -> Result<Option<FixedOffset>, String> {
+    let upper = input.to_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    let (sign, rest) = match rest.chars().next() {
+        Some('+') => (1, &rest[1..]),
+        Some('-') => (-1, &rest[1..]),
+        _ => return Ok(None),
+    };
+
+    let invalid = || format!("Invalid UTC offset '{}'. Use +HH:MM, e.g. +05:30", input);
+    if !rest.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return Err(invalid());
+    }
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let (Ok(hours), Ok(minutes)) = (hours.parse::<i32>(), minutes.parse::<i32>()) else {
+        return Err(invalid());
+    };
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .map(Some)
+        .ok_or_else(invalid)
+}
+
+/// Parse zones separated by commas or newlines, skipping blank lines and `#` comments
+pub fn parse_zone_list(input: &str) -> Result<Vec<Zone>, String> {
+    input
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(Zone::parse)
+        .collect()
+}
+
+/// Names of all IANA zones in the embedded database
+pub fn zone_names() -> Vec<&'static str> {
+    TZ_VARIANTS.iter().map(|tz| tz.name()).collect()
+}
@@ -0,0 +1,149 @@
+// Descriptive statistics over a list of numbers
+//
+// Design decisions:
+// - Numbers are parsed with `calculator::parse_literal`, so `1_000`, `0xff` and
+//   `-1.5` are accepted the same way as in `strapd calc`
+// - Variance and standard deviation are the sample (n - 1) estimates, which is
+//   what you want for latency samples taken from logs
+// - Percentiles interpolate linearly between closest ranks (the same method as
+//   numpy's default and Excel's PERCENTILE.INC)
+
+use crate::calculator;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample variance (0 for a single value)
+    pub variance: f64,
+    /// Sample standard deviation (0 for a single value)
+    pub std_dev: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramBin {
+    /// Inclusive lower bound
+    pub start: f64,
+    /// Exclusive upper bound (inclusive for the last bin)
+    pub end: f64,
+    pub count: usize,
+}
+
+/// Parse whitespace- or newline-separated numbers
+pub fn parse_numbers(input: &str) -> Result<Vec<f64>, String> {
+    let values = input
+        .split_whitespace()
+        .map(calculator::parse_literal)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if values.is_empty() {
+        return Err("No numbers found in input".to_string());
+    }
+    Ok(values)
+}
+
+/// Compute count, sum, min, max, mean, median, variance and standard deviation
+pub fn summarize(values: &[f64]) -> Result<Summary, String> {
+    if values.is_empty() {
+        return Err("Cannot compute statistics of an empty list".to_string());
+    }
+
+    let count = values.len();
+    let sum: f64 = values.iter().sum();
+    let mean = sum / count as f64;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let variance = if count > 1 {
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+    } else {
+        0.0
+    };
+
+    Ok(Summary {
+        count,
+        sum,
+        min,
+        max,
+        mean,
+        median: percentile(values, 50.0)?,
+        variance,
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// Compute the given percentile (0-100) with linear interpolation
+pub fn percentile(values: &[f64], p: f64) -> Result<f64, String> {
+    percentiles(values, &[p]).map(|results| results[0])
+}
+
+/// Compute several percentiles (0-100) at once, sorting the values only once
+pub fn percentiles(values: &[f64], ps: &[f64]) -> Result<Vec<f64>, String> {
+    if values.is_empty() {
+        return Err("Cannot compute percentiles of an empty list".to_string());
+    }
+    if let Some(p) = ps.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+        return Err(format!("Percentile must be between 0 and 100, got {}", p));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let last = (sorted.len() - 1) as f64;
+    Ok(ps
+        .iter()
+        .map(|p| {
+            let rank = p / 100.0 * last;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+        })
+        .collect())
+}
+
+/// Split the range of values into equal-width bins and count the values in each
+pub fn histogram(values: &[f64], bins: usize) -> Result<Vec<HistogramBin>, String> {
+    if values.is_empty() {
+        return Err("Cannot build a histogram of an empty list".to_string());
+    }
+    if bins == 0 {
+        return Err("Number of bins must be at least 1".to_string());
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    // All values equal: a single bin holds everything
+    if min == max {
+        return Ok(vec![HistogramBin {
+            start: min,
+            end: max,
+            count: values.len(),
+        }]);
+    }
+
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let index = (((value - min) / width) as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| HistogramBin {
+            start: min + width * i as f64,
+            end: if i == bins - 1 {
+                max
+            } else {
+                min + width * (i + 1) as f64
+            },
+            count,
+        })
+        .collect())
+}
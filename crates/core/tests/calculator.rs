@@ -1,4 +1,4 @@
-use strapd_core::calculator;
+use strapd_core::calculator::{self, Notation, NumberFormat};
 
 // ========== Basic Operations Tests ==========
 
@@ -99,6 +99,7 @@ fn test_exponentiation_negative_base_fractional_exponent() {
     assert!(
         result
             .unwrap_err()
+            .to_string()
             .contains("Complex numbers not supported")
     );
 
@@ -108,6 +109,7 @@ fn test_exponentiation_negative_base_fractional_exponent() {
     assert!(
         result
             .unwrap_err()
+            .to_string()
             .contains("Complex numbers not supported")
     );
 
@@ -255,35 +257,40 @@ fn test_error_whitespace_only() {
 fn test_error_division_by_zero() {
     let result = calculator::evaluate("5/0");
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Division by zero");
+    assert_eq!(result.unwrap_err().to_string(), "Division by zero");
 }
 
 #[test]
 fn test_error_modulo_by_zero() {
     let result = calculator::evaluate("5%0");
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Division by zero");
+    assert_eq!(result.unwrap_err().to_string(), "Division by zero");
 }
 
 #[test]
 fn test_error_invalid_character() {
-    let result = calculator::evaluate("5&3");
+    let result = calculator::evaluate("5$3");
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Invalid character"));
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid character")
+    );
 }
 
 #[test]
 fn test_error_mismatched_parentheses_open() {
     let result = calculator::evaluate("(5+2");
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Mismatched parentheses");
+    assert_eq!(result.unwrap_err().to_string(), "Mismatched parentheses");
 }
 
 #[test]
 fn test_error_mismatched_parentheses_close() {
     let result = calculator::evaluate("5+2)");
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Mismatched parentheses");
+    assert_eq!(result.unwrap_err().to_string(), "Mismatched parentheses");
 }
 
 #[test]
@@ -325,14 +332,19 @@ fn test_decimal_multiplication() {
 #[test]
 fn test_very_large_numbers() {
     let result = calculator::evaluate("99999999999999999999*2");
-    assert!(result.is_ok());
+    assert_eq!(result, Ok("199999999999999999998".to_string()));
 }
 
 #[test]
 fn test_error_multiple_decimal_points() {
     let result = calculator::evaluate("5.5.5 + 2");
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("multiple decimal points"));
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("multiple decimal points")
+    );
 }
 
 // ========== Number Type Handling Tests ==========
@@ -407,3 +419,936 @@ fn test_zero_operations() {
     assert_eq!(calculator::evaluate("0*5"), Ok("0".to_string()));
     assert_eq!(calculator::evaluate("5-5"), Ok("0".to_string()));
 }
+
+// ========== Function Tests ==========
+
+#[test]
+fn test_function_sqrt_perfect_square_stays_integer() {
+    assert_eq!(calculator::evaluate("sqrt(16)"), Ok("4".to_string()));
+}
+
+#[test]
+fn test_function_sqrt_float() {
+    let result: f64 = calculator::evaluate("sqrt(2)").unwrap().parse().unwrap();
+    assert!((result - std::f64::consts::SQRT_2).abs() < 1e-12);
+}
+
+#[test]
+fn test_function_sqrt_negative() {
+    let result = calculator::evaluate("sqrt(-4)");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Complex numbers not supported")
+    );
+}
+
+#[test]
+fn test_function_logarithms() {
+    assert_eq!(calculator::evaluate("log2(4096)"), Ok("12".to_string()));
+    assert_eq!(calculator::evaluate("log10(1000)"), Ok("3".to_string()));
+    assert_eq!(calculator::evaluate("ln(1)"), Ok("0".to_string()));
+}
+
+#[test]
+fn test_function_logarithm_of_zero() {
+    let result = calculator::evaluate("ln(0)");
+    assert!(result.unwrap_err().to_string().contains("undefined"));
+}
+
+#[test]
+fn test_function_abs() {
+    assert_eq!(calculator::evaluate("abs(-7)"), Ok("7".to_string()));
+    assert_eq!(calculator::evaluate("abs(-2.5)"), Ok("2.5".to_string()));
+}
+
+#[test]
+fn test_function_floor_ceil_return_integers() {
+    assert_eq!(calculator::evaluate("floor(3.7)"), Ok("3".to_string()));
+    assert_eq!(calculator::evaluate("ceil(3.2)"), Ok("4".to_string()));
+    assert_eq!(calculator::evaluate("floor(-3.2)"), Ok("-4".to_string()));
+}
+
+#[test]
+fn test_function_round() {
+    assert_eq!(calculator::evaluate("round(2.5)"), Ok("3".to_string()));
+    assert_eq!(
+        calculator::evaluate("round(12.345, 2)"),
+        Ok("12.35".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("round(1234, -2)"),
+        Ok("1200".to_string())
+    );
+}
+
+#[test]
+fn test_function_min_max() {
+    assert_eq!(calculator::evaluate("max(3, 7)"), Ok("7".to_string()));
+    assert_eq!(calculator::evaluate("min(3, 7, -2)"), Ok("-2".to_string()));
+    assert_eq!(calculator::evaluate("max(1, 2.5)"), Ok("2.5".to_string()));
+}
+
+#[test]
+fn test_function_trigonometry() {
+    assert_eq!(calculator::evaluate("sin(0)"), Ok("0".to_string()));
+    assert_eq!(calculator::evaluate("cos(0)"), Ok("1".to_string()));
+    let result: f64 = calculator::evaluate("atan2(1, 1)")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((result - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+}
+
+#[test]
+fn test_function_asin_out_of_range() {
+    let result = calculator::evaluate("asin(2)");
+    assert!(result.unwrap_err().to_string().contains("between -1 and 1"));
+}
+
+#[test]
+fn test_function_exp() {
+    let result: f64 = calculator::evaluate("exp(1)").unwrap().parse().unwrap();
+    assert!((result - std::f64::consts::E).abs() < 1e-12);
+}
+
+#[test]
+fn test_function_nested_and_in_expressions() {
+    assert_eq!(
+        calculator::evaluate("sqrt(max(9, 16)) + 1"),
+        Ok("5".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("2 * abs(3 - 10)"),
+        Ok("14".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("max(1 + 2, 2 * 2)"),
+        Ok("4".to_string())
+    );
+}
+
+#[test]
+fn test_function_case_insensitive_and_spaced() {
+    assert_eq!(calculator::evaluate("SQRT (25)"), Ok("5".to_string()));
+}
+
+#[test]
+fn test_function_negated() {
+    assert_eq!(calculator::evaluate("-sqrt(9)"), Ok("-3".to_string()));
+    assert_eq!(calculator::evaluate("-(2 + 3)"), Ok("-5".to_string()));
+    assert_eq!(calculator::evaluate("2 * -abs(-4)"), Ok("-8".to_string()));
+}
+
+#[test]
+fn test_error_function_wrong_arity() {
+    let result = calculator::evaluate("sqrt(1, 2)");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "sqrt() expects 1 argument, got 2".to_string()
+    );
+
+    let result = calculator::evaluate("max()");
+    assert!(result.unwrap_err().to_string().contains("at least 1"));
+}
+
+#[test]
+fn test_error_unknown_function() {
+    let result = calculator::evaluate("foo(2)");
+    assert_eq!(result.unwrap_err().to_string(), "Unknown function: foo");
+}
+
+#[test]
+fn test_error_comma_outside_function() {
+    let result = calculator::evaluate("(1, 2)");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("outside of a function call")
+    );
+}
+
+// ========== Constant Tests ==========
+
+#[test]
+fn test_constant_pi() {
+    let result: f64 = calculator::evaluate("pi * 2^2").unwrap().parse().unwrap();
+    assert!((result - 4.0 * std::f64::consts::PI).abs() < 1e-12);
+}
+
+#[test]
+fn test_constant_e_and_tau() {
+    let result: f64 = calculator::evaluate("ln(e)").unwrap().parse().unwrap();
+    assert!((result - 1.0).abs() < 1e-12);
+    let result: f64 = calculator::evaluate("tau / 2 - pi")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(result.abs() < 1e-12);
+}
+
+#[test]
+fn test_constant_negated() {
+    let result: f64 = calculator::evaluate("-pi").unwrap().parse().unwrap();
+    assert!((result + std::f64::consts::PI).abs() < 1e-12);
+}
+
+#[test]
+fn test_error_unknown_constant() {
+    let result = calculator::evaluate("2 * foo");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Unknown variable or constant: foo"
+    );
+}
+
+// ========== Programmer Mode Tests ==========
+
+#[test]
+fn test_radix_literals() {
+    assert_eq!(calculator::evaluate("0xff"), Ok("255".to_string()));
+    assert_eq!(calculator::evaluate("0b1010"), Ok("10".to_string()));
+    assert_eq!(calculator::evaluate("0o17"), Ok("15".to_string()));
+    assert_eq!(calculator::evaluate("0XFF + 1"), Ok("256".to_string()));
+    assert_eq!(calculator::evaluate("-0x10"), Ok("-16".to_string()));
+}
+
+#[test]
+fn test_digit_separators() {
+    assert_eq!(
+        calculator::evaluate("1_000_000 + 1"),
+        Ok("1000001".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("0xffff_ffff"),
+        Ok("4294967295".to_string())
+    );
+    assert_eq!(calculator::evaluate("1_000.5 * 2"), Ok("2001".to_string()));
+}
+
+#[test]
+fn test_exponent_literals() {
+    assert_eq!(calculator::evaluate("1e3"), Ok("1000".to_string()));
+    assert_eq!(calculator::evaluate("2.5E-3 * 2"), Ok("0.005".to_string()));
+    assert_eq!(calculator::evaluate("1e+2 % 7"), Ok("2".to_string()));
+    assert_eq!(calculator::evaluate("1_000e3"), Ok("1000000".to_string()));
+    assert_eq!(
+        calculator::evaluate_exact("1e-1 + 2e-1", 10),
+        Ok("0.3".to_string())
+    );
+    assert_eq!(calculator::parse_literal("1e3"), Ok(1000.0));
+    // Without digits after it, `e` is not an exponent
+    assert!(calculator::evaluate("2e").is_err());
+}
+
+#[test]
+fn test_error_invalid_radix_literal() {
+    assert!(
+        calculator::evaluate("0x")
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid number")
+    );
+    assert!(
+        calculator::evaluate("0b102")
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid number")
+    );
+    assert!(
+        calculator::evaluate("1__0")
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid number")
+    );
+}
+
+#[test]
+fn test_bitwise_and_or_xor() {
+    assert_eq!(calculator::evaluate("0b1100 & 0b1010"), Ok("8".to_string()));
+    assert_eq!(
+        calculator::evaluate("0b1100 | 0b1010"),
+        Ok("14".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("0b1100 xor 0b1010"),
+        Ok("6".to_string())
+    );
+}
+
+#[test]
+fn test_bitwise_not() {
+    assert_eq!(calculator::evaluate("~0"), Ok("-1".to_string()));
+    assert_eq!(calculator::evaluate("0xff & ~0x0f"), Ok("240".to_string()));
+}
+
+#[test]
+fn test_shifts() {
+    assert_eq!(calculator::evaluate("1 << 10"), Ok("1024".to_string()));
+    assert_eq!(calculator::evaluate("1024 >> 3"), Ok("128".to_string()));
+    assert_eq!(calculator::evaluate("-16 >> 2"), Ok("-4".to_string()));
+}
+
+#[test]
+fn test_bitwise_precedence() {
+    // Shifts bind looser than arithmetic, & binds tighter than |
+    assert_eq!(calculator::evaluate("1 << 2 + 1"), Ok("8".to_string()));
+    assert_eq!(calculator::evaluate("1 | 2 & 3"), Ok("3".to_string()));
+    assert_eq!(calculator::evaluate("6 & 3 xor 1"), Ok("3".to_string()));
+}
+
+#[test]
+fn test_error_bitwise_on_float() {
+    let result = calculator::evaluate("1.5 & 1");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("require integer operands")
+    );
+}
+
+#[test]
+fn test_error_shift_out_of_range() {
+    assert!(calculator::evaluate("1 << -1").is_err());
+    assert!(calculator::evaluate("1 << 2000000").is_err());
+}
+
+#[test]
+fn test_shift_promotes_to_big_integer() {
+    assert_eq!(
+        calculator::evaluate("1 << 127"),
+        Ok("170141183460469231731687303715884105728".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("(1 << 130) >> 129"),
+        Ok("2".to_string())
+    );
+}
+
+#[test]
+fn test_error_single_angle_bracket() {
+    let result = calculator::evaluate("1 < 2");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid character")
+    );
+}
+
+#[test]
+fn test_evaluate_integer() {
+    assert_eq!(calculator::evaluate_integer("0x10 * 2"), Ok(32));
+    assert!(calculator::evaluate_integer("1 / 4").is_err());
+}
+
+#[test]
+fn test_format_radix() {
+    use calculator::Radix;
+    assert_eq!(
+        calculator::format_radix(255, Radix::Hexadecimal, None),
+        Ok("0xff".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(5, Radix::Binary, None),
+        Ok("0b101".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(8, Radix::Octal, None),
+        Ok("0o10".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(-255, Radix::Hexadecimal, None),
+        Ok("-0xff".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(-42, Radix::Decimal, Some(8)),
+        Ok("-42".to_string())
+    );
+}
+
+#[test]
+fn test_format_radix_twos_complement() {
+    use calculator::Radix;
+    assert_eq!(
+        calculator::format_radix(-1, Radix::Hexadecimal, Some(8)),
+        Ok("0xff".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(-2, Radix::Binary, Some(4)),
+        Ok("0b1110".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(5, Radix::Hexadecimal, Some(16)),
+        Ok("0x0005".to_string())
+    );
+    assert_eq!(
+        calculator::format_radix(-1, Radix::Hexadecimal, Some(128)),
+        Ok(format!("0x{}", "f".repeat(32)))
+    );
+}
+
+#[test]
+fn test_format_radix_value_too_wide() {
+    use calculator::Radix;
+    assert!(calculator::format_radix(256, Radix::Hexadecimal, Some(8)).is_err());
+    assert!(calculator::format_radix(-129, Radix::Hexadecimal, Some(8)).is_err());
+    assert!(calculator::format_radix(255, Radix::Hexadecimal, Some(8)).is_ok());
+}
+
+// ========== Context Tests ==========
+
+#[test]
+fn test_context_assignment_and_reuse() {
+    let mut context = calculator::Context::new();
+    assert_eq!(context.evaluate("rate = 1500 / 60"), Ok("25".to_string()));
+    assert_eq!(context.evaluate("rate * 4"), Ok("100".to_string()));
+}
+
+#[test]
+fn test_context_ans_and_underscore() {
+    let mut context = calculator::Context::new();
+    assert_eq!(context.evaluate("6 * 7"), Ok("42".to_string()));
+    assert_eq!(context.evaluate("ans + 1"), Ok("43".to_string()));
+    assert_eq!(context.evaluate("_ * 2"), Ok("86".to_string()));
+}
+
+#[test]
+fn test_context_ans_without_previous_result() {
+    let mut context = calculator::Context::new();
+    let result = context.evaluate("ans + 1");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("No previous result")
+    );
+}
+
+#[test]
+fn test_context_failed_evaluation_keeps_state() {
+    let mut context = calculator::Context::new();
+    context.evaluate("x = 10").unwrap();
+    assert!(context.evaluate("x = 1 / 0").is_err());
+    assert_eq!(context.evaluate("x"), Ok("10".to_string()));
+    assert_eq!(context.evaluate("ans"), Ok("10".to_string()));
+}
+
+#[test]
+fn test_context_reassignment_uses_previous_value() {
+    let mut context = calculator::Context::new();
+    context.evaluate("total = 5").unwrap();
+    assert_eq!(context.evaluate("total = total * 3"), Ok("15".to_string()));
+}
+
+#[test]
+fn test_context_variables_and_clear() {
+    let mut context = calculator::Context::new();
+    context.evaluate("b = 2").unwrap();
+    context.evaluate("a = 1.5").unwrap();
+    assert_eq!(
+        context.variables(),
+        vec![
+            ("a".to_string(), "1.5".to_string()),
+            ("b".to_string(), "2".to_string())
+        ]
+    );
+
+    context.clear();
+    assert!(context.variables().is_empty());
+    assert!(context.evaluate("a").is_err());
+}
+
+#[test]
+fn test_context_evaluate_integer() {
+    let mut context = calculator::Context::new();
+    context.evaluate("mask = 0xf0").unwrap();
+    assert_eq!(context.evaluate_integer("mask >> 4"), Ok(15));
+}
+
+#[test]
+fn test_error_assign_to_reserved_name() {
+    let mut context = calculator::Context::new();
+    assert!(
+        context
+            .evaluate("pi = 3")
+            .unwrap_err()
+            .to_string()
+            .contains("reserved")
+    );
+    assert!(
+        context
+            .evaluate("ans = 3")
+            .unwrap_err()
+            .to_string()
+            .contains("reserved")
+    );
+    assert!(
+        context
+            .evaluate("sqrt = 3")
+            .unwrap_err()
+            .to_string()
+            .contains("reserved")
+    );
+}
+
+#[test]
+fn test_error_invalid_assignment_target() {
+    let mut context = calculator::Context::new();
+    let result = context.evaluate("2x = 3");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid assignment target")
+    );
+}
+
+#[test]
+fn test_stateless_evaluate_supports_assignment() {
+    assert_eq!(calculator::evaluate("x = 2 + 3"), Ok("5".to_string()));
+}
+
+// ========== Big Integer Tests ==========
+
+#[test]
+fn test_overflow_promotes_to_big_integer() {
+    assert_eq!(
+        calculator::evaluate("2^200"),
+        Ok("1606938044258990275541962092341162602522202993782792835301376".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("170141183460469231731687303715884105727 + 1"),
+        Ok("170141183460469231731687303715884105728".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("-170141183460469231731687303715884105728 - 1"),
+        Ok("-170141183460469231731687303715884105729".to_string())
+    );
+}
+
+#[test]
+fn test_big_integer_back_to_i128() {
+    assert_eq!(calculator::evaluate("2^200 / 2^199"), Ok("2".to_string()));
+    assert_eq!(
+        calculator::evaluate("2^130 - 2^130 + 5"),
+        Ok("5".to_string())
+    );
+}
+
+#[test]
+fn test_big_integer_quotient_rounding() {
+    // The quotient is kept exact, so the low digits are not f64 noise
+    assert_eq!(
+        calculator::evaluate("floor(2^200 / 3)"),
+        Ok("535646014752996758513987364113720867507400997927597611767125".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("ceil(10^17 / 3)"),
+        Ok("33333333333333334".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("round(2^60 / 3)"),
+        Ok("384307168202282325".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("10^17 / 3"),
+        Ok("33333333333333333.333".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("10^41 / 3"),
+        Ok("3.3333333333333333333e40".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("-(10^20 + 1) / 2"),
+        Ok("-5.0000000000000000001e19".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("1 / 3"),
+        Ok("0.3333333333333333".to_string())
+    );
+}
+
+#[test]
+fn test_big_integer_literal() {
+    assert_eq!(
+        calculator::evaluate("123456789012345678901234567890123456789012 % 1000"),
+        Ok("12".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate("0xffffffffffffffffffffffffffffffffff"),
+        Ok("87112285931760246646623899502532662132735".to_string())
+    );
+}
+
+#[test]
+fn test_error_power_result_too_large() {
+    assert_eq!(
+        calculator::evaluate("10^1000000").unwrap_err().to_string(),
+        "Calculation overflow"
+    );
+}
+
+#[test]
+fn test_evaluate_integer_big_result() {
+    assert!(
+        calculator::evaluate_integer("2^130")
+            .unwrap_err()
+            .to_string()
+            .contains("does not fit in 128 bits")
+    );
+}
+
+// ========== Exact Mode Tests ==========
+
+#[test]
+fn test_exact_decimal_addition() {
+    assert_eq!(
+        calculator::evaluate("0.1 + 0.2"),
+        Ok("0.30000000000000004".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("0.1 + 0.2", 20),
+        Ok("0.3".to_string())
+    );
+}
+
+#[test]
+fn test_exact_money_arithmetic() {
+    assert_eq!(
+        calculator::evaluate_exact("19.99 * 3 - 0.97", 20),
+        Ok("59".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("1234.56 * 1.0825", 20),
+        Ok("1336.4112".to_string())
+    );
+}
+
+#[test]
+fn test_exact_division_significant_digits() {
+    assert_eq!(
+        calculator::evaluate_exact("1 / 3", 20),
+        Ok("0.33333333333333333333".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("2 / 3", 5),
+        Ok("0.66667".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("100 / 7", 4),
+        Ok("14.29".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("-1 / 8", 20),
+        Ok("-0.125".to_string())
+    );
+}
+
+#[test]
+fn test_exact_small_values_keep_significant_digits() {
+    assert_eq!(
+        calculator::evaluate_exact("1 / 3000", 3),
+        Ok("0.000333".to_string())
+    );
+}
+
+#[test]
+fn test_exact_integer_part_beyond_digits_uses_scientific() {
+    // The fraction does not fit in the significant digits, so the value is not
+    // shown as an integer
+    assert_eq!(
+        calculator::evaluate_exact("123456.5 / 1", 3),
+        Ok("1.23e5".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("99999.5", 3),
+        Ok("1e5".to_string())
+    );
+}
+
+#[test]
+fn test_exact_modulo_and_power() {
+    assert_eq!(
+        calculator::evaluate_exact("5.5 % 2", 20),
+        Ok("1.5".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("-5.5 % 2", 20),
+        Ok("-1.5".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("1.1 ^ 2", 20),
+        Ok("1.21".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("2 ^ -3", 20),
+        Ok("0.125".to_string())
+    );
+}
+
+#[test]
+fn test_exact_fractions_carry_through() {
+    // 1/3 is kept as a fraction, so multiplying back gives exactly 1
+    assert_eq!(
+        calculator::evaluate_exact("1 / 3 * 3", 20),
+        Ok("1".to_string())
+    );
+}
+
+#[test]
+fn test_exact_functions() {
+    assert_eq!(
+        calculator::evaluate_exact("round(2.675, 2)", 20),
+        Ok("2.68".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("floor(-2.5)", 20),
+        Ok("-3".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("sqrt(0.25)", 20),
+        Ok("0.5".to_string())
+    );
+    assert_eq!(
+        calculator::evaluate_exact("max(0.1, 0.3 - 0.2)", 20),
+        Ok("0.1".to_string())
+    );
+}
+
+#[test]
+fn test_exact_mode_floats_stay_floats() {
+    // Irrational results fall back to f64
+    let result = calculator::evaluate_exact("sqrt(2)", 20).unwrap();
+    assert!(result.starts_with("1.41421356"));
+}
+
+#[test]
+fn test_exact_context_keeps_variables() {
+    let mut ctx = calculator::Context::exact(20);
+    assert_eq!(ctx.evaluate("price = 0.1"), Ok("0.1".to_string()));
+    assert_eq!(ctx.evaluate("price * 3"), Ok("0.3".to_string()));
+    assert_eq!(ctx.evaluate("ans + 0.7"), Ok("1".to_string()));
+}
+
+// ========== Error Span Tests ==========
+
+fn error_span(expr: &str) -> Option<std::ops::Range<usize>> {
+    calculator::evaluate(expr).unwrap_err().span()
+}
+
+#[test]
+fn test_error_span_invalid_character() {
+    let err = calculator::evaluate("1 + 2 $ 3").unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &calculator::CalcErrorKind::InvalidCharacter('$')
+    );
+    assert_eq!(err.span(), Some(6..7));
+}
+
+#[test]
+fn test_error_span_is_byte_offset() {
+    // `é` and `×` are two bytes wide in UTF-8
+    assert_eq!(error_span("(é) + 1"), Some(1..3));
+    assert_eq!(error_span("1 + ×"), Some(4..6));
+}
+
+#[test]
+fn test_error_span_division_by_zero_points_at_operator() {
+    assert_eq!(error_span("10 + 4 / (2 - 2)"), Some(7..8));
+}
+
+#[test]
+fn test_error_span_mismatched_parentheses() {
+    assert_eq!(error_span("(1 + 2"), Some(0..1));
+    assert_eq!(error_span("1 + 2)"), Some(5..6));
+}
+
+#[test]
+fn test_error_span_missing_operand_and_operator() {
+    let err = calculator::evaluate("2 * * 3").unwrap_err();
+    assert_eq!(err.kind(), &calculator::CalcErrorKind::MissingOperand);
+    assert_eq!(err.span(), Some(4..5));
+
+    let err = calculator::evaluate("2 3").unwrap_err();
+    assert_eq!(err.kind(), &calculator::CalcErrorKind::MissingOperator);
+    assert_eq!(err.span(), Some(2..3));
+
+    assert_eq!(error_span("1 +"), Some(2..3));
+}
+
+#[test]
+fn test_error_span_unknown_identifiers() {
+    assert_eq!(error_span("2 * foo"), Some(4..7));
+    assert_eq!(error_span("1 + sqr(4)"), Some(4..7));
+}
+
+#[test]
+fn test_error_span_number_literals() {
+    assert_eq!(error_span("1 + 5.5.5"), Some(4..8));
+    assert_eq!(error_span("0x + 1"), Some(0..2));
+    assert_eq!(error_span("-1__0"), Some(1..5));
+}
+
+#[test]
+fn test_error_span_function_error_points_at_name() {
+    assert_eq!(error_span("1 + sqrt(1, 2)"), Some(4..8));
+}
+
+#[test]
+fn test_error_span_function_arity_points_at_name() {
+    let err = calculator::evaluate("2 + min()").unwrap_err();
+    assert_eq!(err.to_string(), "min() expects at least 1 argument, got 0");
+    assert_eq!(err.span(), Some(4..7));
+}
+
+#[test]
+fn test_error_span_unexpected_comma_points_at_comma() {
+    assert_eq!(error_span("(1,2)"), Some(2..3));
+    assert_eq!(error_span("1 + (2, 3, 4)"), Some(6..7));
+    assert_eq!(error_span("1, 2"), Some(1..2));
+}
+
+#[test]
+fn test_error_span_relative_to_assignment() {
+    let mut context = calculator::Context::new();
+    assert_eq!(
+        context.evaluate("total = 1 / 0").unwrap_err().span(),
+        Some(10..11)
+    );
+    assert_eq!(context.evaluate("  pi = 3").unwrap_err().span(), Some(2..4));
+}
+
+// ========== Number Format Tests ==========
+
+fn number_format(
+    notation: Notation,
+    decimals: Option<usize>,
+    significant_figures: Option<usize>,
+    separator: Option<char>,
+) -> NumberFormat {
+    NumberFormat {
+        notation,
+        decimals,
+        significant_figures,
+        separator,
+    }
+}
+
+#[test]
+fn test_number_format_default_leaves_numbers() {
+    let format = NumberFormat::default();
+    assert_eq!(format.format(0.1 + 0.2), "0.30000000000000004");
+    assert_eq!(
+        format.format_str("123456789012345678901234567890"),
+        "123456789012345678901234567890"
+    );
+}
+
+#[test]
+fn test_number_format_decimals() {
+    let format = number_format(Notation::Fixed, Some(2), None, None);
+    assert_eq!(format.format(1.23456), "1.23");
+    assert_eq!(format.format(2.5), "2.50");
+    assert_eq!(format.format(0.999), "1.00");
+    assert_eq!(format.format(1e20), "100000000000000000000.00");
+}
+
+#[test]
+fn test_number_format_nonzero_keeps_small_values() {
+    // Nonzero values that would round to 0 keep significant figures
+    let format = number_format(Notation::Fixed, Some(2), None, None);
+    assert_eq!(format.format_nonzero(-0.001), "-0.001");
+    assert_eq!(format.format_nonzero(0.0012345), "0.001235");
+    assert_eq!(format.format_nonzero(1.15740740e-14), "1.157e-14");
+    assert_eq!(format.format_nonzero(0.0), "0.00");
+    assert_eq!(format.format_nonzero(0.5), "0.50");
+}
+
+#[test]
+fn test_number_format_decimals_round_small_values() {
+    // Explicit decimals apply to small values too
+    let format = number_format(Notation::Fixed, Some(2), None, None);
+    assert_eq!(format.format(0.001), "0.00");
+    assert_eq!(format.format(1.15740740e-14), "0.00");
+}
+
+#[test]
+fn test_number_format_significant_figures() {
+    let format = number_format(Notation::Fixed, None, Some(3), None);
+    assert_eq!(format.format(1234567.0), "1230000");
+    assert_eq!(format.format(0.000123456), "0.000123");
+    assert_eq!(format.format(9.996), "10");
+    assert_eq!(format.format(1.5), "1.5");
+    assert_eq!(format.format(0.0), "0");
+    // Big integers keep their digits exact
+    assert_eq!(
+        format.format_str("123456789012345678901234567890"),
+        "123000000000000000000000000000"
+    );
+}
+
+#[test]
+fn test_number_format_scientific() {
+    let format = number_format(Notation::Scientific, None, Some(4), None);
+    assert_eq!(format.format(1.0 / 86400e9), "1.157e-14");
+    assert_eq!(format.format(-1536000.0), "-1.536e6");
+    assert_eq!(format.format(9.99999), "1e1");
+    assert_eq!(format.format(0.0), "0e0");
+
+    let format = number_format(Notation::Scientific, Some(2), None, None);
+    assert_eq!(format.format(1536000.0), "1.54e6");
+    assert_eq!(format.format(1.0), "1.00e0");
+
+    let format = number_format(Notation::Scientific, None, None, None);
+    assert_eq!(format.format(0.00025), "2.5e-4");
+}
+
+#[test]
+fn test_number_format_engineering() {
+    let format = number_format(Notation::Engineering, None, Some(4), None);
+    assert_eq!(format.format(1.0 / 86400e9), "11.57e-15");
+    assert_eq!(format.format(1536000.0), "1.536e6");
+    assert_eq!(format.format(0.00042), "420e-6");
+    assert_eq!(format.format(42.0), "42e0");
+    // Rounding up to a new power of a thousand moves the exponent
+    assert_eq!(format.format(999999.0), "1e6");
+
+    let format = number_format(Notation::Engineering, Some(1), None, None);
+    assert_eq!(format.format(12345.0), "12.3e3");
+}
+
+#[test]
+fn test_number_format_grouping() {
+    let format = number_format(Notation::Fixed, None, None, Some('_'));
+    assert_eq!(format.format(1536000.0), "1_536_000");
+    assert_eq!(format.format(-1234.5), "-1_234.5");
+    assert_eq!(format.format(999.0), "999");
+
+    let format = number_format(Notation::Fixed, Some(2), None, Some(','));
+    assert_eq!(format.format(1234567.891), "1,234,567.89");
+}
+
+#[test]
+fn test_number_format_non_numbers_unchanged() {
+    let format = number_format(Notation::Scientific, None, Some(3), None);
+    assert_eq!(format.format(f64::INFINITY), "inf");
+    assert_eq!(format.format(f64::NAN), "NaN");
+    assert_eq!(format.format_str("abc"), "abc");
+}
+
+#[test]
+fn test_context_with_format() {
+    let format = number_format(Notation::Fixed, None, Some(4), Some(','));
+    let mut context = calculator::Context::new().with_format(format);
+    assert_eq!(context.evaluate("2 ^ 40").unwrap(), "1,100,000,000,000");
+    assert_eq!(context.evaluate("1 / 3").unwrap(), "0.3333");
+
+    let format = number_format(Notation::Scientific, None, None, None);
+    let mut context = calculator::Context::exact(20).with_format(format);
+    assert_eq!(context.evaluate("0.1 + 0.2").unwrap(), "3e-1");
+}
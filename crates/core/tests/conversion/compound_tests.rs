@@ -0,0 +1,124 @@
+use strapd_core::conversion::compound::{parse_target, split};
+use strapd_core::conversion::engine::{convert, convert_input};
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::parser::parse_input;
+use strapd_core::conversion::types::{ConversionRequest, find_unit};
+
+fn convert_and_format(input: &str) -> String {
+    let result = convert_input(input).unwrap();
+    format_output(&[result], None).unwrap()
+}
+
+// ============================================================================
+// Mixed-Unit Input
+// ============================================================================
+
+#[test]
+fn test_mixed_units_input() {
+    let request = parse_input("5 ft 11 in to cm").unwrap();
+    assert_eq!(request.from_unit, "ft");
+    assert!((request.value - 5.916_666_7).abs() < 0.0001);
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 180.34).abs() < 0.0001);
+}
+
+#[test]
+fn test_mixed_units_concatenated() {
+    let request = parse_input("5ft 11in to cm").unwrap();
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 180.34).abs() < 0.0001);
+}
+
+#[test]
+fn test_mixed_units_with_in_separator() {
+    let request = parse_input("5 ft 11 in in cm").unwrap();
+    assert_eq!(request.to_unit, Some("cm".to_string()));
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 180.34).abs() < 0.0001);
+}
+
+#[test]
+fn test_inches_are_not_a_separator() {
+    let request = parse_input("10 in to cm").unwrap();
+    assert_eq!(request.from_unit, "in");
+    assert_eq!(request.to_unit, Some("cm".to_string()));
+}
+
+#[test]
+fn test_mixed_units_different_categories() {
+    let result = parse_input("5 ft 3 kg to cm");
+    assert!(
+        result
+            .unwrap_err()
+            .contains("Cannot combine 'ft' (Length) and 'kg' (Mass)")
+    );
+}
+
+// ============================================================================
+// Compound Targets
+// ============================================================================
+
+#[test]
+fn test_compound_target_length() {
+    assert_eq!(convert_and_format("180 cm to ft+in"), "5 ft 10.87 in");
+}
+
+#[test]
+fn test_compound_target_time() {
+    assert_eq!(convert_and_format("5415 s to h+min+s"), "1 h 30 min 15 s");
+    assert_eq!(convert_and_format("50 h to d+h"), "2 d 2 h");
+}
+
+#[test]
+fn test_compound_target_remainder_does_not_round_up() {
+    assert_eq!(convert_and_format("119.9999 min to h+min"), "2 h 0 min");
+}
+
+#[test]
+fn test_compound_target_negative() {
+    assert_eq!(convert_and_format("-90 min to h+min"), "-1 h 30 min");
+}
+
+#[test]
+fn test_compound_target_from_expression() {
+    assert_eq!(convert_and_format("1 h + 100 min to h+min"), "2 h 40 min");
+}
+
+#[test]
+fn test_compound_target_with_mixed_input() {
+    assert_eq!(convert_and_format("5 ft 14 in to ft+in"), "6 ft 2 in");
+}
+
+#[test]
+fn test_compound_target_precision() {
+    let request = ConversionRequest {
+        value: 180.0,
+        from_unit: "cm".to_string(),
+        to_unit: Some("ft+in".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert_eq!(format_output(&[result], Some(0)).unwrap(), "5 ft 11 in");
+}
+
+#[test]
+fn test_compound_target_errors() {
+    assert!(
+        parse_target("in+ft")
+            .unwrap_err()
+            .contains("largest to smallest")
+    );
+    assert!(
+        parse_target("ft+kg")
+            .unwrap_err()
+            .contains("Cannot combine")
+    );
+    assert!(parse_target("ft+xyz").unwrap_err().contains("Unknown unit"));
+    assert!(parse_target("c+f").unwrap_err().contains("cannot be used"));
+    assert!(convert_input("10 kg to ft+in").is_err());
+}
+
+#[test]
+fn test_split() {
+    let units = [find_unit("h").unwrap(), find_unit("min").unwrap()];
+    assert_eq!(split(1.75, &units, 2), vec![1.0, 45.0]);
+}
@@ -1,5 +1,5 @@
 use strapd_core::conversion::engine::convert;
-use strapd_core::conversion::types::ConversionRequest;
+use strapd_core::conversion::types::{ConversionRequest, find_unit};
 
 // ============================================================================
 // Length Conversion Tests
@@ -55,18 +55,45 @@ fn test_convert_in_to_cm() {
 
 #[test]
 fn test_convert_bytes_to_kb() {
+    // kB is SI (1000-based)
     let request = ConversionRequest {
-        value: 1024.0,
+        value: 1000.0,
         from_unit: "bytes".to_string(),
-        to_unit: Some("kb".to_string()),
+        to_unit: Some("kB".to_string()),
     };
     let result = convert(&request).unwrap();
     assert!((result.output_value - 1.0).abs() < 0.0001);
 }
 
+#[test]
+fn test_convert_megabits_to_megabytes() {
+    // Lowercase b is a bit, uppercase B is a byte
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "Mb".to_string(),
+        to_unit: Some("MB".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 0.125).abs() < 1e-12);
+    assert_eq!(result.output_unit, "MB");
+}
+
+#[test]
+fn test_find_unit_case_keeps_bits_and_bytes_apart() {
+    assert_eq!(find_unit("kb").unwrap().canonical_name, "kbit");
+    assert_eq!(find_unit("KB").unwrap().canonical_name, "kB");
+    assert_eq!(find_unit("Gb").unwrap().canonical_name, "Gbit");
+    assert_eq!(find_unit("gB").unwrap().canonical_name, "GB");
+    assert_eq!(find_unit("MB/S").unwrap().canonical_name, "MB/s");
+    assert_eq!(find_unit("mb/s").unwrap().canonical_name, "Mbps");
+    assert_eq!(find_unit("MEGABYTES").unwrap().canonical_name, "MB");
+    assert_eq!(find_unit("KIB").unwrap().canonical_name, "KiB");
+    assert_eq!(find_unit("LBS").unwrap().canonical_name, "lb");
+}
+
 #[test]
 fn test_convert_bytes_to_kib() {
-    // kb and kib are now aliases - both use 1024-based
+    // KiB is IEC (1024-based)
     let request = ConversionRequest {
         value: 1024.0,
         from_unit: "bytes".to_string(),
@@ -80,12 +107,55 @@ fn test_convert_bytes_to_kib() {
 fn test_convert_gb_to_mb() {
     let request = ConversionRequest {
         value: 1.5,
-        from_unit: "gb".to_string(),
-        to_unit: Some("mb".to_string()),
+        from_unit: "GB".to_string(),
+        to_unit: Some("MB".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    // 1.5 GB = 1.5 * 1000 MB = 1500 MB (1000-based)
+    assert!((result.output_value - 1500.0).abs() < 0.01);
+}
+
+#[test]
+fn test_convert_gb_to_gib() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "GB".to_string(),
+        to_unit: Some("GiB".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 0.931).abs() < 0.001);
+}
+
+#[test]
+fn test_convert_tib_to_tb() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "TiB".to_string(),
+        to_unit: Some("TB".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 1.0995).abs() < 0.0001);
+}
+
+#[test]
+fn test_byte_and_bit_case_rules() {
+    // B is a byte, b is a bit
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "B".to_string(),
+        to_unit: Some("b".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 8.0).abs() < 0.0001);
+
+    // IEC units are recognized in any case
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "GIB".to_string(),
+        to_unit: Some("mib".to_string()),
     };
     let result = convert(&request).unwrap();
-    // 1.5 GB = 1.5 * 1024 MB = 1536 MB (1024-based)
-    assert!((result.output_value - 1536.0).abs() < 0.01);
+    assert!((result.output_value - 1024.0).abs() < 0.0001);
 }
 
 #[test]
@@ -216,3 +286,257 @@ fn test_convert_negative_temperature() {
     let result = convert(&request).unwrap();
     assert!((result.output_value - (-40.0)).abs() < 0.01);
 }
+
+// ============================================================================
+// Data Rate Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_mbps_to_mib_per_second() {
+    let request = ConversionRequest {
+        value: 100.0,
+        from_unit: "Mbps".to_string(),
+        to_unit: Some("MiB/s".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 11.920929).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_gbps_to_mbps() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "Gbps".to_string(),
+        to_unit: Some("Mbps".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 1000.0).abs() < 0.0001);
+}
+
+#[test]
+fn test_data_rate_bits_and_bytes_are_case_sensitive() {
+    // Mb/s is megabits, MB/s is megabytes
+    let request = ConversionRequest {
+        value: 8.0,
+        from_unit: "Mb/s".to_string(),
+        to_unit: Some("kbps".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 8000.0).abs() < 0.0001);
+
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "MB/s".to_string(),
+        to_unit: Some("Mbps".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 8.0).abs() < 0.0001);
+}
+
+// ============================================================================
+// Mass Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_kg_to_lb() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "kg".to_string(),
+        to_unit: Some("lb".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 2.20462).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_ounces_to_grams() {
+    let request = ConversionRequest {
+        value: 16.0,
+        from_unit: "ounces".to_string(),
+        to_unit: Some("g".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 453.59237).abs() < 0.0001);
+}
+
+// ============================================================================
+// Volume Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_gallons_to_liters() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "gal".to_string(),
+        to_unit: Some("L".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 3.785411784).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_m3_to_ml() {
+    let request = ConversionRequest {
+        value: 0.5,
+        from_unit: "m³".to_string(),
+        to_unit: Some("ml".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 500_000.0).abs() < 0.001);
+}
+
+// ============================================================================
+// Area Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_acre_to_m2() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "acre".to_string(),
+        to_unit: Some("m2".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 4046.8564224).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_hectares_to_square_feet() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "ha".to_string(),
+        to_unit: Some("sqft".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 107_639.104).abs() < 0.001);
+}
+
+// ============================================================================
+// Speed Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_kmh_to_mph() {
+    let request = ConversionRequest {
+        value: 100.0,
+        from_unit: "km/h".to_string(),
+        to_unit: Some("mph".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 62.1371).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_knots_to_mps() {
+    let request = ConversionRequest {
+        value: 10.0,
+        from_unit: "knots".to_string(),
+        to_unit: Some("m/s".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 5.14444).abs() < 0.0001);
+}
+
+// ============================================================================
+// Angle Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_degrees_to_radians() {
+    let request = ConversionRequest {
+        value: 180.0,
+        from_unit: "deg".to_string(),
+        to_unit: Some("rad".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - std::f64::consts::PI).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_turn_to_gradians() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "turn".to_string(),
+        to_unit: Some("grad".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 400.0).abs() < 0.0001);
+}
+
+// ============================================================================
+// Energy Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_kwh_to_mj() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "kWh".to_string(),
+        to_unit: Some("MJ".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 3.6).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_kcal_to_kj() {
+    let request = ConversionRequest {
+        value: 100.0,
+        from_unit: "kcal".to_string(),
+        to_unit: Some("kJ".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 418.4).abs() < 0.0001);
+}
+
+// ============================================================================
+// Pressure Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_atm_to_kpa() {
+    let request = ConversionRequest {
+        value: 1.0,
+        from_unit: "atm".to_string(),
+        to_unit: Some("kPa".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 101.325).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_bar_to_psi() {
+    let request = ConversionRequest {
+        value: 2.0,
+        from_unit: "bar".to_string(),
+        to_unit: Some("psi".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 29.0075).abs() < 0.0001);
+}
+
+// ============================================================================
+// Frequency Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_convert_ghz_to_mhz() {
+    let request = ConversionRequest {
+        value: 2.4,
+        from_unit: "GHz".to_string(),
+        to_unit: Some("MHz".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 2400.0).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_rpm_to_hz() {
+    let request = ConversionRequest {
+        value: 3000.0,
+        from_unit: "rpm".to_string(),
+        to_unit: Some("Hz".to_string()),
+    };
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 50.0).abs() < 0.0001);
+}
@@ -0,0 +1,128 @@
+use strapd_core::conversion::currency::{
+    RateTable, missing_currency_error, parse_csv, parse_json, rates, set_rates,
+};
+use strapd_core::conversion::engine::{convert_input, convert_to_all};
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::types::{UnitCategory, find_unit};
+
+fn convert_and_format(input: &str) -> String {
+    let result = convert_input(input).unwrap();
+    format_output(&[result], None).unwrap()
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_json_rates() {
+    let table =
+        parse_json(r#"{"base": "usd", "date": "2026-10-01", "rates": {"eur": 0.92, "GBP": 0.79}}"#)
+            .unwrap();
+    assert_eq!(table.base, "USD");
+    assert_eq!(table.date, "2026-10-01");
+    // Codes are uppercased and the base is added at 1
+    let codes: Vec<(&str, f64)> = table.rates.iter().map(|(c, r)| (c.as_str(), *r)).collect();
+    assert_eq!(codes, vec![("EUR", 0.92), ("GBP", 0.79), ("USD", 1.0)]);
+}
+
+#[test]
+fn test_parse_csv_rates() {
+    let table =
+        parse_csv("date,base,currency,rate\n2026-10-01,EUR,USD,1.087\n2026-10-01,EUR,JPY,162.5\n")
+            .unwrap();
+    assert_eq!(table.base, "EUR");
+    assert_eq!(table.date, "2026-10-01");
+    assert_eq!(table.rates.len(), 3);
+    assert_eq!(table.rates["JPY"], 162.5);
+
+    // The header is optional
+    assert!(parse_csv("2026-10-01,EUR,USD,1.087").is_ok());
+}
+
+#[test]
+fn test_parse_rates_errors() {
+    assert_eq!(
+        parse_json(r#"{"base": "USD", "rates": {"EUR": 0.92}}"#).unwrap_err(),
+        "Invalid rates file: 'date' must be a string"
+    );
+    assert_eq!(
+        parse_json(r#"{"base": "USD", "date": "01/10/2026", "rates": {"EUR": 0.92}}"#).unwrap_err(),
+        "Invalid rates file: date '01/10/2026' should be YYYY-MM-DD"
+    );
+    assert_eq!(
+        parse_json(r#"{"base": "USD", "date": "2026-10-01", "rates": {"EURO": 0.92}}"#)
+            .unwrap_err(),
+        "Invalid rates file: 'EURO' is not a three-letter currency code"
+    );
+    assert_eq!(
+        parse_json(r#"{"base": "USD", "date": "2026-10-01", "rates": {"EUR": -1}}"#).unwrap_err(),
+        "Invalid rates file: rate of 'EUR' must be a positive number"
+    );
+    assert_eq!(
+        parse_csv("2026-10-01,USD,EUR,0.92\n2026-10-02,USD,GBP,0.79").unwrap_err(),
+        "Invalid rates file: line 2 has base USD on 2026-10-02, but the table is base USD on 2026-10-01"
+    );
+    assert_eq!(
+        parse_csv("2026-10-01,USD,EUR").unwrap_err(),
+        "Invalid rates file: line 1 should be 'date,base,currency,rate'"
+    );
+    assert_eq!(
+        parse_csv("").unwrap_err(),
+        "Invalid rates file: no rates found"
+    );
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+// The rate table is global, so everything that sets it runs in one test
+#[test]
+fn test_currency_conversion_with_rate_table() {
+    // Without a table, currency codes explain what is missing
+    assert_eq!(missing_currency_error("km"), None);
+    if rates().is_none() {
+        assert!(
+            convert_input("120 usd to eur")
+                .unwrap_err()
+                .starts_with("No currency rates configured for 'usd'")
+        );
+    }
+
+    set_rates(
+        parse_json(
+            r#"{"base": "USD", "date": "2026-10-01", "rates": {"EUR": 0.92, "GBP": 0.8, "CUP": 24}}"#,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(convert_and_format("120 usd to eur"), "110.4 eur");
+    assert_eq!(convert_and_format("92 EUR to USD"), "100 USD");
+    // Through the base currency: 46 EUR = 50 USD = 40 GBP
+    assert_eq!(convert_and_format("46 eur to gbp"), "40 gbp");
+    assert_eq!(convert_and_format("10 usd + 46 eur to usd"), "60 usd");
+    assert_eq!(convert_to_all(1.0, "USD").unwrap().len(), 3);
+
+    // Lowercase `cup` stays a volume while `CUP` is the Cuban peso
+    assert_eq!(find_unit("CUP").unwrap().category, UnitCategory::Currency);
+    assert_eq!(find_unit("cup").unwrap().category, UnitCategory::Volume);
+
+    assert_eq!(
+        convert_input("1 usd to jpy").unwrap_err(),
+        "No rate for JPY in the rates table (base USD, 2026-10-01)"
+    );
+
+    // A new table replaces the old one
+    let mut rates = std::collections::BTreeMap::new();
+    rates.insert("JPY".to_string(), 160.0);
+    set_rates(RateTable {
+        base: "EUR".to_string(),
+        date: "2026-10-02".to_string(),
+        rates,
+    })
+    .unwrap();
+    assert_eq!(convert_and_format("1 eur to jpy"), "160 jpy");
+    assert!(find_unit("GBP").is_none());
+}
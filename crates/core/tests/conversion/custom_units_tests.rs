@@ -0,0 +1,228 @@
+use strapd_core::conversion::custom::{CustomUnit, parse_json, parse_toml, register};
+use strapd_core::conversion::engine::convert_input;
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::types::{UnitCategory, find_unit, get_units_in_category};
+
+// Units are registered globally, so every test uses its own unit names and only
+// registers built-in categories whose full unit list no other test checks
+
+fn convert_and_format(input: &str) -> String {
+    let result = convert_input(input).unwrap();
+    format_output(&[result], None).unwrap()
+}
+
+fn unit(name: &str, category: &str, multiplier: f64, offset: f64) -> CustomUnit {
+    CustomUnit {
+        name: name.to_string(),
+        aliases: Vec::new(),
+        category: category.to_string(),
+        multiplier,
+        offset,
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_toml_units_file() {
+    let units = parse_toml(
+        r#"
+        [[units]]
+        name = "furlong"
+        aliases = ["furlongs", "fur"]
+        category = "length"
+        multiplier = 201.168
+
+        [[units]]
+        name = "rankine"
+        category = "temperature"
+        multiplier = 0.5555555555555556
+        offset = 0
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(units.len(), 2);
+    assert_eq!(units[0].name, "furlong");
+    assert_eq!(units[0].aliases, vec!["furlongs", "fur"]);
+    assert_eq!(units[0].category, "length");
+    assert_eq!(units[0].multiplier, 201.168);
+    assert_eq!(units[0].offset, 0.0);
+    assert_eq!(units[1].name, "rankine");
+}
+
+#[test]
+fn test_parse_json_units_file() {
+    let units = parse_json(
+        r#"{"units": [{"name": "barg", "category": "pressure", "multiplier": 100000, "offset": 101325}]}"#,
+    )
+    .unwrap();
+    assert_eq!(units, vec![unit("barg", "pressure", 100000.0, 101325.0)]);
+}
+
+#[test]
+fn test_parse_units_file_errors() {
+    assert_eq!(
+        parse_toml("name = \"furlong\"").unwrap_err(),
+        "Invalid units file: expected a 'units' list"
+    );
+    assert_eq!(
+        parse_toml("[[units]]\nname = \"furlong\"\ncategory = \"length\"").unwrap_err(),
+        "Invalid unit #1 in units file: 'multiplier' is required"
+    );
+    assert_eq!(
+        parse_json(r#"{"units": [{"name": "x", "category": "length", "multiplier": "1"}]}"#)
+            .unwrap_err(),
+        "Invalid unit #1 in units file: 'multiplier' must be a number"
+    );
+    assert!(
+        parse_toml("[[units]\n")
+            .unwrap_err()
+            .starts_with("Invalid units file:")
+    );
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+#[test]
+fn test_custom_unit_in_builtin_category() {
+    let units = parse_toml(
+        "[[units]]\nname = \"pennyweight\"\naliases = [\"dwt\"]\ncategory = \"mass\"\nmultiplier = 0.00155517384",
+    )
+    .unwrap();
+    assert_eq!(register(&units).unwrap(), 1);
+
+    assert_eq!(convert_and_format("1 pennyweight to g"), "1.56 g");
+    assert_eq!(convert_and_format("20 dwt to oz"), "1.1 oz");
+    // Linear custom units work in arithmetic like built-in ones
+    assert_eq!(convert_and_format("2 dwt + 1 g to g"), "4.11 g");
+}
+
+#[test]
+fn test_custom_temperature_unit() {
+    let mut rankine = unit("rankine", "temperature", 5.0 / 9.0, 0.0);
+    rankine.aliases = vec!["degR".to_string()];
+    register(&[rankine]).unwrap();
+
+    assert_eq!(convert_and_format("491.67 degR to c"), "0 c");
+    assert_eq!(convert_and_format("100 c to rankine"), "671.67 rankine");
+    assert_eq!(convert_and_format("0 k to degR"), "0 degR");
+}
+
+#[test]
+fn test_custom_offset_unit() {
+    register(&[unit("psig", "pressure", 6894.757293168361, 101325.0)]).unwrap();
+
+    assert_eq!(convert_and_format("0 psig to Pa"), "101325 Pa");
+    assert_eq!(convert_and_format("101325 Pa to psig"), "0 psig");
+    assert_eq!(convert_and_format("20 psig to psi"), "34.7 psi");
+    // Offset units are formula-based and cannot be used in arithmetic
+    assert!(convert_input("1 psig + 1 psi to psi").is_err());
+}
+
+#[test]
+fn test_custom_category() {
+    register(&[
+        unit("storypoint", "Effort", 1.0, 0.0),
+        unit("sprint", "effort", 20.0, 0.0),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        convert_and_format("3 sprint to storypoint"),
+        "60 storypoint"
+    );
+
+    let category = UnitCategory::from_name("EFFORT").unwrap();
+    assert_eq!(category, UnitCategory::Custom("Effort"));
+    assert_eq!(category.to_string(), "Effort");
+    let names: Vec<&str> = get_units_in_category(category)
+        .iter()
+        .map(|u| u.canonical_name)
+        .collect();
+    assert_eq!(names, vec!["storypoint", "sprint"]);
+
+    let error = convert_input("1 sprint to m").unwrap_err();
+    assert!(
+        error.contains("'sprint' (Effort) to 'm' (Length)"),
+        "{}",
+        error
+    );
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[test]
+fn test_register_rejects_builtin_collisions() {
+    assert_eq!(
+        register(&[unit("ft", "length", 0.3, 0.0)]).unwrap_err(),
+        "Unit name 'ft' of 'ft' collides with built-in unit 'ft'"
+    );
+
+    // Case-insensitive matches of built-in names collide too
+    let mut league = unit("league", "length", 4828.032, 0.0);
+    league.aliases = vec!["KM".to_string()];
+    assert_eq!(
+        register(&[league]).unwrap_err(),
+        "Unit name 'KM' of 'league' collides with built-in unit 'km'"
+    );
+    assert!(find_unit("league").is_none());
+}
+
+#[test]
+fn test_register_rejects_duplicates_in_file() {
+    let mut cubit = unit("cubit", "length", 0.4572, 0.0);
+    cubit.aliases = vec!["cbt".to_string()];
+    let mut cubit_royal = unit("royalcubit", "length", 0.525, 0.0);
+    cubit_royal.aliases = vec!["CBT".to_string()];
+
+    assert_eq!(
+        register(&[cubit, cubit_royal]).unwrap_err(),
+        "Unit name 'CBT' is used by both 'cubit' and 'royalcubit' in the units file"
+    );
+    // Nothing is registered when any unit is invalid
+    assert!(find_unit("cubit").is_none());
+}
+
+#[test]
+fn test_register_same_definition_twice() {
+    let firkin = unit("firkin", "volume", 0.040914816, 0.0);
+    assert_eq!(register(std::slice::from_ref(&firkin)).unwrap(), 1);
+    assert_eq!(register(&[firkin]).unwrap(), 0);
+
+    // A different definition under the same name is a collision
+    assert_eq!(
+        register(&[unit("firkin", "volume", 0.034068706, 0.0)]).unwrap_err(),
+        "Unit name 'firkin' of 'firkin' collides with custom unit 'firkin'"
+    );
+}
+
+#[test]
+fn test_register_rejects_invalid_definitions() {
+    assert_eq!(
+        register(&[unit("to", "length", 1.0, 0.0)]).unwrap_err(),
+        "Unit name 'to' is reserved"
+    );
+    assert_eq!(
+        register(&[unit("two feet", "length", 0.6, 0.0)]).unwrap_err(),
+        "Invalid unit name 'two feet'"
+    );
+    assert_eq!(
+        register(&[unit("2ft", "length", 0.6, 0.0)]).unwrap_err(),
+        "Invalid unit name '2ft'"
+    );
+    assert_eq!(
+        register(&[unit("span", "length", 0.0, 0.0)]).unwrap_err(),
+        "Multiplier of unit 'span' must be a positive number"
+    );
+    assert_eq!(
+        register(&[unit("span", "", 0.2286, 0.0)]).unwrap_err(),
+        "Unit 'span' needs a category"
+    );
+}
@@ -0,0 +1,175 @@
+use strapd_core::conversion::duration::{
+    DurationFormat, DurationPart, format_human, format_iso8601, parse_calendar_duration,
+    parse_duration,
+};
+use strapd_core::conversion::engine::convert;
+use strapd_core::conversion::expression::evaluate;
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::parser::parse_input;
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_compound_duration() {
+    assert_eq!(parse_duration("1h30m15s"), Ok(5415.0));
+    assert_eq!(parse_duration("1h 30m 15s"), Ok(5415.0));
+    assert_eq!(parse_duration("250ms"), Ok(0.25));
+    assert_eq!(parse_duration("1d12h"), Ok(129_600.0));
+    assert_eq!(parse_duration("1.5h"), Ok(5400.0));
+    assert_eq!(parse_duration("-2m"), Ok(-120.0));
+}
+
+#[test]
+fn test_parse_compound_duration_long_names() {
+    assert_eq!(parse_duration("1 hour 30 minutes"), Ok(5400.0));
+}
+
+#[test]
+fn test_parse_iso8601_duration() {
+    assert_eq!(parse_duration("PT1H30M"), Ok(5400.0));
+    assert_eq!(parse_duration("P1DT2H"), Ok(93_600.0));
+    assert_eq!(parse_duration("P2W"), Ok(1_209_600.0));
+    assert_eq!(parse_duration("PT0.5S"), Ok(0.5));
+    assert_eq!(parse_duration("pt90m"), Ok(5400.0));
+}
+
+#[test]
+fn test_parse_duration_errors() {
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("1h30").unwrap_err().contains("missing unit"));
+    assert!(
+        parse_duration("5km")
+            .unwrap_err()
+            .contains("unknown time unit")
+    );
+    assert!(
+        parse_duration("P1M")
+            .unwrap_err()
+            .contains("no fixed length")
+    );
+    assert!(parse_duration("P1DT").is_err());
+    assert!(
+        parse_duration("PT5")
+            .unwrap_err()
+            .contains("missing designator")
+    );
+}
+
+#[test]
+fn test_parse_duration_separators_and_weeks() {
+    assert_eq!(parse_duration("2w"), Ok(1_209_600.0));
+    assert_eq!(parse_duration("1h, 30m and 15s"), Ok(5415.0));
+    assert!(
+        parse_duration("1y")
+            .unwrap_err()
+            .contains("no fixed length")
+    );
+}
+
+#[test]
+fn test_parse_calendar_duration() {
+    assert_eq!(
+        parse_calendar_duration("1y 2mo 3w 4d 5h"),
+        Ok(vec![
+            DurationPart::Months(12.0),
+            DurationPart::Months(2.0),
+            DurationPart::Days(21.0),
+            DurationPart::Days(4.0),
+            DurationPart::Seconds(18_000.0),
+        ])
+    );
+    assert_eq!(
+        parse_calendar_duration("-P1MT1M"),
+        Ok(vec![
+            DurationPart::Months(-1.0),
+            DurationPart::Seconds(-60.0)
+        ])
+    );
+    assert!(
+        parse_calendar_duration("1.5d")
+            .unwrap_err()
+            .contains("must be whole numbers")
+    );
+}
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+#[test]
+fn test_format_human() {
+    assert_eq!(format_human(5415.0), "1h 30m 15s");
+    assert_eq!(format_human(93_600.0), "1d 2h");
+    assert_eq!(format_human(1.25), "1s 250ms");
+    assert_eq!(format_human(0.0), "0s");
+    assert_eq!(format_human(-90.0), "-1m 30s");
+}
+
+#[test]
+fn test_format_iso8601() {
+    assert_eq!(format_iso8601(5415.0), "PT1H30M15S");
+    assert_eq!(format_iso8601(93_600.0), "P1DT2H");
+    assert_eq!(format_iso8601(86_400.0), "P1D");
+    assert_eq!(format_iso8601(0.25), "PT0.25S");
+    assert_eq!(format_iso8601(0.0), "PT0S");
+}
+
+#[test]
+fn test_duration_format_names() {
+    assert_eq!(
+        DurationFormat::from_name("human"),
+        Some(DurationFormat::Human)
+    );
+    assert_eq!(
+        DurationFormat::from_name("ISO"),
+        Some(DurationFormat::Iso8601)
+    );
+    assert_eq!(
+        DurationFormat::from_name("iso8601"),
+        Some(DurationFormat::Iso8601)
+    );
+    assert_eq!(DurationFormat::from_name("s"), None);
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+#[test]
+fn test_convert_compound_duration_to_unit() {
+    let request = parse_input("1h30m15s to min").unwrap();
+    assert_eq!(request.from_unit, "s");
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 90.25).abs() < 0.0001);
+
+    let request = parse_input("P1DT2H to h").unwrap();
+    let result = convert(&request).unwrap();
+    assert!((result.output_value - 26.0).abs() < 0.0001);
+}
+
+#[test]
+fn test_convert_to_human_and_iso() {
+    let request = parse_input("5415 s to human").unwrap();
+    let result = convert(&request).unwrap();
+    assert_eq!(format_output(&[result], None).unwrap(), "1h 30m 15s");
+
+    let request = parse_input("1500 ms to iso").unwrap();
+    let result = convert(&request).unwrap();
+    assert_eq!(format_output(&[result], None).unwrap(), "PT1.5S");
+}
+
+#[test]
+fn test_convert_to_human_requires_time_unit() {
+    let request = parse_input("10 km to human").unwrap();
+    assert!(convert(&request).unwrap_err().contains("as a duration"));
+}
+
+#[test]
+fn test_expression_to_human() {
+    let result = evaluate("1 h + 45 min + 30 s to human").unwrap();
+    assert_eq!(format_output(&[result], None).unwrap(), "1h 45m 30s");
+
+    assert!(evaluate("10 GiB / 2 s to human").is_err());
+}
@@ -1,4 +1,5 @@
 use strapd_core::conversion::engine::convert;
+use strapd_core::conversion::suggestions::suggest_units;
 use strapd_core::conversion::types::ConversionRequest;
 
 #[test]
@@ -36,3 +37,48 @@ fn test_convert_unknown_unit_error() {
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Unknown"));
 }
+
+#[test]
+fn test_convert_unknown_unit_suggestions() {
+    let request = ConversionRequest {
+        value: 10.0,
+        from_unit: "kms".to_string(),
+        to_unit: Some("mi".to_string()),
+    };
+    let error = convert(&request).unwrap_err();
+    assert!(error.starts_with("Unknown source unit: 'kms'. Did you mean 'km'"));
+    assert!(error.ends_with('?'));
+}
+
+#[test]
+fn test_convert_unknown_target_prefix_suggestion() {
+    let request = ConversionRequest {
+        value: 10.0,
+        from_unit: "km".to_string(),
+        to_unit: Some("kilomet".to_string()),
+    };
+    let error = convert(&request).unwrap_err();
+    assert_eq!(
+        error,
+        "Unknown target unit: 'kilomet'. Did you mean 'kilometer' or 'kilobit'?"
+    );
+}
+
+#[test]
+fn test_convert_cross_category_lists_valid_units() {
+    let request = ConversionRequest {
+        value: 10.0,
+        from_unit: "km".to_string(),
+        to_unit: Some("kg".to_string()),
+    };
+    let error = convert(&request).unwrap_err();
+    assert!(error.ends_with("Valid Length units: mm, cm, in, ft, yd, m, km, mi"));
+}
+
+#[test]
+fn test_suggest_units() {
+    assert_eq!(suggest_units("gibs"), vec!["GiB", "GiB/s"]);
+    assert_eq!(suggest_units("mnute").first(), Some(&"minute"));
+    assert!(suggest_units("zzzzzz").is_empty());
+    assert!(suggest_units("").is_empty());
+}
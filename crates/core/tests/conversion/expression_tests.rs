@@ -0,0 +1,304 @@
+use strapd_core::calculator::Context;
+use strapd_core::conversion::expression::{
+    contains_units, evaluate, evaluate_in, is_expression, misspelled_unit,
+};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+// ============================================================================
+// Same-Category Arithmetic
+// ============================================================================
+
+#[test]
+fn test_add_time_units_to_seconds() {
+    let result = evaluate("1 h + 45 min to s").unwrap();
+    assert_close(result.output_value, 6300.0);
+    assert_eq!(result.output_unit, "s");
+}
+
+#[test]
+fn test_compound_duration_operand() {
+    let result = evaluate("1h30m + 45min to s").unwrap();
+    assert_close(result.output_value, 8100.0);
+    assert_eq!(result.output_unit, "s");
+
+    let result = evaluate("2 * 1h30m15s to min").unwrap();
+    assert_close(result.output_value, 180.5);
+}
+
+#[test]
+fn test_same_unit_arithmetic_stays_exact() {
+    // 0.0254 m per inch is not exact in binary, so the sum stays in inches
+    let result = evaluate("10 in + 2 in").unwrap();
+    assert_eq!(result.output_value, 12.0);
+    assert_eq!(result.output_unit, "in");
+
+    let result = evaluate("10 in - 2 in to in").unwrap();
+    assert_eq!(result.output_value, 8.0);
+
+    let result = evaluate("3 in * 2 in").unwrap();
+    assert_eq!(result.output_value, 6.0);
+    assert_eq!(result.output_unit, "in^2");
+
+    let result = evaluate("10 in + 2 cm").unwrap();
+    assert_close(result.output_value, 10.0 + 2.0 / 2.54);
+}
+
+#[test]
+fn test_single_m_after_number_is_meters() {
+    let result = evaluate("5m + 50 cm to cm").unwrap();
+    assert_close(result.output_value, 550.0);
+}
+
+#[test]
+fn test_result_uses_first_unit_without_target() {
+    let result = evaluate("1 h + 30 min").unwrap();
+    assert_close(result.output_value, 1.5);
+    assert_eq!(result.output_unit, "h");
+}
+
+#[test]
+fn test_subtract_lengths() {
+    let result = evaluate("1 km - 250 m to m").unwrap();
+    assert_close(result.output_value, 750.0);
+}
+
+#[test]
+fn test_scalar_multiplication_keeps_unit() {
+    let result = evaluate("3 * 512 MiB").unwrap();
+    assert_close(result.output_value, 1536.0);
+    assert_eq!(result.output_unit, "MiB");
+}
+
+#[test]
+fn test_concatenated_value_and_unit() {
+    let result = evaluate("10km + 500m to km").unwrap();
+    assert_close(result.output_value, 10.5);
+}
+
+// ============================================================================
+// Derived Units
+// ============================================================================
+
+#[test]
+fn test_bandwidth() {
+    let result = evaluate("2 GiB / 30 s").unwrap();
+    assert_eq!(result.output_unit, "GiB/s");
+    assert_close(result.output_value, 2.0 / 30.0);
+}
+
+#[test]
+fn test_bandwidth_to_derived_target() {
+    let result = evaluate("2 GiB / 30 s to MiB/s").unwrap();
+    assert_close(result.output_value, 2048.0 / 30.0);
+    assert_eq!(result.output_unit, "MiB/s");
+}
+
+#[test]
+fn test_transfer_time() {
+    // Bytes cancel out, leaving a duration
+    let result = evaluate("500 GiB / (100 MiB/s) to h").unwrap();
+    assert_close(result.output_value, 500.0 * 1024.0 / 100.0 / 3600.0);
+    assert_eq!(result.output_unit, "h");
+}
+
+#[test]
+fn test_rate_times_duration() {
+    let result = evaluate("10 MiB/s * 1 min to GiB").unwrap();
+    assert_close(result.output_value, 600.0 / 1024.0);
+}
+
+#[test]
+fn test_unit_power() {
+    let result = evaluate("3 m^2 to cm^2").unwrap();
+    assert_close(result.output_value, 30000.0);
+    assert_eq!(result.output_unit, "cm^2");
+}
+
+#[test]
+fn test_units_cancel_to_plain_number() {
+    let result = evaluate("1 GiB / 1 MiB").unwrap();
+    assert_close(result.output_value, 1024.0);
+    assert_eq!(result.output_unit, "");
+}
+
+#[test]
+fn test_derived_category_units_in_arithmetic() {
+    // Speed is Length/Time, so a distance over a duration converts to mph
+    let result = evaluate("100 km / 2 h to mph").unwrap();
+    assert_close(result.output_value, 31.068_559_611_866_7);
+    assert_eq!(result.output_unit, "mph");
+
+    // Bit rates are Bytes/Time
+    let result = evaluate("1 Gbps * 1 min to GiB").unwrap();
+    assert_close(result.output_value, 7.5e9 / 1_073_741_824.0);
+
+    // Frequency is 1/Time
+    let result = evaluate("1 / 50 Hz to ms").unwrap();
+    assert_close(result.output_value, 20.0);
+}
+
+#[test]
+fn test_derived_unit_kept_without_target() {
+    let result = evaluate("60 mph + 10 mph").unwrap();
+    assert_close(result.output_value, 70.0);
+    assert_eq!(result.output_unit, "mph");
+}
+
+#[test]
+fn test_slash_unit_read_as_one_unit() {
+    // `Mb/s` is megabits per second, not megabytes per second
+    let result = evaluate("2 * 8 Mb/s to MB/s").unwrap();
+    assert_close(result.output_value, 2.0);
+    assert_eq!(result.output_unit, "MB/s");
+}
+
+#[test]
+fn test_calculator_literals_and_functions() {
+    let result = evaluate("0x10 * 1_000 m to km").unwrap();
+    assert_close(result.output_value, 16.0);
+
+    let result = evaluate("sqrt(16) * 1 h to min").unwrap();
+    assert_close(result.output_value, 240.0);
+
+    let err = evaluate("sqrt(4 m)").unwrap_err();
+    assert_eq!(err, "sqrt() needs plain numbers, not m");
+}
+
+// ============================================================================
+// Shared Calculator Context
+// ============================================================================
+
+#[test]
+fn test_unit_result_becomes_ans() {
+    let mut context = Context::new();
+    let result = evaluate_in(&mut context, "2 GiB / 30 s").unwrap();
+    assert_eq!(result.output_unit, "GiB/s");
+
+    let result = evaluate_in(&mut context, "ans * 1 min to GiB").unwrap();
+    assert_close(result.output_value, 4.0);
+}
+
+#[test]
+fn test_variables_in_unit_expressions() {
+    let mut context = Context::new();
+    context.evaluate("files = 3").unwrap();
+    let result = evaluate_in(&mut context, "files * 512 MiB to GiB").unwrap();
+    assert_close(result.output_value, 1.5);
+
+    evaluate_in(&mut context, "d = 5 km").unwrap();
+    let result = evaluate_in(&mut context, "d + 500 m to m").unwrap();
+    assert_close(result.output_value, 5500.0);
+    assert_eq!(
+        context.variables()[0],
+        ("d".to_string(), "5 km".to_string())
+    );
+}
+
+#[test]
+fn test_error_span_points_at_unit() {
+    let err = evaluate_in(&mut Context::new(), "2 GiB + 30 s").unwrap_err();
+    assert_eq!(err.span(), Some(6..7));
+
+    let err = evaluate_in(&mut Context::new(), "5 parsecs + 1 km").unwrap_err();
+    assert_eq!(err.span(), Some(2..9));
+}
+
+// ============================================================================
+// Dimension Errors
+// ============================================================================
+
+#[test]
+fn test_error_add_different_categories() {
+    let err = evaluate("2 GiB + 30 s").unwrap_err();
+    assert!(err.contains("Cannot add"));
+    assert!(err.contains("Bytes"));
+    assert!(err.contains("Time"));
+}
+
+#[test]
+fn test_error_add_number_to_quantity() {
+    let err = evaluate("5 km + 3").unwrap_err();
+    assert!(err.contains("dimensionless"));
+}
+
+#[test]
+fn test_error_target_dimension_mismatch() {
+    let err = evaluate("2 GiB / 30 s to h").unwrap_err();
+    assert!(err.contains("Cannot convert GiB/s (Bytes/Time) to h (Time)"));
+}
+
+#[test]
+fn test_error_temperature_arithmetic() {
+    let err = evaluate("10 c + 5 c").unwrap_err();
+    assert!(err.contains("cannot be used in arithmetic"));
+}
+
+#[test]
+fn test_error_unknown_unit() {
+    let err = evaluate("5 parsecs + 1 km").unwrap_err();
+    assert_eq!(err, "Unknown unit: 'parsecs'. Please check unit spelling.");
+}
+
+#[test]
+fn test_misspelled_unit() {
+    let misspelled = |input: &str| {
+        let error = Context::new().evaluate(input).unwrap_err();
+        misspelled_unit(input, &error).map(|error| (error.to_string(), error.span()))
+    };
+    assert_eq!(
+        misspelled("5 kms to m"),
+        Some((
+            "Unknown unit: 'kms'. Did you mean 'km', 'ms' or 'ems'?".to_string(),
+            Some(2..5)
+        ))
+    );
+    assert!(misspelled("kms to m").is_some());
+    assert!(misspelled("foo + 1").is_none());
+    assert!(misspelled("2 * foo").is_none());
+    assert!(misspelled("tomato + 1").is_none());
+}
+
+#[test]
+fn test_error_missing_target() {
+    let err = evaluate("1 h + 1 min to").unwrap_err();
+    assert!(err.contains("Missing target unit"));
+}
+
+#[test]
+fn test_error_mismatched_parentheses() {
+    assert!(evaluate("(1 h + 1 min").is_err());
+    assert!(evaluate("1 h + 1 min)").is_err());
+}
+
+// ============================================================================
+// Detection
+// ============================================================================
+
+#[test]
+fn test_is_expression() {
+    assert!(is_expression("1 h + 45 min to s"));
+    assert!(is_expression("2 GiB / 30 s"));
+    assert!(!is_expression("10 km to mi"));
+    assert!(!is_expression("-40 c to f"));
+    assert!(is_expression("10 c + 5 c"));
+    assert!(is_expression("sqrt(16) * 1 m"));
+    assert!(!is_expression("1 + 2"));
+}
+
+#[test]
+fn test_contains_units() {
+    let context = Context::new();
+    assert!(contains_units(&context, "2 GiB / 30 s"));
+    assert!(contains_units(&context, "1h30m"));
+    assert!(contains_units(&context, "1h30m to s"));
+    assert!(!contains_units(&context, "2 + 3"));
+    assert!(!contains_units(&context, "sqrt(4)"));
+}
@@ -1,4 +1,6 @@
-use strapd_core::conversion::formatter::format_output;
+use strapd_core::calculator::{Notation, NumberFormat};
+use strapd_core::conversion::engine::{convert_input, convert_to_all};
+use strapd_core::conversion::formatter::{format_output, format_output_with};
 use strapd_core::conversion::types::ConversionResult;
 
 #[test]
@@ -49,3 +51,95 @@ fn test_format_precision_validation() {
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Precision must be between 0 and 10");
 }
+
+#[test]
+fn test_format_groups_si_and_iec_units() {
+    let results = convert_to_all(1.0, "GB").unwrap();
+    let output = format_output(&results, None).unwrap();
+    assert_eq!(
+        output,
+        "8000000000 bit\n1000000000 byte\n\nSI (powers of 1000):\n8000000 kbit\n1000000 kB\n\
+         8000 Mbit\n1000 MB\n8 Gbit\n0.01 Tbit\n0.001 TB\n1e-6 PB\n\nIEC (powers of 1024):\n976562.5 KiB\n953.67 MiB\n0.93 GiB\n\
+         0.0009095 TiB\n8.882e-7 PiB"
+    );
+}
+
+#[test]
+fn test_format_significant_figures() {
+    let result = convert_input("1 ns to day").unwrap();
+    // Tiny values are not rounded away to 0
+    assert_eq!(
+        format_output(std::slice::from_ref(&result), None).unwrap(),
+        "1.157e-14 day"
+    );
+
+    let format = NumberFormat {
+        significant_figures: Some(4),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(&[result], &format).unwrap(),
+        "0.00000000000001157 day"
+    );
+}
+
+#[test]
+fn test_format_scientific_and_engineering() {
+    let result = convert_input("1 ns to day").unwrap();
+    let format = NumberFormat {
+        notation: Notation::Scientific,
+        significant_figures: Some(4),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(std::slice::from_ref(&result), &format).unwrap(),
+        "1.157e-14 day"
+    );
+
+    let format = NumberFormat {
+        notation: Notation::Engineering,
+        decimals: Some(1),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(&[result], &format).unwrap(),
+        "11.6e-15 day"
+    );
+}
+
+#[test]
+fn test_format_grouping() {
+    let result = convert_input("1.5 GiB to byte").unwrap();
+    let format = NumberFormat {
+        separator: Some('_'),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(&[result], &format).unwrap(),
+        "1_610_612_736 byte"
+    );
+
+    // Grouping keeps the default rounding to 2 decimal places
+    let result = convert_input("1000 km to ft").unwrap();
+    let format = NumberFormat {
+        separator: Some(','),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(&[result], &format).unwrap(),
+        "3,280,839.9 ft"
+    );
+}
+
+#[test]
+fn test_format_significant_figures_validation() {
+    let result = convert_input("1 km to m").unwrap();
+    let format = NumberFormat {
+        significant_figures: Some(0),
+        ..NumberFormat::default()
+    };
+    assert_eq!(
+        format_output_with(&[result], &format).unwrap_err(),
+        "Significant figures must be at least 1"
+    );
+}
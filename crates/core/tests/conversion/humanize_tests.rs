@@ -0,0 +1,127 @@
+use strapd_core::conversion::engine::convert_input;
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::humanize::{best_unit, humanize, is_auto};
+use strapd_core::conversion::parser::parse_input;
+use strapd_core::conversion::types::{ConversionResult, find_unit};
+
+fn convert_and_format(input: &str) -> String {
+    let result = convert_input(input).unwrap();
+    format_output(&[result], None).unwrap()
+}
+
+// ============================================================================
+// Unit Selection
+// ============================================================================
+
+#[test]
+fn test_is_auto() {
+    assert!(is_auto("auto"));
+    assert!(is_auto(" AUTO "));
+    assert!(!is_auto("human"));
+}
+
+#[test]
+fn test_best_unit_picks_largest_unit_above_one() {
+    let byte = find_unit("byte").unwrap();
+    assert_eq!(best_unit(1536000.0, byte).unwrap().canonical_name, "MiB");
+    assert_eq!(best_unit(1023.0, byte).unwrap().canonical_name, "byte");
+    assert_eq!(best_unit(1024.0, byte).unwrap().canonical_name, "KiB");
+
+    // Rounding does not keep a value just below the next unit
+    let ms = find_unit("ms").unwrap();
+    assert_eq!(best_unit(1000.0, ms).unwrap().canonical_name, "s");
+    assert_eq!(best_unit(0.5, ms).unwrap().canonical_name, "us");
+    // Values below the smallest unit use it
+    let s = find_unit("s").unwrap();
+    assert_eq!(best_unit(1e-12, s).unwrap().canonical_name, "ns");
+}
+
+#[test]
+fn test_best_unit_keeps_scale_of_source() {
+    // SI bytes stay SI, IEC bytes stay IEC
+    let kb = find_unit("kB").unwrap();
+    assert_eq!(best_unit(1536.0, kb).unwrap().canonical_name, "MB");
+    let kib = find_unit("KiB").unwrap();
+    assert_eq!(best_unit(1536.0, kib).unwrap().canonical_name, "MiB");
+    let kbit = find_unit("kb").unwrap();
+    assert_eq!(best_unit(8000.0, kbit).unwrap().canonical_name, "Mbit");
+
+    // Imperial stays imperial
+    let ft = find_unit("ft").unwrap();
+    assert_eq!(best_unit(10560.0, ft).unwrap().canonical_name, "mi");
+    let m = find_unit("m").unwrap();
+    assert_eq!(best_unit(10560.0, m).unwrap().canonical_name, "km");
+
+    // Units on no scale use the first scale of their category
+    let week = find_unit("week").unwrap();
+    assert_eq!(best_unit(2.0, week).unwrap().canonical_name, "day");
+}
+
+#[test]
+fn test_best_unit_keeps_source_unit() {
+    // No scale for the category
+    assert!(best_unit(300.0, find_unit("mph").unwrap()).is_none());
+    // Formula-based units
+    assert!(best_unit(300.0, find_unit("c").unwrap()).is_none());
+    // Zero and non-finite values
+    assert!(best_unit(0.0, find_unit("byte").unwrap()).is_none());
+    assert!(best_unit(f64::INFINITY, find_unit("byte").unwrap()).is_none());
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+#[test]
+fn test_convert_to_auto() {
+    assert_eq!(convert_and_format("1536000 bytes to auto"), "1.46 MiB");
+    assert_eq!(convert_and_format("93784 s to auto"), "1.09 day");
+    assert_eq!(convert_and_format("0.000250 s to auto"), "250 us");
+    assert_eq!(convert_and_format("2500000 Hz to auto"), "2.5 MHz");
+    assert_eq!(convert_and_format("1500 g to auto"), "1.5 kg");
+    assert_eq!(convert_and_format("-2048 KiB to auto"), "-2 MiB");
+    assert_eq!(convert_and_format("1200000 bps to AUTO"), "1.2 Mbps");
+}
+
+#[test]
+fn test_convert_to_auto_keeps_unit() {
+    assert_eq!(convert_and_format("100 c to auto"), "100 c");
+    assert_eq!(convert_and_format("0 MB to auto"), "0 MB");
+    assert_eq!(convert_and_format("60 mph to auto"), "60 mph");
+}
+
+#[test]
+fn test_parse_auto_target() {
+    let request = parse_input("1536000 B to auto").unwrap();
+    assert_eq!(request.to_unit, Some("auto".to_string()));
+}
+
+#[test]
+fn test_expression_to_auto() {
+    assert_eq!(convert_and_format("1 GiB / 1024 to auto"), "1 MiB");
+    assert_eq!(convert_and_format("45 min + 45 min to auto"), "1.5 h");
+}
+
+#[test]
+fn test_humanize_result() {
+    let result = humanize(ConversionResult {
+        input_value: 3.0,
+        input_unit: "h".to_string(),
+        output_value: 10800000.0,
+        output_unit: "ms".to_string(),
+    })
+    .unwrap();
+    assert_eq!(result.input_unit, "h");
+    assert_eq!(result.output_unit, "h");
+    assert_eq!(result.output_value, 3.0);
+
+    // Outputs that are not a single unit are left alone
+    let result = humanize(ConversionResult {
+        input_value: 5415.0,
+        input_unit: "s".to_string(),
+        output_value: 5415.0,
+        output_unit: "human".to_string(),
+    })
+    .unwrap();
+    assert_eq!(result.output_unit, "human");
+}
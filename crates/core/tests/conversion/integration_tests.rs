@@ -1,5 +1,5 @@
 use strapd_core::conversion::engine::{convert, convert_to_all};
-use strapd_core::conversion::types::ConversionRequest;
+use strapd_core::conversion::types::{ConversionRequest, UnitCategory, get_units_in_category};
 
 // ============================================================================
 // Convert to All Tests
@@ -34,6 +34,58 @@ fn test_convert_to_all_temperature() {
     assert!((k_result.unwrap().output_value - 273.15).abs() < 0.01);
 }
 
+#[test]
+fn test_convert_to_all_new_categories() {
+    // Every category lists its other units
+    for (unit, expected) in [
+        ("Mbps", "MiB/s"),
+        ("kg", "lb"),
+        ("l", "gal"),
+        ("m2", "acre"),
+        ("km/h", "mph"),
+        ("deg", "rad"),
+        ("kWh", "J"),
+        ("bar", "psi"),
+        ("Hz", "kHz"),
+    ] {
+        let results = convert_to_all(1.0, unit).unwrap();
+        assert!(
+            results.iter().any(|r| r.output_unit == expected),
+            "{} should convert to {}",
+            unit,
+            expected
+        );
+        assert!(results.iter().all(|r| r.output_unit != unit));
+    }
+}
+
+#[test]
+fn test_units_in_new_categories_sorted_by_size() {
+    let units = get_units_in_category(UnitCategory::DataRate);
+    let names: Vec<&str> = units.iter().map(|u| u.canonical_name).collect();
+    assert_eq!(names.first(), Some(&"bps"));
+    assert_eq!(names.last(), Some(&"TiB/s"));
+
+    for category in [
+        UnitCategory::Mass,
+        UnitCategory::Volume,
+        UnitCategory::Area,
+        UnitCategory::Speed,
+        UnitCategory::Angle,
+        UnitCategory::Energy,
+        UnitCategory::Pressure,
+        UnitCategory::Frequency,
+    ] {
+        let units = get_units_in_category(category);
+        assert!(units.len() >= 5, "{:?} has too few units", category);
+        assert!(
+            units.iter().any(|u| u.to_base_multiplier == Some(1.0)),
+            "{:?} has no base unit",
+            category
+        );
+    }
+}
+
 // ============================================================================
 // Edge Cases
 // ============================================================================
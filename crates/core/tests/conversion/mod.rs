@@ -2,13 +2,28 @@
 //
 // Test modules:
 // - parser_tests: Input parsing and validation
-// - conversion_tests: Core conversion logic (length, bytes, time, temperature, data rate)
+// - compound_tests: Mixed-unit input and compound targets like ft+in
+// - conversion_tests: Core conversion logic (length, bytes, time, temperature, data rate,
+//   mass, volume, area, speed, angle, energy, pressure, frequency)
+// - currency_tests: Offline currency conversion from a rate table
+// - custom_units_tests: User-defined units and categories from a units file
+// - duration_tests: Compound and ISO 8601 durations
 // - error_tests: Error handling and validation
+// - expression_tests: Unit-aware arithmetic and dimension checks
 // - formatter_tests: Output formatting and precision
+// - humanize_tests: Auto-scaled output with `to auto`
 // - integration_tests: End-to-end tests and edge cases
+// - typography_tests: CSS units with root font size, viewport and DPI settings
 
+mod compound_tests;
 mod conversion_tests;
+mod currency_tests;
+mod custom_units_tests;
+mod duration_tests;
 mod error_tests;
+mod expression_tests;
 mod formatter_tests;
+mod humanize_tests;
 mod integration_tests;
 mod parser_tests;
+mod typography_tests;
@@ -0,0 +1,124 @@
+use strapd_core::conversion::engine::{convert_input, convert_input_with, convert_to_all_with};
+use strapd_core::conversion::formatter::format_output;
+use strapd_core::conversion::types::{UnitCategory, find_unit};
+use strapd_core::conversion::typography::{TypographySettings, extract_settings};
+
+fn convert_and_format(input: &str) -> String {
+    let result = convert_input(input).unwrap();
+    format_output(&[result], None).unwrap()
+}
+
+// ============================================================================
+// Default Settings
+// ============================================================================
+
+#[test]
+fn test_typography_units() {
+    assert_eq!(find_unit("px").unwrap().category, UnitCategory::Typography);
+    assert_eq!(find_unit("pt").unwrap().category, UnitCategory::Typography);
+    assert_eq!(find_unit("pint").unwrap().category, UnitCategory::Volume);
+}
+
+#[test]
+fn test_px_rem_em() {
+    assert_eq!(convert_and_format("24px to rem"), "1.5 rem");
+    assert_eq!(convert_and_format("1.5rem to px"), "24 px");
+    assert_eq!(convert_and_format("2 em to rem"), "2 rem");
+}
+
+#[test]
+fn test_points_and_picas() {
+    // At 96 DPI, 1 pt is 4/3 px as in CSS
+    assert_eq!(convert_and_format("12 pt to px"), "16 px");
+    assert_eq!(convert_and_format("1 pc to pt"), "12 pt");
+    assert_eq!(convert_and_format("16 px to pt"), "12 pt");
+}
+
+#[test]
+fn test_viewport_units() {
+    assert_eq!(convert_and_format("10 vw to px"), "192 px");
+    assert_eq!(convert_and_format("540 px to vh"), "50 vh");
+}
+
+#[test]
+fn test_length_units_through_dpi() {
+    assert_eq!(convert_and_format("1 in to px"), "96 px");
+    assert_eq!(convert_and_format("2.54 cm to px"), "96 px");
+    assert_eq!(convert_and_format("96 px to in"), "1 in");
+    assert_eq!(convert_and_format("1 in to pt"), "72 pt");
+    assert_eq!(convert_and_format("10 mm to px @dpi=254"), "100 px");
+    assert!(convert_input("1 in to kg").is_err());
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+#[test]
+fn test_settings_in_expression() {
+    assert_eq!(convert_and_format("24px to rem @base=18"), "1.33 rem");
+    assert_eq!(convert_and_format("@root=10px 2 rem to px"), "20 px");
+    assert_eq!(convert_and_format("10 vw to px @vw=1440"), "144 px");
+    assert_eq!(convert_and_format("10 vh to px @vh=900"), "90 px");
+    assert_eq!(convert_and_format("72 pt to px @dpi=300"), "300 px");
+}
+
+#[test]
+fn test_settings_override_defaults() {
+    let settings = TypographySettings {
+        root_font_size: 20.0,
+        ..TypographySettings::default()
+    };
+    let result = convert_input_with("30px to rem", &settings).unwrap();
+    assert_eq!(result.output_value, 1.5);
+
+    // Settings in the input win over the ones passed in
+    let result = convert_input_with("30px to rem @base=10", &settings).unwrap();
+    assert_eq!(result.output_value, 3.0);
+}
+
+#[test]
+fn test_convert_to_all_typography() {
+    let settings = TypographySettings::default();
+    let results = convert_to_all_with(16.0, "px", &settings).unwrap();
+    let rem = results.iter().find(|r| r.output_unit == "rem").unwrap();
+    assert_eq!(rem.output_value, 1.0);
+    let pt = results.iter().find(|r| r.output_unit == "pt").unwrap();
+    assert_eq!(pt.output_value, 12.0);
+}
+
+#[test]
+fn test_extract_settings() {
+    let (rest, settings) = extract_settings(
+        "24px to rem @base=18 @dpi=72",
+        TypographySettings::default(),
+    )
+    .unwrap();
+    assert_eq!(rest, "24px to rem");
+    assert_eq!(settings.root_font_size, 18.0);
+    assert_eq!(settings.dpi, 72.0);
+    assert_eq!(settings.viewport_width, 1920.0);
+}
+
+#[test]
+fn test_settings_errors() {
+    let defaults = TypographySettings::default();
+    assert_eq!(
+        extract_settings("1 rem to px @size=18", defaults).unwrap_err(),
+        "Unknown setting '@size'. Use @base, @vw, @vh or @dpi"
+    );
+    assert_eq!(
+        extract_settings("1 rem to px @base", defaults).unwrap_err(),
+        "Invalid setting '@base'. Use @key=value, e.g. @base=18"
+    );
+    assert_eq!(
+        extract_settings("1 rem to px @base=big", defaults).unwrap_err(),
+        "Invalid value in setting '@base=big'"
+    );
+    assert_eq!(
+        extract_settings("1 rem to px @base=0", defaults).unwrap_err(),
+        "Root font size must be a positive number, got 0"
+    );
+    // Typography units other than px are not fixed, so they stay out of arithmetic
+    assert!(convert_input("1 rem + 4 px to px").is_err());
+}
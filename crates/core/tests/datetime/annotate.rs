@@ -0,0 +1,229 @@
+use strapd_core::datetime::annotate::{self, AnnotateMode, AnnotateOptions};
+use strapd_core::datetime::timestamp::TimestampFormat;
+use strapd_core::datetime::timezone::Zone;
+
+// 2025-03-04 08:00:00 UTC
+const ISO: &str = "2025-03-04T08:00:00+00:00";
+
+fn options() -> AnnotateOptions {
+    AnnotateOptions {
+        format: TimestampFormat::Iso,
+        zone: Zone::Utc,
+        ..Default::default()
+    }
+}
+
+fn annotate_line(line: &str) -> String {
+    annotate::annotate_line(line, &options())
+}
+
+// Epoch Tests
+#[test]
+fn test_annotate_epoch_seconds() {
+    assert_eq!(
+        annotate_line("started at 1741075200 by cron"),
+        format!("started at 1741075200 ({}) by cron", ISO)
+    );
+}
+
+#[test]
+fn test_annotate_epoch_units() {
+    assert_eq!(
+        annotate_line("ts=1741075200123"),
+        "ts=1741075200123 (2025-03-04T08:00:00.123+00:00)"
+    );
+    assert_eq!(
+        annotate_line("ts=1741075200123456"),
+        "ts=1741075200123456 (2025-03-04T08:00:00.123456+00:00)"
+    );
+    assert_eq!(
+        annotate_line("ts=1741075200123456789"),
+        "ts=1741075200123456789 (2025-03-04T08:00:00.123456789+00:00)"
+    );
+}
+
+#[test]
+fn test_annotate_several_per_line() {
+    assert_eq!(
+        annotate_line("[1741075200] took 1741075260000"),
+        format!(
+            "[1741075200 ({})] took 1741075260000 (2025-03-04T08:01:00+00:00)",
+            ISO
+        )
+    );
+}
+
+#[test]
+fn test_annotate_skips_implausible_numbers() {
+    for line in [
+        "listening on port 8080",
+        "pid 12345 exited with 0",
+        "read 99999999999999999999 bytes",
+        // 1970 in seconds
+        "counter 1000000",
+        // 2200 in seconds
+        "expires 7258118400",
+    ] {
+        assert_eq!(annotate_line(line), line);
+    }
+}
+
+#[test]
+fn test_annotate_skips_numbers_inside_words_and_decimals() {
+    for line in [
+        "id a1741075200",
+        "id 1741075200a",
+        "build_1741075200",
+        "took 1741075200.5 s",
+        "version 1.1741075200",
+    ] {
+        assert_eq!(annotate_line(line), line);
+    }
+}
+
+#[test]
+fn test_annotate_epoch_at_end_of_sentence() {
+    assert_eq!(
+        annotate_line("done at 1741075200."),
+        format!("done at 1741075200 ({}).", ISO)
+    );
+}
+
+// ISO Tests
+#[test]
+fn test_annotate_iso() {
+    assert_eq!(
+        annotate_line("2025-03-04T10:00:00+02:00 INFO ready"),
+        format!("2025-03-04T10:00:00+02:00 ({}) INFO ready", ISO)
+    );
+    assert_eq!(
+        annotate_line("at 2025-03-04T08:00:00.250Z"),
+        "at 2025-03-04T08:00:00.250Z (2025-03-04T08:00:00.250+00:00)"
+    );
+}
+
+#[test]
+fn test_annotate_iso_without_offset_reads_in_zone() {
+    let options = AnnotateOptions {
+        zone: Zone::parse("+05:30").unwrap(),
+        ..options()
+    };
+    assert_eq!(
+        annotate::annotate_line("2025-03-04 13:30:00 GET /", &options),
+        "2025-03-04 13:30:00 (2025-03-04T13:30:00+05:30) GET /"
+    );
+}
+
+#[test]
+fn test_annotate_skips_plain_dates() {
+    let line = "released 2025-03-04, v1.2.3";
+    assert_eq!(annotate_line(line), line);
+}
+
+// Mode and Format Tests
+#[test]
+fn test_annotate_replace() {
+    let options = AnnotateOptions {
+        mode: AnnotateMode::Replace,
+        format: TimestampFormat::Sql,
+        ..options()
+    };
+    assert_eq!(
+        annotate::annotate_line("ts=1741075200 level=info", &options),
+        "ts=2025-03-04 08:00:00 level=info"
+    );
+}
+
+#[test]
+fn test_annotate_zone() {
+    let options = AnnotateOptions {
+        zone: Zone::parse("Asia/Tokyo").unwrap(),
+        format: TimestampFormat::Custom("%H:%M %Z".to_string()),
+        ..options()
+    };
+    assert_eq!(
+        annotate::annotate_line("2025-03-04T08:00:00Z", &options),
+        "2025-03-04T08:00:00Z (17:00 JST)"
+    );
+}
+
+#[test]
+fn test_annotate_multiple_lines() {
+    assert_eq!(
+        annotate::annotate("a 1741075200\nb\nc 1741075200", &options()),
+        format!("a 1741075200 ({0})\nb\nc 1741075200 ({0})", ISO)
+    );
+}
+
+#[test]
+fn test_annotate_quotes_json_numbers() {
+    assert_eq!(
+        annotate_line(r#"{"ts":1741075200,"n":1}"#),
+        format!(r#"{{"ts":"1741075200 ({})","n":1}}"#, ISO)
+    );
+    let options = AnnotateOptions {
+        mode: AnnotateMode::Replace,
+        ..options()
+    };
+    assert_eq!(
+        annotate::annotate_line(
+            r#"{"ts": 1741075200, "at": "2025-03-04T08:00:00Z"}"#,
+            &options
+        ),
+        format!(r#"{{"ts": "{0}", "at": "{0}"}}"#, ISO)
+    );
+}
+
+#[test]
+fn test_annotate_plain_text_numbers_stay_unquoted() {
+    // Only a number right after `"key":` is a JSON value
+    assert_eq!(
+        annotate_line(r#"ts: 1741075200 "done""#),
+        format!(r#"ts: 1741075200 ({}) "done""#, ISO)
+    );
+}
+
+// JSON Key Tests
+fn key_options(keys: &[&str], mode: AnnotateMode) -> AnnotateOptions {
+    AnnotateOptions {
+        keys: keys.iter().map(|key| key.to_string()).collect(),
+        mode,
+        ..options()
+    }
+}
+
+#[test]
+fn test_annotate_keys_only() {
+    let options = key_options(&["ts"], AnnotateMode::Append);
+    assert_eq!(
+        annotate::annotate_line(r#"{"ts": 1741075200, "user_id": 1741075200}"#, &options),
+        format!(r#"{{"ts": "1741075200 ({})", "user_id": 1741075200}}"#, ISO)
+    );
+}
+
+#[test]
+fn test_annotate_keys_replace() {
+    let options = key_options(&["ts", "created_at"], AnnotateMode::Replace);
+    assert_eq!(
+        annotate::annotate_line(
+            r#"{"ts":1741075200000,"created_at":"2025-03-04T08:00:00Z","n":1}"#,
+            &options
+        ),
+        format!(r#"{{"ts":"{0}","created_at":"{0}","n":1}}"#, ISO)
+    );
+}
+
+#[test]
+fn test_annotate_keys_skips_other_values() {
+    let options = key_options(&["ts"], AnnotateMode::Append);
+    for line in [
+        r#"{"msg": "ts", "level": "info"}"#,
+        r#"{"ts": "soon"}"#,
+        r#"{"ts": 42}"#,
+        r#"{"ts": 1741075200.5}"#,
+        r#"{"msg": "say \"ts\": 1741075200"}"#,
+        "ts 1741075200",
+    ] {
+        assert_eq!(annotate::annotate_line(line, &options), line);
+    }
+}
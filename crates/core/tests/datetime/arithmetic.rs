@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use strapd_core::datetime::arithmetic::{
+    self, CalendarDuration, add, calendar_diff, diff, parse_instant, sub,
+};
+use strapd_core::datetime::timestamp::TimestampUnit;
+use strapd_core::datetime::timezone::Zone;
+
+fn instant(input: &str) -> DateTime<Utc> {
+    parse_instant(input, false, Zone::Utc).unwrap()
+}
+
+fn duration(input: &str) -> CalendarDuration {
+    CalendarDuration::parse(input).unwrap_or_else(|e| panic!("{}: {}", input, e))
+}
+
+fn add_utc(start: &str, by: &str) -> String {
+    add(&instant(start), &duration(by), Zone::Utc)
+        .unwrap()
+        .to_rfc3339()
+}
+
+// Duration Parsing Tests
+#[test]
+fn test_parse_compound_durations() {
+    let expected = CalendarDuration {
+        months: 14,
+        days: 3,
+        millis: 4 * 3_600_000 + 30 * 60_000,
+    };
+    assert_eq!(duration("1y2mo3d4h30m"), expected);
+    assert_eq!(
+        duration("1 year 2 months 3 days 4 hours 30 minutes"),
+        expected
+    );
+    assert_eq!(duration("1y, 2mo, 3d, 4h and 30m"), expected);
+    assert_eq!(duration("P1Y2M3DT4H30M"), expected);
+}
+
+#[test]
+fn test_parse_simple_durations() {
+    assert_eq!(duration("90d").days, 90);
+    assert_eq!(duration("2w").days, 14);
+    assert_eq!(duration("3600").millis, 3_600_000);
+    assert_eq!(duration("1.5h").millis, 5_400_000);
+    assert_eq!(duration("250ms").millis, 250);
+    assert_eq!(duration("PT1M").millis, 60_000);
+    assert_eq!(duration("P1M").months, 1);
+    assert_eq!(duration("-1d").days, -1);
+    assert_eq!(duration("-1d2h").millis, -7_200_000);
+}
+
+#[test]
+fn test_parse_duration_errors() {
+    assert_eq!(
+        CalendarDuration::parse("soon").unwrap_err(),
+        "Invalid duration 'soon': expected a number at 'soon'. \
+         Use e.g. 90d, 1h30m, 2 weeks, P1M or 3600 (seconds)"
+    );
+    assert_eq!(
+        CalendarDuration::parse("3 fortnights").unwrap_err(),
+        "Invalid duration '3 fortnights': unknown time unit 'fortnights'. \
+         Use e.g. 90d, 1h30m, 2 weeks, P1M or 3600 (seconds)"
+    );
+    assert!(
+        CalendarDuration::parse("1.5mo")
+            .unwrap_err()
+            .contains("must be whole numbers")
+    );
+    assert!(CalendarDuration::parse("").is_err());
+    assert!(CalendarDuration::parse("P").is_err());
+    assert!(CalendarDuration::parse("P1H").is_err());
+}
+
+#[test]
+fn test_display_durations() {
+    assert_eq!(duration("1y2mo3d4h5m6s").to_string(), "1y 2mo 3d 4h 5m 6s");
+    assert_eq!(duration("90m").to_string(), "1h 30m");
+    assert_eq!(duration("1.25s").to_string(), "1.25s");
+    assert_eq!(duration("-2d").to_string(), "-2d");
+    assert_eq!(CalendarDuration::default().to_string(), "0s");
+}
+
+// Arithmetic Tests
+#[test]
+fn test_add_exact_durations() {
+    assert_eq!(add_utc("1741075200", "3600"), "2025-03-04T09:00:00+00:00");
+    assert_eq!(
+        add_utc("2025-03-04T08:00:00Z", "90d"),
+        "2025-06-02T08:00:00+00:00"
+    );
+    assert_eq!(
+        add_utc("2025-03-04T08:00:00Z", "1h30m15s"),
+        "2025-03-04T09:30:15+00:00"
+    );
+}
+
+#[test]
+fn test_add_months_clamps_to_month_end() {
+    assert_eq!(add_utc("2025-01-31", "1mo"), "2025-02-28T00:00:00+00:00");
+    assert_eq!(add_utc("2024-01-31", "1mo"), "2024-02-29T00:00:00+00:00");
+    assert_eq!(add_utc("2024-02-29", "1y"), "2025-02-28T00:00:00+00:00");
+    assert_eq!(add_utc("2025-03-31", "-1mo"), "2025-02-28T00:00:00+00:00");
+}
+
+#[test]
+fn test_sub() {
+    let result = sub(
+        &instant("2025-03-04T08:00:00Z"),
+        &duration("1w 1h"),
+        Zone::Utc,
+    )
+    .unwrap();
+    assert_eq!(result.to_rfc3339(), "2025-02-25T07:00:00+00:00");
+}
+
+#[test]
+fn test_add_days_keeps_wall_clock_across_daylight_saving() {
+    let new_york = Zone::parse("America/New_York").unwrap();
+    // 2024-03-09 12:00 EST; US daylight saving time starts the next night
+    let start = instant("2024-03-09T17:00:00Z");
+
+    let next_day = add(&start, &duration("1d"), new_york).unwrap();
+    assert_eq!(
+        new_york.format_rfc3339(&next_day),
+        "2024-03-10T12:00:00-04:00"
+    );
+    // 24 hours is an hour later on the wall clock
+    let exact = add(&start, &duration("24h"), new_york).unwrap();
+    assert_eq!(new_york.format_rfc3339(&exact), "2024-03-10T13:00:00-04:00");
+}
+
+#[test]
+fn test_add_out_of_range() {
+    let result = add(&instant("2025-01-01"), &duration("300000y"), Zone::Utc);
+    assert_eq!(result.unwrap_err(), "The result is out of range");
+}
+
+// Difference Tests
+#[test]
+fn test_diff() {
+    let from = instant("2025-03-04T08:00:00Z");
+    let to = instant("2025-03-05T09:30:00.5Z");
+    let delta = diff(&from, &to);
+    assert_eq!(delta.num_milliseconds(), 91_800_500);
+    assert_eq!(CalendarDuration::from(delta).to_string(), "1d 1h 30m 0.5s");
+    assert_eq!(
+        CalendarDuration::from(diff(&to, &from)).to_string(),
+        "-1d 1h 30m 0.5s"
+    );
+}
+
+#[test]
+fn test_calendar_diff() {
+    let from = instant("2024-01-31");
+    let to = instant("2025-03-01T06:00:00Z");
+    let calendar = calendar_diff(&from, &to, Zone::Utc);
+    assert_eq!(calendar.to_string(), "1y 1mo 1d 6h");
+    // Adding the difference gives the end back
+    assert_eq!(add(&from, &calendar, Zone::Utc).unwrap(), to);
+
+    assert_eq!(
+        calendar_diff(&to, &from, Zone::Utc).to_string(),
+        "-1y 1mo 1d 6h"
+    );
+    // Jan 31 plus a month is Feb 28
+    assert_eq!(
+        calendar_diff(&instant("2025-01-31"), &instant("2025-02-28"), Zone::Utc).to_string(),
+        "1mo"
+    );
+}
+
+// Instant Parsing Tests
+#[test]
+fn test_parse_instant() {
+    assert_eq!(
+        instant("1741075200").to_rfc3339(),
+        "2025-03-04T08:00:00+00:00"
+    );
+    assert_eq!(
+        parse_instant("1741075200000", true, Zone::Utc)
+            .unwrap()
+            .timestamp(),
+        1_741_075_200
+    );
+    let now = arithmetic::parse_instant("now", false, Zone::Utc).unwrap();
+    assert!((Utc::now() - now).num_seconds().abs() < 5);
+    assert!(parse_instant("yesterday-ish", false, Zone::Utc).is_err());
+}
+
+#[test]
+fn test_parse_instant_in_unit() {
+    let nanos =
+        arithmetic::parse_instant_in_unit("1741075200123456789", TimestampUnit::Nanos, Zone::Utc)
+            .unwrap();
+    assert_eq!(nanos.to_rfc3339(), "2025-03-04T08:00:00.123456789+00:00");
+    // The unit only applies to timestamps
+    let date =
+        arithmetic::parse_instant_in_unit("2025-03-04T08:00:00Z", TimestampUnit::Micros, Zone::Utc)
+            .unwrap();
+    assert_eq!(date.timestamp(), 1_741_075_200);
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use strapd_core::datetime::cron::CronSchedule;
+use strapd_core::datetime::timezone::Zone;
+
+// Tuesday 2025-03-04 08:00:00 UTC
+const START: &str = "2025-03-04T08:00:00Z";
+
+fn describe(expression: &str) -> String {
+    CronSchedule::parse(expression)
+        .unwrap_or_else(|e| panic!("{}: {}", expression, e))
+        .describe()
+}
+
+fn next_runs(expression: &str, count: usize, zone: Zone) -> Vec<String> {
+    let start: DateTime<Utc> = START.parse().unwrap();
+    CronSchedule::parse(expression)
+        .unwrap_or_else(|e| panic!("{}: {}", expression, e))
+        .next_runs(&start, count, zone)
+        .iter()
+        .map(|run| zone.format_rfc3339(run))
+        .collect()
+}
+
+fn first_run(expression: &str) -> String {
+    next_runs(expression, 1, Zone::Utc)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("{} never runs", expression))
+}
+
+// Explanation Tests
+#[test]
+fn test_describe_fixed_times() {
+    assert_eq!(
+        describe("0 9 * * MON-FRI"),
+        "At 09:00, on Monday through Friday"
+    );
+    assert_eq!(describe("30 9,17 * * *"), "At 09:30 and 17:30");
+    assert_eq!(describe("15 30 9 * * *"), "At 09:30:15");
+    assert_eq!(describe("@daily"), "At 00:00");
+    assert_eq!(
+        describe("@yearly"),
+        "At 00:00, on day 1 of the month, in January"
+    );
+    assert_eq!(describe("@weekly"), "At 00:00, on Sunday");
+}
+
+#[test]
+fn test_describe_repeating_times() {
+    assert_eq!(describe("* * * * *"), "Every minute");
+    assert_eq!(describe("*/15 * * * *"), "Every 15 minutes");
+    assert_eq!(describe("@hourly"), "Every hour");
+    assert_eq!(describe("0 */2 * * *"), "Every 2 hours");
+    assert_eq!(describe("5 * * * *"), "At 5 minutes past the hour");
+    assert_eq!(describe("*/10 * * * * *"), "Every 10 seconds");
+    assert_eq!(
+        describe("*/15 9-17 * * 1-5"),
+        "Every 15 minutes, between 09:00 and 17:59, on Monday through Friday"
+    );
+    assert_eq!(
+        describe("0,30 8,12 * * *"),
+        "At minutes 0 and 30, during hours 08 and 12"
+    );
+}
+
+#[test]
+fn test_describe_days() {
+    assert_eq!(
+        describe("0 0 1,15 * *"),
+        "At 00:00, on days 1 and 15 of the month"
+    );
+    assert_eq!(
+        describe("0 0 1 */3 *"),
+        "At 00:00, on day 1 of the month, every 3 months"
+    );
+    assert_eq!(
+        describe("0 0 1 * 1"),
+        "At 00:00, on day 1 of the month or on Monday"
+    );
+    assert_eq!(
+        describe("0 0 12 L * ?"),
+        "At 12:00, on the last day of the month"
+    );
+    assert_eq!(
+        describe("0 0 12 LW * ?"),
+        "At 12:00, on the last weekday of the month"
+    );
+    assert_eq!(
+        describe("0 0 12 15W * ?"),
+        "At 12:00, on the weekday nearest day 15 of the month"
+    );
+    assert_eq!(
+        describe("0 0 12 ? * 6L"),
+        "At 12:00, on the last Friday of the month"
+    );
+    assert_eq!(
+        describe("0 0 12 ? * FRI#3"),
+        "At 12:00, on the third Friday of the month"
+    );
+    assert_eq!(
+        describe("0 0 9 ? JAN-MAR MON 2026"),
+        "At 09:00, on Monday, in January through March, in 2026"
+    );
+}
+
+#[test]
+fn test_describe_sunday_as_seven() {
+    assert_eq!(describe("0 9 * * 0-7"), "At 09:00, every day");
+    assert_eq!(describe("0 9 * * SUN-SAT"), "At 09:00, every day");
+    assert_eq!(describe("0 9 * * 7"), "At 09:00, on Sunday");
+    assert_eq!(describe("0 9 * * 0,7"), "At 09:00, on Sunday");
+    assert_eq!(
+        describe("0 9 * * 5-7"),
+        "At 09:00, on Friday through Sunday"
+    );
+    assert_eq!(
+        describe("0 9 * * 0-7/2"),
+        "At 09:00, every 2 days of the week from Sunday through Saturday"
+    );
+    assert_eq!(describe("0 0 9 ? * 1-7"), "At 09:00, every day");
+}
+
+// Next Run Tests
+#[test]
+fn test_next_runs_weekdays() {
+    assert_eq!(
+        next_runs("0 9 * * MON-FRI", 4, Zone::Utc),
+        [
+            "2025-03-04T09:00:00+00:00",
+            "2025-03-05T09:00:00+00:00",
+            "2025-03-06T09:00:00+00:00",
+            "2025-03-07T09:00:00+00:00",
+        ]
+    );
+    // Sunday is both 0 and 7
+    assert_eq!(first_run("0 9 * * 7"), "2025-03-09T09:00:00+00:00");
+    assert_eq!(first_run("0 9 * * 0"), "2025-03-09T09:00:00+00:00");
+}
+
+#[test]
+fn test_next_runs_starts_after_the_instant() {
+    // A run exactly at the start instant is not included
+    assert_eq!(first_run("0 8 * * *"), "2025-03-05T08:00:00+00:00");
+    assert_eq!(
+        next_runs("*/20 * * * * *", 2, Zone::Utc),
+        ["2025-03-04T08:00:20+00:00", "2025-03-04T08:00:40+00:00"]
+    );
+}
+
+#[test]
+fn test_next_runs_special_days() {
+    assert_eq!(first_run("0 0 12 L * ?"), "2025-03-31T12:00:00+00:00");
+    assert_eq!(first_run("0 0 12 L-3 * ?"), "2025-03-28T12:00:00+00:00");
+    // 2025-05-31 is a Saturday
+    assert_eq!(first_run("0 0 12 LW 5 ?"), "2025-05-30T12:00:00+00:00");
+    // 2025-03-15 is a Saturday, 2025-06-01 a Sunday
+    assert_eq!(first_run("0 0 12 15W * ?"), "2025-03-14T12:00:00+00:00");
+    assert_eq!(first_run("0 0 12 1W 6 ?"), "2025-06-02T12:00:00+00:00");
+    assert_eq!(first_run("0 0 12 ? * 6L"), "2025-03-28T12:00:00+00:00");
+    assert_eq!(first_run("0 0 12 ? * FRI#3"), "2025-03-21T12:00:00+00:00");
+    // Quartz numbers weekdays from Sunday as 1
+    assert_eq!(first_run("0 0 12 ? * 2"), "2025-03-10T12:00:00+00:00");
+}
+
+#[test]
+fn test_next_runs_day_of_month_or_weekday() {
+    // Restricting both runs on either
+    assert_eq!(
+        next_runs("0 0 10 * FRI", 2, Zone::Utc),
+        ["2025-03-07T00:00:00+00:00", "2025-03-10T00:00:00+00:00"]
+    );
+}
+
+#[test]
+fn test_next_runs_leap_day_and_never() {
+    assert_eq!(first_run("0 0 29 2 *"), "2028-02-29T00:00:00+00:00");
+    assert!(next_runs("0 0 30 2 *", 1, Zone::Utc).is_empty());
+    assert!(next_runs("0 0 0 1 1 ? 2024", 1, Zone::Utc).is_empty());
+}
+
+#[test]
+fn test_next_runs_in_zone() {
+    let new_york = Zone::parse("America/New_York").unwrap();
+    // 02:30 does not exist on 2025-03-09, when clocks skip from 02:00 to 03:00
+    assert_eq!(
+        next_runs("30 2 * * *", 5, new_york),
+        [
+            "2025-03-05T02:30:00-05:00",
+            "2025-03-06T02:30:00-05:00",
+            "2025-03-07T02:30:00-05:00",
+            "2025-03-08T02:30:00-05:00",
+            "2025-03-10T02:30:00-04:00",
+        ]
+    );
+}
+
+// Validation Tests
+#[test]
+fn test_parse_errors() {
+    for (expression, expected) in [
+        (
+            "0 9 * *",
+            "Invalid cron expression '0 9 * *': expected 5 fields (minute hour day-of-month month day-of-week), 6 with seconds or 7 with a year, found 4",
+        ),
+        (
+            "0 25 * * *",
+            "Invalid hour field '25' (field 2 of 5): 25 is out of range 0-23",
+        ),
+        (
+            "61 * * * *",
+            "Invalid minute field '61' (field 1 of 5): 61 is out of range 0-59",
+        ),
+        (
+            "0 0 * FOO *",
+            "Invalid month field 'FOO' (field 4 of 5): unknown value 'FOO', use 1-12 or JAN-DEC",
+        ),
+        (
+            "0 0 * * FUN",
+            "Invalid day-of-week field 'FUN' (field 5 of 5): unknown value 'FUN', use 0-7 or SUN-SAT",
+        ),
+        (
+            "*/0 * * * *",
+            "Invalid minute field '*/0' (field 1 of 5): step must be at least 1",
+        ),
+        (
+            "0 17-9 * * *",
+            "Invalid hour field '17-9' (field 2 of 5): range 17-9 goes backwards",
+        ),
+        (
+            "? * * * *",
+            "Invalid minute field '?' (field 1 of 5): '?' is only allowed in the day-of-month and day-of-week fields",
+        ),
+        (
+            "0 L * * *",
+            "Invalid hour field 'L' (field 2 of 5): 'L' uses L, W or #, which are only allowed in the day fields",
+        ),
+        (
+            "0 0 12 ? * FRI#6",
+            "Invalid day-of-week field 'FRI#6' (field 6 of 6): #6 needs a week of the month from 1 to 5",
+        ),
+        (
+            "@reboot",
+            "Unknown cron macro '@reboot'. Use @yearly, @annually, @monthly, @weekly, @daily, @midnight or @hourly",
+        ),
+    ] {
+        assert_eq!(CronSchedule::parse(expression).unwrap_err(), expected);
+    }
+}
+
+#[test]
+fn test_parse_names_and_case() {
+    assert_eq!(
+        describe("0 0 * jul-dec sun"),
+        "At 00:00, on Sunday, in July through December"
+    );
+    assert!(CronSchedule::parse("0 0 1 JAN,JUL *").is_ok());
+    assert!(!CronSchedule::parse("0 9 * * 1").unwrap().is_quartz());
+    assert!(CronSchedule::parse("0 0 9 ? * 1").unwrap().is_quartz());
+}
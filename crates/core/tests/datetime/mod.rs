@@ -1 +1,7 @@
+pub mod annotate;
+pub mod arithmetic;
+pub mod cron;
+pub mod parse;
+pub mod relative;
 pub mod timestamp;
+pub mod timezone;
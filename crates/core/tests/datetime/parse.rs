@@ -0,0 +1,145 @@
+use strapd_core::datetime::parse::{parse_datetime, parse_datetime_with_format};
+use strapd_core::datetime::timestamp;
+use strapd_core::datetime::timezone::Zone;
+
+// 2025-03-04 08:00:00 UTC
+const MARCH_4: i64 = 1_741_075_200;
+
+fn utc(input: &str) -> i64 {
+    parse_datetime(input, Zone::Utc)
+        .unwrap_or_else(|e| panic!("{}: {}", input, e))
+        .timestamp()
+}
+
+// Detected Format Tests
+#[test]
+fn test_parse_rfc3339() {
+    assert_eq!(utc("2025-03-04T10:00:00+02:00"), MARCH_4);
+    assert_eq!(utc("2025-03-04T08:00:00Z"), MARCH_4);
+    assert_eq!(utc("2025-03-04T08:00:00.250Z"), MARCH_4);
+}
+
+#[test]
+fn test_parse_rfc2822_and_http_dates() {
+    assert_eq!(utc("Tue, 04 Mar 2025 08:00:00 GMT"), MARCH_4);
+    assert_eq!(utc("Tue, 4 Mar 2025 10:00:00 +0200"), MARCH_4);
+    // RFC 850 and asctime, the obsolete HTTP date formats
+    assert_eq!(utc("Tuesday, 04-Mar-25 08:00:00 GMT"), MARCH_4);
+    assert_eq!(utc("Tue Mar  4 08:00:00 2025"), MARCH_4);
+}
+
+#[test]
+fn test_parse_log_formats() {
+    assert_eq!(utc("[04/Mar/2025:10:00:00 +0200]"), MARCH_4);
+    assert_eq!(utc("2025-03-04 08:00:00,123"), MARCH_4);
+    assert_eq!(utc("2025/03/04 08:00:00"), MARCH_4);
+    assert_eq!(utc("2025-03-04 10:00:00 +0200"), MARCH_4);
+    assert_eq!(utc("2025-03-04 08:00:00 UTC"), MARCH_4);
+}
+
+#[test]
+fn test_parse_naive_and_date_only() {
+    assert_eq!(utc("2025-03-04 08:00"), MARCH_4);
+    assert_eq!(utc("2025-03-04T08:00:00"), MARCH_4);
+    assert_eq!(utc("2025-03-04"), MARCH_4 - 8 * 3600);
+    assert_eq!(utc("4 Mar 2025"), MARCH_4 - 8 * 3600);
+    assert_eq!(utc("March 4, 2025"), MARCH_4 - 8 * 3600);
+}
+
+// Timezone Tests
+#[test]
+fn test_parse_naive_in_zone() {
+    let kolkata = Zone::parse("Asia/Kolkata").unwrap();
+    let dt = parse_datetime("2025-03-04 13:30", kolkata).unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4);
+
+    // An explicit offset wins over the zone
+    let dt = parse_datetime("2025-03-04T10:00:00+02:00", kolkata).unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4);
+    // So does a UTC suffix
+    let dt = parse_datetime("2025-03-04 08:00:00 UTC", kolkata).unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4);
+}
+
+#[test]
+fn test_parse_daylight_saving_gaps_and_overlaps() {
+    let new_york = Zone::parse("America/New_York").unwrap();
+    // Clocks skip from 02:00 to 03:00 on 2024-03-10
+    let error = parse_datetime("2024-03-10 02:30", new_york).unwrap_err();
+    assert!(
+        error.contains("does not exist in America/New_York"),
+        "{}",
+        error
+    );
+
+    // 01:30 happens twice on 2024-11-03; the earlier one, in EDT, is used
+    let dt = parse_datetime("2024-11-03 01:30", new_york).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2024-11-03T05:30:00+00:00");
+}
+
+#[test]
+fn test_parse_unrecognized() {
+    let error = parse_datetime("next tuesday", Zone::Utc).unwrap_err();
+    assert!(
+        error.starts_with("Unrecognized date 'next tuesday'"),
+        "{}",
+        error
+    );
+    assert!(parse_datetime("2025-13-04", Zone::Utc).is_err());
+}
+
+// Custom Format Tests
+#[test]
+fn test_parse_with_format() {
+    let dt = parse_datetime_with_format("04.03.2025 08:00", "%d.%m.%Y %H:%M", Zone::Utc).unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4);
+
+    let dt = parse_datetime_with_format("04.03.2025 10:00 +0200", "%d.%m.%Y %H:%M %z", Zone::Utc)
+        .unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4);
+
+    // A date without a time is midnight
+    let dt = parse_datetime_with_format("04.03.2025", "%d.%m.%Y", Zone::Utc).unwrap();
+    assert_eq!(dt.timestamp(), MARCH_4 - 8 * 3600);
+}
+
+#[test]
+fn test_parse_with_format_errors() {
+    let error = parse_datetime_with_format("2025-03-04", "%d.%m.%Y", Zone::Utc).unwrap_err();
+    assert!(
+        error.starts_with("Could not parse '2025-03-04' with format '%d.%m.%Y'"),
+        "{}",
+        error
+    );
+}
+
+// Timestamp Tests
+#[test]
+fn test_to_timestamp() {
+    assert_eq!(
+        timestamp::to_timestamp("2025-03-04T10:00:00+02:00", None, Zone::Utc).unwrap(),
+        MARCH_4
+    );
+    assert_eq!(
+        timestamp::to_timestamp_millis("2025-03-04T08:00:00.250Z", None, Zone::Utc).unwrap(),
+        MARCH_4 * 1000 + 250
+    );
+    assert_eq!(
+        timestamp::to_timestamp("04.03.2025", Some("%d.%m.%Y"), Zone::Utc).unwrap(),
+        MARCH_4 - 8 * 3600
+    );
+}
+
+#[test]
+fn test_to_timestamp_round_trip() {
+    let formatted = timestamp::from_timestamp_in(
+        MARCH_4,
+        timestamp::TimestampFormat::Iso,
+        Zone::parse("Asia/Tokyo").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        timestamp::to_timestamp(&formatted, None, Zone::Utc).unwrap(),
+        MARCH_4
+    );
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use strapd_core::datetime::relative::{self, RelativeOptions, RelativeUnit};
+use strapd_core::datetime::timestamp::{self, TimestampFormat};
+use strapd_core::datetime::timezone::Zone;
+
+// Tuesday 2025-03-04 08:00:00 UTC
+const SINCE: &str = "2025-03-04T08:00:00Z";
+
+fn relative_with(instant: &str, parts: usize, granularity: RelativeUnit) -> String {
+    let instant: DateTime<Utc> = instant.parse().unwrap();
+    let since: DateTime<Utc> = SINCE.parse().unwrap();
+    let options = RelativeOptions { parts, granularity };
+    relative::relative(&instant, &since, Zone::Utc, &options)
+}
+
+fn relative(instant: &str) -> String {
+    relative_with(instant, 1, RelativeUnit::Second)
+}
+
+// Past and Future Tests
+#[test]
+fn test_relative_past() {
+    assert_eq!(relative("2025-03-04T07:59:30Z"), "30 seconds ago");
+    assert_eq!(relative("2025-03-04T07:59:00Z"), "1 minute ago");
+    assert_eq!(relative("2025-03-04T05:00:00Z"), "3 hours ago");
+    assert_eq!(relative("2025-03-02T08:00:00Z"), "2 days ago");
+    assert_eq!(relative("2025-02-18T08:00:00Z"), "2 weeks ago");
+    assert_eq!(relative("2024-12-04T08:00:00Z"), "3 months ago");
+    assert_eq!(relative("2020-03-04T08:00:00Z"), "5 years ago");
+}
+
+#[test]
+fn test_relative_future() {
+    assert_eq!(relative("2025-03-04T08:00:01Z"), "in 1 second");
+    assert_eq!(relative("2025-03-06T08:00:00Z"), "in 2 days");
+    assert_eq!(relative("2026-03-04T08:00:00Z"), "in 1 year");
+}
+
+#[test]
+fn test_relative_just_now() {
+    assert_eq!(relative(SINCE), "just now");
+    assert_eq!(relative("2025-03-04T08:00:00.400Z"), "just now");
+}
+
+#[test]
+fn test_relative_truncates() {
+    // 1 day 23 hours is shown as 1 day
+    assert_eq!(relative("2025-03-02T09:00:00Z"), "1 day ago");
+}
+
+#[test]
+fn test_relative_calendar_months() {
+    // Feb 4 to Mar 4 is a calendar month, though only 28 days
+    assert_eq!(relative("2025-02-04T08:00:00Z"), "1 month ago");
+    assert_eq!(relative("2025-02-05T08:00:00Z"), "3 weeks ago");
+}
+
+// Granularity Tests
+#[test]
+fn test_relative_parts() {
+    assert_eq!(
+        relative_with("2025-03-02T05:30:00Z", 2, RelativeUnit::Second),
+        "2 days, 2 hours ago"
+    );
+    assert_eq!(
+        relative_with("2025-03-02T05:30:00Z", 3, RelativeUnit::Second),
+        "2 days, 2 hours, 30 minutes ago"
+    );
+    assert_eq!(
+        relative_with("2026-05-04T09:00:00Z", 7, RelativeUnit::Second),
+        "in 1 year, 2 months, 1 hour"
+    );
+}
+
+#[test]
+fn test_relative_parts_skip_zero_units() {
+    // 1 day, 0 hours and 5 minutes: the zero hours do not use up a part
+    assert_eq!(
+        relative_with("2025-03-03T07:55:00Z", 2, RelativeUnit::Second),
+        "1 day, 5 minutes ago"
+    );
+    // 2 years, 0 months, 0 weeks and 5 days
+    assert_eq!(
+        relative_with("2023-02-27T08:00:00Z", 3, RelativeUnit::Day),
+        "2 years, 5 days ago"
+    );
+}
+
+#[test]
+fn test_relative_granularity() {
+    assert_eq!(
+        relative_with("2025-03-02T05:30:00Z", 7, RelativeUnit::Day),
+        "2 days ago"
+    );
+    assert_eq!(
+        relative_with("2025-03-04T05:00:00Z", 1, RelativeUnit::Day),
+        "less than a day ago"
+    );
+    assert_eq!(
+        relative_with("2025-03-04T08:20:00Z", 1, RelativeUnit::Hour),
+        "in less than an hour"
+    );
+}
+
+#[test]
+fn test_relative_unit_parse() {
+    assert_eq!(RelativeUnit::parse("h").unwrap(), RelativeUnit::Hour);
+    assert_eq!(RelativeUnit::parse("Days").unwrap(), RelativeUnit::Day);
+    assert_eq!(RelativeUnit::parse("mo").unwrap(), RelativeUnit::Month);
+    assert_eq!(
+        RelativeUnit::parse("fortnight").unwrap_err(),
+        "Unknown unit 'fortnight'. Use year, month, week, day, hour, minute or second"
+    );
+}
+
+// Format Tests
+#[test]
+fn test_relative_format() {
+    assert_eq!(
+        TimestampFormat::parse("relative").unwrap(),
+        TimestampFormat::Relative
+    );
+    assert_eq!(
+        TimestampFormat::parse("ago").unwrap(),
+        TimestampFormat::Relative
+    );
+
+    let hour_ago = timestamp::now() - 3600;
+    assert_eq!(
+        timestamp::from_timestamp_in(hour_ago, TimestampFormat::Relative, Zone::Utc).unwrap(),
+        "1 hour ago"
+    );
+    let in_two_hours = timestamp::now_millis() + 7_230_000;
+    assert_eq!(
+        timestamp::from_timestamp_millis_in(in_two_hours, TimestampFormat::Relative, Zone::Utc)
+            .unwrap(),
+        "in 2 hours"
+    );
+}
@@ -1,4 +1,5 @@
-use strapd_core::datetime::timestamp::{self, TimestampFormat};
+use strapd_core::datetime::timestamp::{self, TimestampFormat, TimestampUnit};
+use strapd_core::datetime::timezone::Zone;
 
 // Now Tests
 #[test]
@@ -205,3 +206,152 @@ fn test_from_timestamp_recent_values() {
         assert!(iso.is_ok());
     }
 }
+
+// Preset and Custom Format Tests
+fn format_utc(timestamp: i64, format: TimestampFormat) -> String {
+    timestamp::from_timestamp_in(timestamp, format, Zone::Utc).unwrap()
+}
+
+#[test]
+fn test_from_timestamp_presets() {
+    // 2025-03-04 08:00:00 UTC
+    let ts = 1_741_075_200;
+    assert_eq!(
+        format_utc(ts, TimestampFormat::Rfc2822),
+        "Tue, 4 Mar 2025 08:00:00 +0000"
+    );
+    assert_eq!(
+        format_utc(ts, TimestampFormat::Http),
+        "Tue, 04 Mar 2025 08:00:00 GMT"
+    );
+    assert_eq!(format_utc(ts, TimestampFormat::Sql), "2025-03-04 08:00:00");
+    assert_eq!(format_utc(ts, TimestampFormat::Compact), "20250304080000");
+    assert_eq!(format_utc(ts, TimestampFormat::Date), "2025-03-04");
+}
+
+#[test]
+fn test_from_timestamp_presets_in_zone() {
+    let ts = 1_741_075_200;
+    let zone = Zone::parse("Europe/Berlin").unwrap();
+    assert_eq!(
+        timestamp::from_timestamp_in(ts, TimestampFormat::Sql, zone).unwrap(),
+        "2025-03-04 09:00:00"
+    );
+    // HTTP dates are always in GMT
+    assert_eq!(
+        timestamp::from_timestamp_in(ts, TimestampFormat::Http, zone).unwrap(),
+        "Tue, 04 Mar 2025 08:00:00 GMT"
+    );
+    let custom = TimestampFormat::custom("%H:%M %Z").unwrap();
+    assert_eq!(
+        timestamp::from_timestamp_in(ts, custom, zone).unwrap(),
+        "09:00 CET"
+    );
+}
+
+#[test]
+fn test_from_timestamp_custom() {
+    let format = TimestampFormat::custom("%d/%m/%Y %H:%M").unwrap();
+    assert_eq!(format_utc(1_741_075_200, format), "04/03/2025 08:00");
+}
+
+#[test]
+fn test_parse_format() {
+    assert_eq!(TimestampFormat::parse("Iso").unwrap(), TimestampFormat::Iso);
+    assert_eq!(
+        TimestampFormat::parse("human").unwrap(),
+        TimestampFormat::Human
+    );
+    assert_eq!(
+        TimestampFormat::parse("HTTP").unwrap(),
+        TimestampFormat::Http
+    );
+    assert_eq!(
+        TimestampFormat::parse("%Y").unwrap(),
+        TimestampFormat::Custom("%Y".to_string())
+    );
+}
+
+#[test]
+fn test_parse_format_errors() {
+    assert_eq!(
+        TimestampFormat::parse("unix").unwrap_err(),
+        "Unknown format 'unix'. Use human, iso, rfc2822, http, sql, compact, date, relative or a strftime pattern like '%Y-%m-%d %H:%M'"
+    );
+    assert_eq!(
+        TimestampFormat::parse("%Y-%Q").unwrap_err(),
+        "Invalid format pattern '%Y-%Q'"
+    );
+    assert!(TimestampFormat::custom("%").is_err());
+}
+
+#[test]
+fn test_from_timestamp_unchecked_pattern_errors() {
+    // Patterns built without validation error instead of panicking
+    let result = timestamp::from_timestamp(0, TimestampFormat::Custom("%Q".to_string()));
+    assert_eq!(result.unwrap_err(), "Invalid format pattern");
+}
+
+// Unit Tests
+#[test]
+fn test_detect_unit() {
+    for (timestamp, unit) in [
+        (0, TimestampUnit::Seconds),
+        (1_741_075_200, TimestampUnit::Seconds),
+        (-1_741_075_200, TimestampUnit::Seconds),
+        (1_741_075_200_123, TimestampUnit::Millis),
+        (1_741_075_200_123_456, TimestampUnit::Micros),
+        (1_741_075_200_123_456_789, TimestampUnit::Nanos),
+        (99_999_999_999, TimestampUnit::Seconds),
+        (100_000_000_000, TimestampUnit::Millis),
+    ] {
+        assert_eq!(TimestampUnit::detect(timestamp), unit, "{}", timestamp);
+    }
+}
+
+#[test]
+fn test_parse_unit() {
+    assert_eq!(TimestampUnit::parse("ms").unwrap(), TimestampUnit::Millis);
+    assert_eq!(TimestampUnit::parse("µs").unwrap(), TimestampUnit::Micros);
+    assert_eq!(TimestampUnit::parse("NS").unwrap(), TimestampUnit::Nanos);
+    assert_eq!(
+        TimestampUnit::parse("minutes").unwrap_err(),
+        "Unknown timestamp unit 'minutes'. Use s, ms, us or ns"
+    );
+}
+
+#[test]
+fn test_from_timestamp_micros_and_nanos() {
+    assert_eq!(
+        timestamp::from_timestamp_unit_in(
+            1_741_075_200_123_456,
+            TimestampUnit::Micros,
+            TimestampFormat::Iso,
+            Zone::Utc
+        )
+        .unwrap(),
+        "2025-03-04T08:00:00.123456+00:00"
+    );
+    assert_eq!(
+        timestamp::from_timestamp_unit_in(
+            1_741_075_200_123_456_789,
+            TimestampUnit::Nanos,
+            TimestampFormat::Human,
+            Zone::Utc
+        )
+        .unwrap(),
+        "2025-03-04 08:00:00.123456789 UTC"
+    );
+    assert!(timestamp::from_timestamp_micros(0, TimestampFormat::Iso).is_ok());
+    assert!(timestamp::from_timestamp_nanos(0, TimestampFormat::Iso).is_ok());
+    assert!(timestamp::from_timestamp_micros(i64::MAX, TimestampFormat::Iso).is_err());
+}
+
+#[test]
+fn test_now_micros_and_nanos() {
+    let micros = timestamp::now_micros();
+    let nanos = timestamp::now_nanos();
+    assert!((nanos / 1000 - micros).abs() < 1_000_000);
+    assert_eq!(TimestampUnit::detect(micros), TimestampUnit::Micros);
+    assert_eq!(TimestampUnit::detect(nanos), TimestampUnit::Nanos);
+}
@@ -0,0 +1,159 @@
+use strapd_core::datetime::timestamp::{self, TimestampFormat};
+use strapd_core::datetime::timezone::{DEFAULT_WORLD_CLOCK, Zone, parse_zone_list, zone_names};
+
+// 2024-03-10 12:00:00 UTC, the day US daylight saving time starts
+const SPRING_FORWARD: i64 = 1_710_072_000;
+
+// Parsing Tests
+#[test]
+fn test_parse_utc_and_local() {
+    assert_eq!(Zone::parse("UTC").unwrap(), Zone::Utc);
+    assert_eq!(Zone::parse("utc").unwrap(), Zone::Utc);
+    assert_eq!(Zone::parse("Z").unwrap(), Zone::Utc);
+    assert_eq!(Zone::parse("local").unwrap(), Zone::Local);
+}
+
+#[test]
+fn test_parse_iana_names() {
+    assert_eq!(
+        Zone::parse("America/New_York").unwrap().to_string(),
+        "America/New_York"
+    );
+    // Names match case-insensitively
+    assert_eq!(
+        Zone::parse("asia/kolkata").unwrap().to_string(),
+        "Asia/Kolkata"
+    );
+    assert_eq!(Zone::parse("GMT").unwrap().to_string(), "GMT");
+}
+
+#[test]
+fn test_parse_offsets() {
+    for (input, expected) in [
+        ("+05:30", "+05:30"),
+        ("-0800", "-08:00"),
+        ("+05", "+05:00"),
+        ("UTC+5:30", "+05:30"),
+        ("GMT-8", "-08:00"),
+        ("+00:00", "+00:00"),
+    ] {
+        assert_eq!(
+            Zone::parse(input).unwrap().to_string(),
+            expected,
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Zone::parse("Mars/Olympus").unwrap_err(),
+        "Unknown timezone 'Mars/Olympus'. Use an IANA name like America/New_York, UTC or an offset like +05:30"
+    );
+    assert_eq!(
+        Zone::parse("+25:00").unwrap_err(),
+        "Invalid UTC offset '+25:00'. Use +HH:MM, e.g. +05:30"
+    );
+    assert!(Zone::parse("+05:xx").is_err());
+}
+
+#[test]
+fn test_parse_offset_with_non_ascii() {
+    // Four bytes, but not four characters
+    assert_eq!(
+        Zone::parse("+1é1").unwrap_err(),
+        "Invalid UTC offset '+1é1'. Use +HH:MM, e.g. +05:30"
+    );
+    assert!(Zone::parse("+٠٥:٣٠").is_err());
+}
+
+#[test]
+fn test_parse_zone_list() {
+    let zones = parse_zone_list("UTC, Europe/London\n# comment\n\n+05:30 # India\n").unwrap();
+    let names: Vec<String> = zones.iter().map(Zone::to_string).collect();
+    assert_eq!(names, vec!["UTC", "Europe/London", "+05:30"]);
+
+    assert!(parse_zone_list("UTC,Nowhere").is_err());
+    assert!(parse_zone_list(&DEFAULT_WORLD_CLOCK.join(",")).is_ok());
+}
+
+#[test]
+fn test_zone_names() {
+    let names = zone_names();
+    assert!(names.contains(&"America/New_York"));
+    assert!(names.contains(&"Australia/Sydney"));
+}
+
+// Formatting Tests
+#[test]
+fn test_from_timestamp_in_named_zone() {
+    let zone = Zone::parse("America/New_York").unwrap();
+    assert_eq!(
+        timestamp::from_timestamp_in(0, TimestampFormat::Human, zone).unwrap(),
+        "1969-12-31 19:00:00 EST"
+    );
+    assert_eq!(
+        timestamp::from_timestamp_in(0, TimestampFormat::Iso, zone).unwrap(),
+        "1969-12-31T19:00:00-05:00"
+    );
+}
+
+#[test]
+fn test_from_timestamp_in_follows_daylight_saving() {
+    let zone = Zone::parse("America/New_York").unwrap();
+    assert_eq!(
+        timestamp::from_timestamp_in(SPRING_FORWARD, TimestampFormat::Human, zone).unwrap(),
+        "2024-03-10 08:00:00 EDT"
+    );
+    // One day earlier is still standard time
+    assert_eq!(
+        timestamp::from_timestamp_in(SPRING_FORWARD - 86_400, TimestampFormat::Human, zone)
+            .unwrap(),
+        "2024-03-09 07:00:00 EST"
+    );
+}
+
+#[test]
+fn test_from_timestamp_in_utc_and_offset() {
+    assert_eq!(
+        timestamp::from_timestamp_in(0, TimestampFormat::Human, Zone::Utc).unwrap(),
+        "1970-01-01 00:00:00 UTC"
+    );
+    assert_eq!(
+        timestamp::from_timestamp_in(0, TimestampFormat::Iso, Zone::Utc).unwrap(),
+        "1970-01-01T00:00:00+00:00"
+    );
+    let zone = Zone::parse("+05:30").unwrap();
+    assert_eq!(
+        timestamp::from_timestamp_millis_in(1_500, TimestampFormat::Human, zone).unwrap(),
+        "1970-01-01 05:30:01.500 +05:30"
+    );
+}
+
+#[test]
+fn test_world_clock() {
+    let zones = parse_zone_list("UTC,Europe/London,Asia/Tokyo").unwrap();
+    let rows =
+        timestamp::world_clock(SPRING_FORWARD * 1000, &zones, TimestampFormat::Human).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            ("UTC".to_string(), "2024-03-10 12:00:00 UTC".to_string()),
+            (
+                "Europe/London".to_string(),
+                "2024-03-10 12:00:00 GMT".to_string()
+            ),
+            (
+                "Asia/Tokyo".to_string(),
+                "2024-03-10 21:00:00 JST".to_string()
+            ),
+        ]
+    );
+
+    assert_eq!(
+        timestamp::world_clock(i64::MAX, &zones, TimestampFormat::Human).unwrap_err(),
+        "Invalid timestamp"
+    );
+}
@@ -6,4 +6,5 @@ pub mod encoding;
 pub mod identifiers;
 pub mod random;
 pub mod security;
+pub mod statistics;
 pub mod string;
@@ -0,0 +1,113 @@
+use strapd_core::statistics;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+// ========== Parsing Tests ==========
+
+#[test]
+fn test_parse_numbers_whitespace_and_newlines() {
+    let values = statistics::parse_numbers("1 2\n3\t4\n\n5").unwrap();
+    assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn test_parse_numbers_calculator_syntax() {
+    let values = statistics::parse_numbers("1_000 0x10 -2.5 .5 +3").unwrap();
+    assert_eq!(values, vec![1000.0, 16.0, -2.5, 0.5, 3.0]);
+}
+
+#[test]
+fn test_parse_numbers_invalid() {
+    let err = statistics::parse_numbers("1 2 abc").unwrap_err();
+    assert_eq!(err, "Invalid number: abc");
+    assert!(statistics::parse_numbers("1.2.3").is_err());
+    assert!(statistics::parse_numbers("12ms").is_err());
+}
+
+#[test]
+fn test_parse_numbers_empty() {
+    assert!(statistics::parse_numbers("  \n ").is_err());
+}
+
+// ========== Summary Tests ==========
+
+#[test]
+fn test_summarize() {
+    let summary = statistics::summarize(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+    assert_eq!(summary.count, 8);
+    assert_close(summary.sum, 40.0);
+    assert_close(summary.min, 2.0);
+    assert_close(summary.max, 9.0);
+    assert_close(summary.mean, 5.0);
+    assert_close(summary.median, 4.5);
+    assert_close(summary.variance, 32.0 / 7.0);
+    assert_close(summary.std_dev, (32.0f64 / 7.0).sqrt());
+}
+
+#[test]
+fn test_summarize_single_value() {
+    let summary = statistics::summarize(&[42.0]).unwrap();
+    assert_close(summary.median, 42.0);
+    assert_close(summary.variance, 0.0);
+    assert_close(summary.std_dev, 0.0);
+}
+
+#[test]
+fn test_summarize_empty() {
+    assert!(statistics::summarize(&[]).is_err());
+}
+
+// ========== Percentile Tests ==========
+
+#[test]
+fn test_percentiles_interpolate() {
+    let values: Vec<f64> = (1..=10).map(f64::from).collect();
+    let results = statistics::percentiles(&values, &[0.0, 50.0, 90.0, 100.0]).unwrap();
+    assert_close(results[0], 1.0);
+    assert_close(results[1], 5.5);
+    assert_close(results[2], 9.1);
+    assert_close(results[3], 10.0);
+}
+
+#[test]
+fn test_percentile_unsorted_input() {
+    assert_close(statistics::percentile(&[9.0, 1.0, 5.0], 50.0).unwrap(), 5.0);
+}
+
+#[test]
+fn test_percentile_out_of_range() {
+    let err = statistics::percentile(&[1.0], 101.0).unwrap_err();
+    assert!(err.contains("between 0 and 100"));
+}
+
+// ========== Histogram Tests ==========
+
+#[test]
+fn test_histogram() {
+    let bins = statistics::histogram(&[0.0, 1.0, 2.0, 3.0, 4.0, 10.0], 2).unwrap();
+    assert_eq!(bins.len(), 2);
+    assert_close(bins[0].start, 0.0);
+    assert_close(bins[0].end, 5.0);
+    assert_eq!(bins[0].count, 5);
+    assert_close(bins[1].end, 10.0);
+    assert_eq!(bins[1].count, 1);
+}
+
+#[test]
+fn test_histogram_identical_values() {
+    let bins = statistics::histogram(&[3.0, 3.0, 3.0], 5).unwrap();
+    assert_eq!(bins.len(), 1);
+    assert_eq!(bins[0].count, 3);
+}
+
+#[test]
+fn test_histogram_zero_bins() {
+    assert!(statistics::histogram(&[1.0], 0).is_err());
+}
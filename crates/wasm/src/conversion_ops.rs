@@ -11,15 +11,21 @@ pub struct WasmUnit {
 }
 
 // Convert a single value between units
+// Typography settings can follow the target unit, e.g. "rem @base=18"
 #[wasm_bindgen]
 pub fn convert(value: f64, from_unit: &str, to_unit: &str) -> String {
+    let defaults = conversion::typography::TypographySettings::default();
+    let (to_unit, settings) = match conversion::typography::extract_settings(to_unit, defaults) {
+        Ok(extracted) => extracted,
+        Err(e) => return format!("Error: {}", e),
+    };
     let request = conversion::types::ConversionRequest {
         value,
         from_unit: from_unit.to_string(),
-        to_unit: Some(to_unit.to_string()),
+        to_unit: Some(to_unit),
     };
 
-    match conversion::engine::convert(&request) {
+    match conversion::engine::convert_with(&request, &settings) {
         Ok(result) => {
             // Format the result internally before returning
             match conversion::formatter::format_output(&[result], None) {
@@ -31,15 +37,23 @@ pub fn convert(value: f64, from_unit: &str, to_unit: &str) -> String {
     }
 }
 
+// Convert free-form input like "5 ft 11 in to cm" or "180 cm to ft+in"
+#[wasm_bindgen]
+pub fn convert_expression(input: &str) -> String {
+    match conversion::engine::convert_input(input) {
+        Ok(result) => match conversion::formatter::format_output(&[result], None) {
+            Ok(formatted) => formatted,
+            Err(e) => format!("Error: Failed to format result: {}", e),
+        },
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
 // Get all units in a category
 #[wasm_bindgen]
 pub fn get_units_in_category(category: &str) -> String {
-    let category_enum = match category.to_lowercase().as_str() {
-        "bytes" => conversion::types::UnitCategory::Bytes,
-        "time" => conversion::types::UnitCategory::Time,
-        "length" => conversion::types::UnitCategory::Length,
-        "temperature" => conversion::types::UnitCategory::Temperature,
-        _ => return format!("Error: Unknown category: {}", category),
+    let Some(category_enum) = conversion::types::UnitCategory::from_name(category) else {
+        return format!("Error: Unknown category: {}", category);
     };
 
     let units = conversion::types::get_units_in_category(category_enum);
@@ -49,7 +63,7 @@ pub fn get_units_in_category(category: &str) -> String {
         .map(|u| WasmUnit {
             canonical_name: u.canonical_name.to_string(),
             aliases: u.aliases.iter().map(|s| s.to_string()).collect(),
-            category: format!("{:?}", u.category),
+            category: u.category.to_string(),
         })
         .collect();
 
@@ -59,6 +73,32 @@ pub fn get_units_in_category(category: &str) -> String {
     }
 }
 
+// Register extra units from a JSON units file, e.g. {"units": [{"name": "furlong", ...}]}
+#[wasm_bindgen]
+pub fn register_units(input: &str) -> String {
+    match conversion::custom::parse_json(input)
+        .and_then(|units| conversion::custom::register(&units))
+    {
+        Ok(count) => count.to_string(),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Set offline currency rates from JSON, returning the date of the rates
+// e.g. {"base": "USD", "date": "2026-10-01", "rates": {"EUR": 0.92}}
+#[wasm_bindgen]
+pub fn set_currency_rates(input: &str) -> String {
+    let table = match conversion::currency::parse_json(input) {
+        Ok(table) => table,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let date = table.date.clone();
+    match conversion::currency::set_rates(table) {
+        Ok(()) => date,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
 // Convert to all units in the same category
 #[wasm_bindgen]
 pub fn convert_all(value: f64, from_unit: &str) -> String {
@@ -73,3 +113,21 @@ pub fn convert_all(value: f64, from_unit: &str) -> String {
         Err(e) => format!("Error: {}", e),
     }
 }
+
+// Parse a compound ("1h30m15s") or ISO 8601 ("PT1H30M") duration into seconds
+#[wasm_bindgen]
+pub fn parse_duration(input: &str) -> String {
+    match conversion::duration::parse_duration(input) {
+        Ok(seconds) => seconds.to_string(),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Format seconds as a compound ("human") or ISO 8601 ("iso") duration
+#[wasm_bindgen]
+pub fn format_duration(seconds: f64, format: &str) -> String {
+    match conversion::duration::DurationFormat::from_name(format) {
+        Some(format) => format.format(seconds),
+        None => format!("Error: Unknown duration format: {}", format),
+    }
+}
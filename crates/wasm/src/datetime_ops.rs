@@ -1,6 +1,40 @@
-use strapd_core::datetime::timestamp::{self, TimestampFormat};
+use serde::Serialize;
+use strapd_core::datetime::{
+    annotate::{self, AnnotateMode, AnnotateOptions},
+    arithmetic::{self, CalendarDuration},
+    cron::CronSchedule,
+    relative::{self, RelativeOptions, RelativeUnit},
+    timestamp::{self, TimestampFormat, TimestampUnit},
+    timezone::{self, Zone},
+};
 use wasm_bindgen::prelude::*;
 
+#[derive(Serialize)]
+pub struct WasmZoneTime {
+    pub zone: String,
+    pub time: String,
+}
+
+#[derive(Serialize)]
+pub struct WasmCronSchedule {
+    pub description: String,
+    pub next: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct WasmTimeDiff {
+    pub millis: i64,
+    pub duration: String,
+    pub calendar: String,
+}
+
+fn parse_zone(tz: &str) -> Result<Zone, String> {
+    match tz.trim() {
+        "" => Ok(Zone::Local),
+        tz => Zone::parse(tz),
+    }
+}
+
 #[wasm_bindgen]
 pub fn datetime_now(millis: bool) -> i64 {
     match millis {
@@ -9,11 +43,13 @@ pub fn datetime_now(millis: bool) -> i64 {
     }
 }
 
+// format is a preset name (Human, Iso, Rfc2822, Http, Sql, Compact, Date) or a
+// strftime pattern like "%Y-%m-%d %H:%M"
 #[wasm_bindgen]
 pub fn datetime_from_timestamp(timestamp: i64, format: &str) -> String {
-    let format = match format {
-        "Iso" => TimestampFormat::Iso,
-        _ => TimestampFormat::Human,
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
     };
 
     match timestamp::from_timestamp(timestamp, format) {
@@ -24,9 +60,9 @@ pub fn datetime_from_timestamp(timestamp: i64, format: &str) -> String {
 
 #[wasm_bindgen]
 pub fn datetime_from_timestamp_millis(timestamp: i64, format: &str) -> String {
-    let format = match format {
-        "Iso" => TimestampFormat::Iso,
-        _ => TimestampFormat::Human,
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
     };
 
     match timestamp::from_timestamp_millis(timestamp, format) {
@@ -34,3 +70,353 @@ pub fn datetime_from_timestamp_millis(timestamp: i64, format: &str) -> String {
         Err(e) => format!("Error: {}", e),
     }
 }
+
+#[wasm_bindgen]
+pub fn datetime_now_micros() -> i64 {
+    timestamp::now_micros()
+}
+
+#[wasm_bindgen]
+pub fn datetime_now_nanos() -> i64 {
+    timestamp::now_nanos()
+}
+
+#[wasm_bindgen]
+pub fn datetime_from_timestamp_micros(timestamp: i64, format: &str) -> String {
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    match timestamp::from_timestamp_micros(timestamp, format) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn datetime_from_timestamp_nanos(timestamp: i64, format: &str) -> String {
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    match timestamp::from_timestamp_nanos(timestamp, format) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Unit of a timestamp guessed from its magnitude: "s", "ms", "us" or "ns"
+#[wasm_bindgen]
+pub fn datetime_detect_unit(timestamp: i64) -> String {
+    match TimestampUnit::detect(timestamp) {
+        TimestampUnit::Seconds => "s",
+        TimestampUnit::Millis => "ms",
+        TimestampUnit::Micros => "us",
+        TimestampUnit::Nanos => "ns",
+    }
+    .to_string()
+}
+
+// Format a timestamp in a unit ("s", "ms", "us", "ns" or "" to guess it) and a
+// timezone ("" for local)
+#[wasm_bindgen]
+pub fn datetime_from_timestamp_unit(timestamp: i64, unit: &str, format: &str, tz: &str) -> String {
+    let unit = match unit.trim() {
+        "" => Ok(TimestampUnit::detect(timestamp)),
+        unit => TimestampUnit::parse(unit),
+    };
+    let unit = match unit {
+        Ok(unit) => unit,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let zone = match parse_zone(tz) {
+        Ok(zone) => zone,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    match timestamp::from_timestamp_unit_in(timestamp, unit, format, zone) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Format a timestamp in a timezone: an IANA name, "UTC", an offset like "+05:30"
+// or "" for local
+#[wasm_bindgen]
+pub fn datetime_from_timestamp_in(timestamp: i64, millis: bool, format: &str, tz: &str) -> String {
+    let zone = match parse_zone(tz) {
+        Ok(zone) => zone,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let result = match millis {
+        true => timestamp::from_timestamp_millis_in(timestamp, format, zone),
+        false => timestamp::from_timestamp_in(timestamp, format, zone),
+    };
+
+    match result {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Parse a date string into a Unix timestamp, detecting the format unless a
+// strptime pattern is given; tz applies to inputs without an offset ("" for local)
+#[wasm_bindgen]
+pub fn datetime_to_timestamp(input: &str, millis: bool, format: &str, tz: &str) -> String {
+    let zone = match parse_zone(tz) {
+        Ok(zone) => zone,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let pattern = Some(format).filter(|f| !f.trim().is_empty());
+    let result = match millis {
+        true => timestamp::to_timestamp_millis(input, pattern, zone),
+        false => timestamp::to_timestamp(input, pattern, zone),
+    };
+
+    match result {
+        Ok(ts) => ts.to_string(),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Add a duration like "90d" or "1mo 2h" to an instant ("now", a Unix timestamp
+// or a date string); tz is used for calendar days and the output ("" for local)
+#[wasm_bindgen]
+pub fn datetime_add(instant: &str, duration: &str, millis: bool, format: &str, tz: &str) -> String {
+    match shift(instant, duration, millis, format, tz, false) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+// Subtract a duration from an instant, like datetime_add
+#[wasm_bindgen]
+pub fn datetime_sub(instant: &str, duration: &str, millis: bool, format: &str, tz: &str) -> String {
+    match shift(instant, duration, millis, format, tz, true) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+fn shift(
+    instant: &str,
+    duration: &str,
+    millis: bool,
+    format: &str,
+    tz: &str,
+    subtract: bool,
+) -> Result<String, String> {
+    let zone = parse_zone(tz)?;
+    let format = TimestampFormat::parse(format)?;
+    let instant = arithmetic::parse_instant(instant, millis, zone)?;
+    let duration = CalendarDuration::parse(duration)?;
+    let result = match subtract {
+        true => arithmetic::sub(&instant, &duration, zone)?,
+        false => arithmetic::add(&instant, &duration, zone)?,
+    };
+    Ok(timestamp::from_timestamp_millis_in(
+        result.timestamp_millis(),
+        format,
+        zone,
+    )?)
+}
+
+// Time between two instants, as JSON
+// e.g. {"millis": 90000000, "duration": "1d 1h", "calendar": "1d 1h"}
+#[wasm_bindgen]
+pub fn datetime_diff(from: &str, to: &str, millis: bool, tz: &str) -> String {
+    let diff = match time_diff(from, to, millis, tz) {
+        Ok(diff) => diff,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    match serde_json::to_string(&diff) {
+        Ok(json) => json,
+        Err(e) => format!("Error: Failed to serialize diff: {}", e),
+    }
+}
+
+fn time_diff(from: &str, to: &str, millis: bool, tz: &str) -> Result<WasmTimeDiff, String> {
+    let zone = parse_zone(tz)?;
+    let from = arithmetic::parse_instant(from, millis, zone)?;
+    let to = arithmetic::parse_instant(to, millis, zone)?;
+    let delta = arithmetic::diff(&from, &to);
+    Ok(WasmTimeDiff {
+        millis: delta.num_milliseconds(),
+        duration: CalendarDuration::from(delta).to_string(),
+        calendar: arithmetic::calendar_diff(&from, &to, zone).to_string(),
+    })
+}
+
+// Show a timestamp in milliseconds in several comma-separated zones, as JSON
+// e.g. [{"zone": "UTC", "time": "2024-01-01 00:00:00 UTC"}]
+#[wasm_bindgen]
+pub fn datetime_world_clock(timestamp_millis: i64, zones: &str, format: &str) -> String {
+    let zones = match timezone::parse_zone_list(zones) {
+        Ok(zones) => zones,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let format = match TimestampFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let rows = match timestamp::world_clock(timestamp_millis, &zones, format) {
+        Ok(rows) => rows,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    let rows: Vec<WasmZoneTime> = rows
+        .into_iter()
+        .map(|(zone, time)| WasmZoneTime { zone, time })
+        .collect();
+    match serde_json::to_string(&rows) {
+        Ok(json) => json,
+        Err(e) => format!("Error: Failed to serialize zones: {}", e),
+    }
+}
+
+// Distance of an instant from `since` ("now" or "", a Unix timestamp or a date
+// string), e.g. "3 hours ago" or "in 2 days"; granularity is the smallest unit shown
+#[wasm_bindgen]
+pub fn datetime_relative(
+    instant: &str,
+    millis: bool,
+    since: &str,
+    parts: u32,
+    granularity: &str,
+    tz: &str,
+) -> String {
+    match relative_time(instant, millis, since, parts, granularity, tz) {
+        Ok(s) => s,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+fn relative_time(
+    instant: &str,
+    millis: bool,
+    since: &str,
+    parts: u32,
+    granularity: &str,
+    tz: &str,
+) -> Result<String, String> {
+    let zone = parse_zone(tz)?;
+    let instant = arithmetic::parse_instant(instant, millis, zone)?;
+    let since = match since.trim() {
+        "" => arithmetic::parse_instant("now", millis, zone)?,
+        since => arithmetic::parse_instant(since, millis, zone)?,
+    };
+    let options = RelativeOptions {
+        parts: parts.clamp(1, 7) as usize,
+        granularity: match granularity.trim() {
+            "" => RelativeUnit::Second,
+            granularity => RelativeUnit::parse(granularity)?,
+        },
+    };
+    Ok(relative::relative(&instant, &since, zone, &options))
+}
+
+// Names of all IANA timezones, as a JSON array
+#[wasm_bindgen]
+pub fn datetime_timezones() -> String {
+    match serde_json::to_string(&timezone::zone_names()) {
+        Ok(json) => json,
+        Err(e) => format!("Error: Failed to serialize timezones: {}", e),
+    }
+}
+
+// Names of the preset timestamp formats, as a JSON array
+#[wasm_bindgen]
+pub fn datetime_formats() -> String {
+    match serde_json::to_string(&timestamp::FORMAT_NAMES) {
+        Ok(json) => json,
+        Err(e) => format!("Error: Failed to serialize formats: {}", e),
+    }
+}
+
+// Explain a cron expression and list its next runs after an instant ("now" or
+// "", a Unix timestamp or a date string), as JSON
+// e.g. {"description": "At 09:00", "next": ["2025-03-05 09:00:00 UTC"]}
+#[wasm_bindgen]
+pub fn datetime_cron(expression: &str, from: &str, count: u32, format: &str, tz: &str) -> String {
+    let schedule = match cron_schedule(expression, from, count, format, tz) {
+        Ok(schedule) => schedule,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    match serde_json::to_string(&schedule) {
+        Ok(json) => json,
+        Err(e) => format!("Error: Failed to serialize schedule: {}", e),
+    }
+}
+
+fn cron_schedule(
+    expression: &str,
+    from: &str,
+    count: u32,
+    format: &str,
+    tz: &str,
+) -> Result<WasmCronSchedule, String> {
+    let zone = parse_zone(tz)?;
+    let format = TimestampFormat::parse(format)?;
+    let schedule = CronSchedule::parse(expression)?;
+    let from = match from.trim() {
+        "" => arithmetic::parse_instant("now", false, zone)?,
+        from => arithmetic::parse_instant(from, false, zone)?,
+    };
+
+    let next = schedule
+        .next_runs(&from, count.min(1000) as usize, zone)
+        .iter()
+        .map(|run| {
+            timestamp::from_timestamp_millis_in(run.timestamp_millis(), format.clone(), zone)
+                .map_err(String::from)
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    Ok(WasmCronSchedule {
+        description: schedule.describe(),
+        next,
+    })
+}
+
+// Add a readable rendering after each timestamp in a text, or replace it with one;
+// keys is a comma-separated list of JSON keys to limit matching to, "" for all
+#[wasm_bindgen]
+pub fn datetime_annotate(text: &str, format: &str, tz: &str, replace: bool, keys: &str) -> String {
+    match annotate_options(format, tz, replace, keys) {
+        Ok(options) => annotate::annotate(text, &options),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+fn annotate_options(
+    format: &str,
+    tz: &str,
+    replace: bool,
+    keys: &str,
+) -> Result<AnnotateOptions, String> {
+    Ok(AnnotateOptions {
+        format: TimestampFormat::parse(format)?,
+        zone: parse_zone(tz)?,
+        mode: match replace {
+            true => AnnotateMode::Replace,
+            false => AnnotateMode::Append,
+        },
+        keys: keys
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(String::from)
+            .collect(),
+    })
+}
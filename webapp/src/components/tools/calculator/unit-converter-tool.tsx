@@ -125,6 +125,16 @@ export const UnitConverterToolComponent: React.FC<BaseToolProps> = ({
     { value: "time", label: "Time" },
     { value: "length", label: "Length" },
     { value: "temperature", label: "Temperature" },
+    { value: "data_rate", label: "Data Rate" },
+    { value: "mass", label: "Mass" },
+    { value: "volume", label: "Volume" },
+    { value: "area", label: "Area" },
+    { value: "speed", label: "Speed" },
+    { value: "angle", label: "Angle" },
+    { value: "energy", label: "Energy" },
+    { value: "pressure", label: "Pressure" },
+    { value: "frequency", label: "Frequency" },
+    { value: "typography", label: "Typography" },
   ];
 
   return (
@@ -158,6 +158,11 @@ export const TimestampToolComponent: React.FC<BaseToolProps> = ({
               value={String(outputs.result || "")}
               placeholder="Converted date will appear here..."
             />
+            {outputs.relative ? (
+              <Text fontSize="sm" color="text.secondary">
+                {String(outputs.relative)}
+              </Text>
+            ) : null}
           </VStack>
         </HStack>
       </VStack>
@@ -11,12 +11,13 @@ export const now = (millis = false) => {
 /**
  * Convert timestamp to human-readable date or ISO string
  * @param timestamp Unix timestamp
- * @param format Output format ('Human' or 'Iso')
+ * @param format Output format: a preset ('Human', 'Iso', 'Rfc2822', 'Http', 'Sql',
+ * 'Compact', 'Date') or a strftime pattern like '%Y-%m-%d %H:%M'
  * @param isMillis Whether input is in milliseconds
  */
 export const fromTimestamp = (
   timestamp: number,
-  format: "Human" | "Iso" = "Human",
+  format: string = "Human",
   isMillis = false,
 ) => {
   if (isMillis) {
@@ -24,3 +25,20 @@ export const fromTimestamp = (
   }
   return wasmWrapper.datetime_from_timestamp(timestamp, format);
 };
+
+/**
+ * Describe a timestamp relative to now, e.g. "3 hours ago" or "in 2 days"
+ * @param timestamp Unix timestamp
+ * @param isMillis Whether input is in milliseconds
+ * @param parts Most units to show, e.g. 2 for "1 day, 3 hours ago"
+ */
+export const relativeTime = (timestamp: number, isMillis = false, parts = 1) => {
+  return wasmWrapper.datetime_relative(
+    String(timestamp),
+    isMillis,
+    "",
+    parts,
+    "",
+    "",
+  );
+};
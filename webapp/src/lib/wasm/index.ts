@@ -59,6 +59,14 @@ export interface WasmModule {
   datetime_now: (millis: boolean) => bigint;
   datetime_from_timestamp: (timestamp: bigint, format: string) => string;
   datetime_from_timestamp_millis: (timestamp: bigint, format: string) => string;
+  datetime_relative: (
+    instant: string,
+    millis: boolean,
+    since: string,
+    parts: number,
+    granularity: string,
+    tz: string,
+  ) => string;
 
   // Unit Conversion
   convert: (value: number, from_unit: string, to_unit: string) => string;
@@ -374,20 +382,34 @@ export class WasmWrapper {
     );
   }
 
-  public datetime_from_timestamp(timestamp: number, format: 'Human' | 'Iso'): ToolResult {
+  public datetime_from_timestamp(timestamp: number, format: string): ToolResult {
     return this.safeWasmCall(
       () => this.wasmModule.datetime_from_timestamp(BigInt(timestamp), format),
       'datetime_from_timestamp'
     );
   }
 
-  public datetime_from_timestamp_millis(timestamp: number, format: 'Human' | 'Iso'): ToolResult {
+  public datetime_from_timestamp_millis(timestamp: number, format: string): ToolResult {
     return this.safeWasmCall(
       () => this.wasmModule.datetime_from_timestamp_millis(BigInt(timestamp), format),
       'datetime_from_timestamp_millis'
     );
   }
 
+  public datetime_relative(
+    instant: string,
+    millis: boolean,
+    since: string,
+    parts: number,
+    granularity: string,
+    tz: string
+  ): ToolResult {
+    return this.safeWasmCall(
+      () => this.wasmModule.datetime_relative(instant, millis, since, parts, granularity, tz),
+      'datetime_relative'
+    );
+  }
+
   // Unit Conversion
   public convert(value: number, from_unit: string, to_unit: string): ToolResult {
     return this.safeWasmCall(
@@ -3,10 +3,15 @@
 import { TimestampToolComponent } from "../components/tools";
 import type { ToolDefinition } from "../components/tools/base-tool";
 import { CATEGORY_ICONS } from "../constants/category-icons";
-import { fromTimestamp, now } from "../lib/utils/datetime";
+import { fromTimestamp, now, relativeTime } from "../lib/utils/datetime";
 import type { Tool, ToolGroup } from "../types";
 
-const timestampToolDefinition: ToolDefinition = {
+// Type definition for timestamp tool result
+type TimestampResult = {
+  relative?: string;
+};
+
+const timestampToolDefinition: ToolDefinition<TimestampResult> = {
   id: "datetime-timestamp",
   name: "Timestamp Converter",
   description: "Convert Unix timestamps to human readable",
@@ -35,7 +40,13 @@ const timestampToolDefinition: ToolDefinition = {
     }
 
     const result = fromTimestamp(timestamp, format, isMillis);
-    return result;
+    if (!result.success) {
+      return result;
+    }
+
+    // Shown next to the date, e.g. "3 hours ago"
+    const relative = relativeTime(timestamp, isMillis, 2);
+    return { ...result, relative: relative.success ? relative.result : "" };
   },
 };
 